@@ -1,42 +1,213 @@
 use crate::{
-    Block128,
-    key::{Key, Subkeys},
+    Backend, Block128,
+    backend::{aesni, bitsliced, ttable},
+    key::{Key, KeySize, MAX_ROUNDS, Subkeys},
     operations::{
         add_round_key, inv_mix_columns, inv_shift_rows, inv_sub_bytes, mix_columns, shift_rows,
         sub_bytes,
     },
+    trace::Trace,
 };
-use cipher_core::{BlockCipher, CipherAction, CipherError};
+use cipher_core::{BlockCipher, CipherAction, CipherError, CipherResult};
 
 pub struct Aes {
     subkeys: Subkeys,
+    backend: Backend,
+    final_mix_columns: bool,
 }
 
 impl Aes {
+    /// Most rounds [`with_rounds`](Self::with_rounds) accepts.
+    pub const MAX_ROUNDS: usize = MAX_ROUNDS;
+
     pub fn new(key: impl Into<Key>) -> Self {
+        Self::with_backend(key, Backend::default())
+    }
+
+    /// Creates a cipher that encrypts and decrypts through `backend`.
+    ///
+    /// The key is expanded by the same backend, so a constant-time backend
+    /// never looks up the S-box table with key bytes. If the CPU does not
+    /// support [`Backend::AesNi`], the T-table backend is used instead.
+    #[must_use]
+    pub fn with_backend(key: impl Into<Key>, backend: Backend) -> Self {
+        let backend = if backend.is_available() {
+            backend
+        } else {
+            Backend::TTable
+        };
         Self {
-            subkeys: Subkeys::from_key(&key.into()),
+            subkeys: Subkeys::from_key_with(&key.into(), |word| backend.sub_word(word)),
+            backend,
+            final_mix_columns: false,
         }
     }
 
+    /// Creates a cipher with `rounds` rounds instead of the standard 10, 12
+    /// or 14, for cryptanalysis of reduced-round AES.
+    ///
+    /// The key schedule stops early or keeps expanding to provide
+    /// `rounds + 1` round keys. The standard cipher omits `MixColumns` from
+    /// the final round; pass `final_mix_columns` to keep it, as some
+    /// published reduced-round results do. Such a cipher runs on the
+    /// reference backend, the only one with a full final round.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidRounds` if `rounds` is not between 1 and
+    /// [`MAX_ROUNDS`](Self::MAX_ROUNDS).
+    pub fn with_rounds(
+        key: impl Into<Key>,
+        rounds: usize,
+        final_mix_columns: bool,
+    ) -> CipherResult<Self> {
+        if !(1..=MAX_ROUNDS).contains(&rounds) {
+            return Err(CipherError::invalid_rounds(1, MAX_ROUNDS, rounds));
+        }
+
+        let backend = if final_mix_columns {
+            Backend::Reference
+        } else {
+            Backend::default()
+        };
+        Ok(Self {
+            subkeys: Subkeys::from_key_with_rounds(&key.into(), rounds, |word| {
+                backend.sub_word(word)
+            }),
+            backend,
+            final_mix_columns,
+        })
+    }
+
     #[inline]
     #[must_use]
     pub fn from_key(key: impl Into<Key>) -> Self {
         Self::new(key)
     }
 
-    #[cfg(test)]
+    /// Returns the key size this cipher was created with.
+    #[inline]
+    #[must_use]
+    pub const fn key_size(&self) -> KeySize {
+        self.subkeys.key_size()
+    }
+
+    /// Returns the number of rounds: 10, 12 or 14 depending on the key size,
+    /// unless set by [`with_rounds`](Self::with_rounds).
+    #[inline]
+    #[must_use]
+    pub const fn rounds(&self) -> usize {
+        self.subkeys.rounds()
+    }
+
+    /// Returns whether the final round keeps `MixColumns`.
+    #[inline]
+    #[must_use]
+    pub const fn final_mix_columns(&self) -> bool {
+        self.final_mix_columns
+    }
+
+    /// Returns the backend this cipher runs on.
+    #[inline]
+    #[must_use]
+    pub const fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Encrypts every block in place.
+    ///
+    /// The bitsliced backend processes four blocks per pass and the AES-NI
+    /// backend loads the round keys once per batch, so batching blocks is
+    /// faster than encrypting them one by one.
+    pub fn encrypt_blocks(&self, blocks: &mut [[u8; 16]]) {
+        match self.backend {
+            Backend::Bitsliced => bitsliced::encrypt_blocks(&self.subkeys, blocks),
+            Backend::AesNi => aesni::encrypt_blocks(&self.subkeys, blocks),
+            Backend::Reference | Backend::TTable => {
+                for block in blocks {
+                    *block = self
+                        .encrypt_block(Block128::from_be_bytes(*block))
+                        .to_be_bytes();
+                }
+            }
+        }
+    }
+
+    /// Decrypts every block in place.
+    pub fn decrypt_blocks(&self, blocks: &mut [[u8; 16]]) {
+        match self.backend {
+            Backend::Bitsliced => bitsliced::decrypt_blocks(&self.subkeys, blocks),
+            Backend::AesNi => aesni::decrypt_blocks(&self.subkeys, blocks),
+            Backend::Reference | Backend::TTable => {
+                for block in blocks {
+                    *block = self
+                        .decrypt_block(Block128::from_be_bytes(*block))
+                        .to_be_bytes();
+                }
+            }
+        }
+    }
+
+    /// Encrypts one block and records the state after every step of every
+    /// round, as in FIPS-197 Appendix B.
+    ///
+    /// The trace always runs the reference rounds, whatever the backend, so
+    /// it is meant for teaching and debugging rather than bulk encryption.
+    #[must_use]
+    pub fn trace_encrypt(&self, block: [u8; 16]) -> Trace {
+        Trace::encrypt(&self.subkeys, block, self.final_mix_columns)
+    }
+
+    /// Returns the expanded round keys.
     #[inline]
     #[must_use]
     pub const fn subkeys(&self) -> &Subkeys {
         &self.subkeys
     }
 
-    pub(crate) fn encrypt_block(&self, mut state: Block128) -> Block128 {
+    pub(crate) fn encrypt_block(&self, state: Block128) -> Block128 {
+        match self.backend {
+            Backend::Reference => self.encrypt_reference(state),
+            Backend::TTable => {
+                Block128::from_be_bytes(ttable::encrypt_block(&self.subkeys, state.to_be_bytes()))
+            }
+            Backend::Bitsliced => {
+                let mut blocks = [state.to_be_bytes()];
+                bitsliced::encrypt_blocks(&self.subkeys, &mut blocks);
+                Block128::from_be_bytes(blocks[0])
+            }
+            Backend::AesNi => {
+                let mut blocks = [state.to_be_bytes()];
+                aesni::encrypt_blocks(&self.subkeys, &mut blocks);
+                Block128::from_be_bytes(blocks[0])
+            }
+        }
+    }
+
+    pub(crate) fn decrypt_block(&self, state: Block128) -> Block128 {
+        match self.backend {
+            Backend::Reference => self.decrypt_reference(state),
+            Backend::TTable => {
+                Block128::from_be_bytes(ttable::decrypt_block(&self.subkeys, state.to_be_bytes()))
+            }
+            Backend::Bitsliced => {
+                let mut blocks = [state.to_be_bytes()];
+                bitsliced::decrypt_blocks(&self.subkeys, &mut blocks);
+                Block128::from_be_bytes(blocks[0])
+            }
+            Backend::AesNi => {
+                let mut blocks = [state.to_be_bytes()];
+                aesni::decrypt_blocks(&self.subkeys, &mut blocks);
+                Block128::from_be_bytes(blocks[0])
+            }
+        }
+    }
+
+    fn encrypt_reference(&self, mut state: Block128) -> Block128 {
         let mut keys = self.subkeys.chunks();
         state = add_round_key(state, keys.next().expect("Round key 0"));
 
-        for _ in 1..10 {
+        for _ in 1..self.rounds() {
             state = sub_bytes(state);
             state = shift_rows(state);
             state = mix_columns(state);
@@ -46,25 +217,33 @@ impl Aes {
         // Final round: SubBytes, ShiftRows, AddRoundKey (no MixColumns)
         state = sub_bytes(state);
         state = shift_rows(state);
+        if self.final_mix_columns {
+            state = mix_columns(state);
+        }
         state = add_round_key(state, keys.next().expect("Final Round key"));
 
         state
     }
 
-    pub(crate) fn decrypt_block(&self, mut state: Block128) -> Block128 {
-        let mut keys = self.subkeys.chunks_rev();
+    /// Decrypts with the equivalent inverse cipher (FIPS-197 section 5.3.5),
+    /// which runs its steps in the same order as encryption.
+    fn decrypt_reference(&self, mut state: Block128) -> Block128 {
+        let mut keys = self.subkeys.decryption_chunks_rev();
         state = add_round_key(state, keys.next().expect("Final round key"));
+        if self.final_mix_columns {
+            state = inv_mix_columns(state);
+        }
 
-        for _ in 1..10 {
-            state = inv_shift_rows(state);
+        for _ in 1..self.rounds() {
             state = inv_sub_bytes(state);
-            state = add_round_key(state, keys.next().expect("Round key"));
+            state = inv_shift_rows(state);
             state = inv_mix_columns(state);
+            state = add_round_key(state, keys.next().expect("Round key"));
         }
 
-        // Final round: SubBytes, ShiftRows, AddRoundKey (no MixColumns)
-        state = inv_shift_rows(state);
+        // Final round: InvSubBytes, InvShiftRows, AddRoundKey (no InvMixColumns)
         state = inv_sub_bytes(state);
+        state = inv_shift_rows(state);
         state = add_round_key(state, keys.next().expect("Round key 0"));
 
         state
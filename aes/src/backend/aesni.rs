@@ -0,0 +1,166 @@
+//! Hardware AES through the x86-64 AES-NI instructions.
+//!
+//! `aesenc` and `aesdec` run a whole round in one instruction and
+//! `aeskeygenassist` computes `SubWord` for the key schedule, all in constant
+//! time. Support is detected at runtime; on CPUs without AES-NI, or on other
+//! architectures, every function here falls back to the T-table backend.
+
+use crate::{backend::ttable, key::Subkeys, sbox::SboxLookup};
+
+/// Returns whether the CPU supports the AES-NI instructions.
+#[must_use]
+pub fn is_available() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        std::arch::is_x86_feature_detected!("aes")
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        false
+    }
+}
+
+/// Encrypts every block in place with the expanded key.
+pub fn encrypt_blocks(subkeys: &Subkeys, blocks: &mut [[u8; 16]]) {
+    #[cfg(target_arch = "x86_64")]
+    if is_available() {
+        // SAFETY: the CPU supports AES-NI, and SSE2 is part of x86-64
+        unsafe { x86::encrypt_blocks(subkeys, blocks) };
+        return;
+    }
+    for block in blocks {
+        *block = ttable::encrypt_block(subkeys, *block);
+    }
+}
+
+/// Decrypts every block in place with the expanded key.
+pub fn decrypt_blocks(subkeys: &Subkeys, blocks: &mut [[u8; 16]]) {
+    #[cfg(target_arch = "x86_64")]
+    if is_available() {
+        // SAFETY: the CPU supports AES-NI, and SSE2 is part of x86-64
+        unsafe { x86::decrypt_blocks(subkeys, blocks) };
+        return;
+    }
+    for block in blocks {
+        *block = ttable::decrypt_block(subkeys, *block);
+    }
+}
+
+/// Applies the S-box to each byte of a key schedule word.
+pub fn sub_word(word: u32) -> u32 {
+    #[cfg(target_arch = "x86_64")]
+    if is_available() {
+        // SAFETY: the CPU supports AES-NI, and SSE2 is part of x86-64
+        return unsafe { x86::sub_word(word) };
+    }
+    word.sbox_lookup()
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use crate::key::{MAX_ROUNDS, Subkey, Subkeys};
+    use std::arch::x86_64::{
+        __m128i, _mm_aesdec_si128, _mm_aesdeclast_si128, _mm_aesenc_si128, _mm_aesenclast_si128,
+        _mm_aeskeygenassist_si128, _mm_cvtsi32_si128, _mm_cvtsi128_si32, _mm_cvtsi128_si64,
+        _mm_set_epi64x, _mm_setzero_si128, _mm_shuffle_epi32, _mm_srli_si128, _mm_xor_si128,
+    };
+
+    #[target_feature(enable = "aes")]
+    pub fn encrypt_blocks(subkeys: &Subkeys, blocks: &mut [[u8; 16]]) {
+        let rounds = subkeys.rounds();
+        let keys = round_keys(subkeys.as_slice());
+
+        for block in blocks {
+            let mut state = _mm_xor_si128(load(block), keys[0]);
+            for key in &keys[1..rounds] {
+                state = _mm_aesenc_si128(state, *key);
+            }
+            *block = store(_mm_aesenclast_si128(state, keys[rounds]));
+        }
+    }
+
+    #[target_feature(enable = "aes")]
+    pub fn decrypt_blocks(subkeys: &Subkeys, blocks: &mut [[u8; 16]]) {
+        let rounds = subkeys.rounds();
+        // aesdec implements the equivalent inverse cipher
+        let keys = round_keys(subkeys.decryption_keys());
+
+        for block in blocks {
+            let mut state = _mm_xor_si128(load(block), keys[rounds]);
+            for key in keys[1..rounds].iter().rev() {
+                state = _mm_aesdec_si128(state, *key);
+            }
+            *block = store(_mm_aesdeclast_si128(state, keys[0]));
+        }
+    }
+
+    #[target_feature(enable = "aes")]
+    pub fn sub_word(word: u32) -> u32 {
+        // aeskeygenassist writes SubWord of dword 1 into dword 0
+        let input = _mm_shuffle_epi32::<0b0000_0000>(_mm_cvtsi32_si128(i32::from_ne_bytes(
+            word.to_ne_bytes(),
+        )));
+        let output = _mm_aeskeygenassist_si128::<0>(input);
+        u32::from_ne_bytes(_mm_cvtsi128_si32(output).to_ne_bytes())
+    }
+
+    /// Loads the round keys into registers, in FIPS-197 byte order.
+    #[target_feature(enable = "aes")]
+    fn round_keys(words: &[Subkey]) -> [__m128i; MAX_ROUNDS + 1] {
+        let mut keys = [_mm_setzero_si128(); MAX_ROUNDS + 1];
+        for (key, round) in keys.iter_mut().zip(words.chunks_exact(4)) {
+            let mut bytes = [0; 16];
+            for (chunk, word) in bytes.chunks_exact_mut(4).zip(round) {
+                chunk.copy_from_slice(&word.to_be_bytes());
+            }
+            *key = load(&bytes);
+        }
+        keys
+    }
+
+    #[target_feature(enable = "aes")]
+    fn load(block: &[u8; 16]) -> __m128i {
+        let (low, high) = block.split_at(8);
+        let word = |half: &[u8]| i64::from_le_bytes(half.try_into().expect("8-byte half"));
+        _mm_set_epi64x(word(high), word(low))
+    }
+
+    #[target_feature(enable = "aes")]
+    fn store(state: __m128i) -> [u8; 16] {
+        let mut block = [0; 16];
+        block[..8].copy_from_slice(&_mm_cvtsi128_si64(state).to_le_bytes());
+        block[8..].copy_from_slice(&_mm_cvtsi128_si64(_mm_srli_si128::<8>(state)).to_le_bytes());
+        block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::Key;
+
+    // FIPS-197 Appendix C.1
+    #[test]
+    fn fips197_example() {
+        let subkeys = Subkeys::from_key_with(
+            &Key::from(0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f_u128),
+            sub_word,
+        );
+        let plaintext = 0x0011_2233_4455_6677_8899_aabb_ccdd_eeff_u128.to_be_bytes();
+        let ciphertext = 0x69c4_e0d8_6a7b_0430_d8cd_b780_70b4_c55a_u128.to_be_bytes();
+
+        let mut blocks = [plaintext];
+        encrypt_blocks(&subkeys, &mut blocks);
+        assert_eq!(blocks, [ciphertext]);
+        decrypt_blocks(&subkeys, &mut blocks);
+        assert_eq!(blocks, [plaintext]);
+    }
+
+    #[test]
+    fn sub_word_matches_sbox() {
+        for byte in 0..=u8::MAX {
+            let word = u32::from_be_bytes([byte, byte ^ 0x5A, byte.rotate_left(3), !byte]);
+            assert_eq!(sub_word(word), word.sbox_lookup(), "{word:08x}");
+        }
+    }
+}
@@ -0,0 +1,447 @@
+//! Constant-time bitsliced AES.
+//!
+//! Up to four blocks are packed into eight 64-bit planes: bit `16 * b + i` of
+//! plane `p` is bit `p` of byte `i` of block `b`, with bytes in FIPS-197 order.
+//! `SubBytes` is evaluated as the Boyar–Peralta Boolean circuit on all 64
+//! bytes at once, and the other steps are fixed shifts and masks, so no
+//! memory access or branch depends on the key or the data.
+
+use crate::key::{SubkeyChunks, Subkeys};
+use zeroize::Zeroizing;
+
+/// Number of blocks processed in parallel.
+pub const PARALLEL_BLOCKS: usize = 4;
+
+const BLOCK_SIZE: usize = 16;
+
+/// One block's worth of bits at the lowest position of each block lane.
+const LANES: u64 = 0x0001_0001_0001_0001;
+
+/// Source byte for every destination byte of `ShiftRows`.
+const SHIFT_ROWS: [usize; BLOCK_SIZE] = [0, 5, 10, 15, 4, 9, 14, 3, 8, 13, 2, 7, 12, 1, 6, 11];
+
+/// Source byte for every destination byte of `InvShiftRows`.
+const INV_SHIFT_ROWS: [usize; BLOCK_SIZE] = [0, 13, 10, 7, 4, 1, 14, 11, 8, 5, 2, 15, 12, 9, 6, 3];
+
+type Planes = [u64; 8];
+
+/// Encrypts every block in place with the expanded key.
+pub fn encrypt_blocks(subkeys: &Subkeys, blocks: &mut [[u8; BLOCK_SIZE]]) {
+    let keys = key_planes(subkeys.chunks());
+    let (last, middle) = keys[1..].split_last().expect("at least one round");
+
+    for batch in blocks.chunks_mut(PARALLEL_BLOCKS) {
+        let mut q = Zeroizing::new(pack(batch));
+        add_round_key(&mut q, &keys[0]);
+        for key in middle {
+            sub_bytes(&mut q);
+            shift_rows(&mut q);
+            mix_columns(&mut q);
+            add_round_key(&mut q, key);
+        }
+        sub_bytes(&mut q);
+        shift_rows(&mut q);
+        add_round_key(&mut q, last);
+        unpack(&q, batch);
+    }
+}
+
+/// Decrypts every block in place with the expanded key.
+pub fn decrypt_blocks(subkeys: &Subkeys, blocks: &mut [[u8; BLOCK_SIZE]]) {
+    // The equivalent inverse cipher, with InvMixColumns folded into the
+    // cached middle round keys
+    let keys = key_planes(subkeys.decryption_chunks());
+    let (first, middle) = keys[..keys.len() - 1]
+        .split_first()
+        .expect("at least one round");
+    let last = &keys[keys.len() - 1];
+
+    for batch in blocks.chunks_mut(PARALLEL_BLOCKS) {
+        let mut q = Zeroizing::new(pack(batch));
+        add_round_key(&mut q, last);
+        for key in middle.iter().rev() {
+            inv_sub_bytes(&mut q);
+            inv_shift_rows(&mut q);
+            inv_mix_columns(&mut q);
+            add_round_key(&mut q, key);
+        }
+        inv_sub_bytes(&mut q);
+        inv_shift_rows(&mut q);
+        add_round_key(&mut q, first);
+        unpack(&q, batch);
+    }
+}
+
+/// Applies the S-box to each byte of a key schedule word without tables.
+pub fn sub_word(word: u32) -> u32 {
+    let mut block = [[0; BLOCK_SIZE]];
+    block[0][..4].copy_from_slice(&word.to_be_bytes());
+    let mut q = pack(&block);
+    sub_bytes(&mut q);
+    unpack(&q, &mut block);
+    let [b0, b1, b2, b3, ..] = block[0];
+    u32::from_be_bytes([b0, b1, b2, b3])
+}
+
+/// Bitslices every round key, replicated into all block lanes.
+fn key_planes(round_keys: SubkeyChunks<'_>) -> Zeroizing<Vec<Planes>> {
+    Zeroizing::new(
+        round_keys
+            .map(|words| {
+                let mut key = Zeroizing::new([[0; BLOCK_SIZE]]);
+                for (bytes, word) in key[0].chunks_exact_mut(4).zip(words) {
+                    bytes.copy_from_slice(&word.to_be_bytes());
+                }
+                pack(&*key).map(|plane| plane * LANES)
+            })
+            .collect(),
+    )
+}
+
+/// Transposes up to four blocks into bit planes.
+fn pack(blocks: &[[u8; BLOCK_SIZE]]) -> Planes {
+    let mut q = [0; 8];
+    for (b, block) in blocks.iter().enumerate() {
+        for (i, &byte) in block.iter().enumerate() {
+            for (p, plane) in q.iter_mut().enumerate() {
+                *plane |= u64::from((byte >> p) & 1) << (BLOCK_SIZE * b + i);
+            }
+        }
+    }
+    q
+}
+
+/// Transposes bit planes back into blocks.
+#[allow(clippy::cast_possible_truncation)]
+fn unpack(q: &Planes, blocks: &mut [[u8; BLOCK_SIZE]]) {
+    for (b, block) in blocks.iter_mut().enumerate() {
+        for (i, byte) in block.iter_mut().enumerate() {
+            *byte = q.iter().enumerate().fold(0, |acc, (p, plane)| {
+                acc | ((((plane >> (BLOCK_SIZE * b + i)) & 1) as u8) << p)
+            });
+        }
+    }
+}
+
+fn add_round_key(q: &mut Planes, key: &Planes) {
+    for (plane, k) in q.iter_mut().zip(key) {
+        *plane ^= k;
+    }
+}
+
+/// Moves byte `map[i]` of every block lane to byte `i`.
+fn permute(plane: u64, map: &[usize; BLOCK_SIZE]) -> u64 {
+    map.iter().enumerate().fold(0, |acc, (dst, &src)| {
+        acc | (((plane >> src) & LANES) << dst)
+    })
+}
+
+fn shift_rows(q: &mut Planes) {
+    for plane in q {
+        *plane = permute(*plane, &SHIFT_ROWS);
+    }
+}
+
+fn inv_shift_rows(q: &mut Planes) {
+    for plane in q {
+        *plane = permute(*plane, &INV_SHIFT_ROWS);
+    }
+}
+
+/// Replaces byte `r` of every column with byte `(r + n) % 4`.
+const fn rotate_rows(plane: u64, n: u32) -> u64 {
+    let low = 0x1111_1111_1111_1111 * ((1 << (4 - n)) - 1);
+    ((plane >> n) & low) | ((plane << (4 - n)) & !low)
+}
+
+/// Multiplies every byte by `x` in GF(2^8).
+const fn xtime(q: &Planes) -> Planes {
+    let hi = q[7];
+    [
+        q[7],
+        q[0] ^ hi,
+        q[1],
+        q[2] ^ hi,
+        q[3] ^ hi,
+        q[4],
+        q[5],
+        q[6],
+    ]
+}
+
+/// `out_r = 2 * a_r + 3 * a_{r+1} + a_{r+2} + a_{r+3}` for every column.
+fn mix_columns(q: &mut Planes) {
+    let r1 = q.map(|p| rotate_rows(p, 1));
+    let mut sum = [0; 8];
+    for (i, s) in sum.iter_mut().enumerate() {
+        *s = q[i] ^ r1[i];
+    }
+    let doubled = xtime(&sum);
+    for (i, plane) in q.iter_mut().enumerate() {
+        *plane = doubled[i] ^ r1[i] ^ rotate_rows(*plane, 2) ^ rotate_rows(*plane, 3);
+    }
+}
+
+/// `InvMixColumns` as `MixColumns` after multiplying by `4x^2 + 5`.
+fn inv_mix_columns(q: &mut Planes) {
+    let mut sum = [0; 8];
+    for (s, plane) in sum.iter_mut().zip(q.iter()) {
+        *s = plane ^ rotate_rows(*plane, 2);
+    }
+    let quadrupled = xtime(&xtime(&sum));
+    for (plane, u) in q.iter_mut().zip(quadrupled) {
+        *plane ^= u;
+    }
+    mix_columns(q);
+}
+
+/// The inverse of the S-box's affine map, `A^-1`, without the constant.
+const fn inv_affine(q: &Planes) -> Planes {
+    let mut out = [0; 8];
+    let mut i = 0;
+    while i < 8 {
+        out[i] = q[(i + 2) % 8] ^ q[(i + 5) % 8] ^ q[(i + 7) % 8];
+        i += 1;
+    }
+    out
+}
+
+/// `InvSubBytes(x) = A^-1(SubBytes(A^-1(x)))`, since `SubBytes = A o inverse`.
+const fn inv_sub_bytes(q: &mut Planes) {
+    // The affine constant 0x63 maps to 0x05 through A^-1
+    let mut x = inv_affine(q);
+    x[0] = !x[0];
+    x[2] = !x[2];
+    sub_bytes(&mut x);
+    *q = inv_affine(&x);
+    q[0] = !q[0];
+    q[2] = !q[2];
+}
+
+/// The AES S-box as the Boyar–Peralta circuit (113 gates).
+#[allow(
+    clippy::many_single_char_names,
+    clippy::similar_names,
+    clippy::too_many_lines
+)]
+const fn sub_bytes(q: &mut Planes) {
+    let x0 = q[7];
+    let x1 = q[6];
+    let x2 = q[5];
+    let x3 = q[4];
+    let x4 = q[3];
+    let x5 = q[2];
+    let x6 = q[1];
+    let x7 = q[0];
+
+    // Top linear transformation
+    let y14 = x3 ^ x5;
+    let y13 = x0 ^ x6;
+    let y9 = x0 ^ x3;
+    let y8 = x0 ^ x5;
+    let t0 = x1 ^ x2;
+    let y1 = t0 ^ x7;
+    let y4 = y1 ^ x3;
+    let y12 = y13 ^ y14;
+    let y2 = y1 ^ x0;
+    let y5 = y1 ^ x6;
+    let y3 = y5 ^ y8;
+    let t1 = x4 ^ y12;
+    let y15 = t1 ^ x5;
+    let y20 = t1 ^ x1;
+    let y6 = y15 ^ x7;
+    let y10 = y15 ^ t0;
+    let y11 = y20 ^ y9;
+    let y7 = x7 ^ y11;
+    let y17 = y10 ^ y11;
+    let y19 = y10 ^ y8;
+    let y16 = t0 ^ y11;
+    let y21 = y13 ^ y16;
+    let y18 = x0 ^ y16;
+
+    // Non-linear section: inversion in GF(2^8)
+    let t2 = y12 & y15;
+    let t3 = y3 & y6;
+    let t4 = t3 ^ t2;
+    let t5 = y4 & x7;
+    let t6 = t5 ^ t2;
+    let t7 = y13 & y16;
+    let t8 = y5 & y1;
+    let t9 = t8 ^ t7;
+    let t10 = y2 & y7;
+    let t11 = t10 ^ t7;
+    let t12 = y9 & y11;
+    let t13 = y14 & y17;
+    let t14 = t13 ^ t12;
+    let t15 = y8 & y10;
+    let t16 = t15 ^ t12;
+    let t17 = t4 ^ t14;
+    let t18 = t6 ^ t16;
+    let t19 = t9 ^ t14;
+    let t20 = t11 ^ t16;
+    let t21 = t17 ^ y20;
+    let t22 = t18 ^ y19;
+    let t23 = t19 ^ y21;
+    let t24 = t20 ^ y18;
+
+    let t25 = t21 ^ t22;
+    let t26 = t21 & t23;
+    let t27 = t24 ^ t26;
+    let t28 = t25 & t27;
+    let t29 = t28 ^ t22;
+    let t30 = t23 ^ t24;
+    let t31 = t22 ^ t26;
+    let t32 = t31 & t30;
+    let t33 = t32 ^ t24;
+    let t34 = t23 ^ t33;
+    let t35 = t27 ^ t33;
+    let t36 = t24 & t35;
+    let t37 = t36 ^ t34;
+    let t38 = t27 ^ t36;
+    let t39 = t29 & t38;
+    let t40 = t25 ^ t39;
+
+    let t41 = t40 ^ t37;
+    let t42 = t29 ^ t33;
+    let t43 = t29 ^ t40;
+    let t44 = t33 ^ t37;
+    let t45 = t42 ^ t41;
+    let z0 = t44 & y15;
+    let z1 = t37 & y6;
+    let z2 = t33 & x7;
+    let z3 = t43 & y16;
+    let z4 = t40 & y1;
+    let z5 = t29 & y7;
+    let z6 = t42 & y11;
+    let z7 = t45 & y17;
+    let z8 = t41 & y10;
+    let z9 = t44 & y12;
+    let z10 = t37 & y3;
+    let z11 = t33 & y4;
+    let z12 = t43 & y13;
+    let z13 = t40 & y5;
+    let z14 = t29 & y2;
+    let z15 = t42 & y9;
+    let z16 = t45 & y14;
+    let z17 = t41 & y8;
+
+    // Bottom linear transformation
+    let t46 = z15 ^ z16;
+    let t47 = z10 ^ z11;
+    let t48 = z5 ^ z13;
+    let t49 = z9 ^ z10;
+    let t50 = z2 ^ z12;
+    let t51 = z2 ^ z5;
+    let t52 = z7 ^ z8;
+    let t53 = z0 ^ z3;
+    let t54 = z6 ^ z7;
+    let t55 = z16 ^ z17;
+    let t56 = z12 ^ t48;
+    let t57 = t50 ^ t53;
+    let t58 = z4 ^ t46;
+    let t59 = z3 ^ t54;
+    let t60 = t46 ^ t57;
+    let t61 = z14 ^ t57;
+    let t62 = t52 ^ t58;
+    let t63 = t49 ^ t58;
+    let t64 = z4 ^ t59;
+    let t65 = t61 ^ t62;
+    let t66 = z1 ^ t63;
+    let s0 = t59 ^ t63;
+    let s6 = t56 ^ !t62;
+    let s7 = t48 ^ !t60;
+    let t67 = t64 ^ t65;
+    let s3 = t53 ^ t66;
+    let s4 = t51 ^ t66;
+    let s5 = t47 ^ t65;
+    let s1 = t64 ^ !s3;
+    let s2 = t55 ^ !t67;
+
+    *q = [s7, s6, s5, s4, s3, s2, s1, s0];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Block128,
+        key::Key,
+        operations::{inv_mix_columns as ref_inv_mix, mix_columns as ref_mix},
+        sbox::SboxLookup,
+    };
+
+    /// Sixteen blocks holding every byte value once, in the order given by `map`.
+    fn blocks(map: impl Fn(u8) -> u8) -> Vec<[u8; BLOCK_SIZE]> {
+        let bytes: Vec<u8> = (0..=u8::MAX).map(map).collect();
+        bytes
+            .chunks_exact(BLOCK_SIZE)
+            .map(|chunk| chunk.try_into().expect("16-byte chunk"))
+            .collect()
+    }
+
+    fn sample_blocks() -> Vec<[u8; BLOCK_SIZE]> {
+        blocks(|b| b.wrapping_mul(167).wrapping_add(13))
+    }
+
+    #[test]
+    fn sbox_matches_table() {
+        for byte in 0..=u8::MAX {
+            let word = u32::from_be_bytes([byte, !byte, byte ^ 0x5A, byte.rotate_left(3)]);
+            assert_eq!(sub_word(word), word.sbox_lookup(), "word 0x{word:08X}");
+        }
+    }
+
+    #[test]
+    fn inv_sbox_matches_table() {
+        for batch in blocks(|b| b).chunks(PARALLEL_BLOCKS) {
+            let mut q = pack(batch);
+            inv_sub_bytes(&mut q);
+            let mut out = batch.to_vec();
+            unpack(&q, &mut out);
+            for (block, expected) in out.iter().zip(batch) {
+                assert_eq!(*block, expected.map(SboxLookup::inv_sbox_lookup));
+            }
+        }
+    }
+
+    #[test]
+    fn mix_columns_match_reference() {
+        for batch in sample_blocks().chunks(PARALLEL_BLOCKS) {
+            let mut q = pack(batch);
+            mix_columns(&mut q);
+            let mut mixed = batch.to_vec();
+            unpack(&q, &mut mixed);
+
+            inv_mix_columns(&mut q);
+            let mut restored = batch.to_vec();
+            unpack(&q, &mut restored);
+
+            for ((block, m), r) in batch.iter().zip(&mixed).zip(&restored) {
+                let expected = ref_mix(Block128::from_be_bytes(*block)).to_be_bytes();
+                assert_eq!(*m, expected);
+                assert_eq!(
+                    ref_inv_mix(Block128::from_be_bytes(*m)).to_be_bytes(),
+                    *block
+                );
+                assert_eq!(r, block);
+            }
+        }
+    }
+
+    #[test]
+    fn batches_match_single_blocks() {
+        let subkeys = Subkeys::from_key(&Key::from(0x2b7e_1516_28ae_d2a6_abf7_1588_09cf_4f3c_u128));
+        let blocks = &sample_blocks()[..7];
+
+        let mut batched = blocks.to_vec();
+        encrypt_blocks(&subkeys, &mut batched);
+        for (block, expected) in blocks.iter().zip(&batched) {
+            let mut single = [*block];
+            encrypt_blocks(&subkeys, &mut single);
+            assert_eq!(single[0], *expected);
+        }
+
+        decrypt_blocks(&subkeys, &mut batched);
+        assert_eq!(batched, blocks);
+    }
+}
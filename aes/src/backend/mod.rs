@@ -0,0 +1,82 @@
+//! Interchangeable implementations of the AES block function.
+//!
+//! Every backend computes the same cipher; they trade readability for speed
+//! or side-channel resistance. The reference backend follows FIPS-197 step by
+//! step and is the one to read when learning AES.
+
+pub mod aesni;
+pub mod bitsliced;
+pub mod ttable;
+
+use crate::sbox::SboxLookup;
+use std::fmt::Display;
+
+/// Implementation used by [`Aes`](crate::Aes) to encrypt and decrypt blocks.
+///
+/// The default is [`AesNi`](Self::AesNi) when the CPU supports it and
+/// [`TTable`](Self::TTable) otherwise, or [`Bitsliced`](Self::Bitsliced) when
+/// the `bitsliced` feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// The step-by-step FIPS-197 rounds with S-box and `MixColumns` lookup
+    /// tables. Table lookups indexed by secret bytes leak timing through the
+    /// cache.
+    Reference,
+    /// 32-bit T-tables that fuse `SubBytes`, `ShiftRows` and `MixColumns`
+    /// into sixteen lookups per round. The fastest portable backend, but its
+    /// lookups are indexed by secret bytes as well.
+    TTable,
+    /// Table-free bitsliced rounds that process four blocks in parallel and
+    /// run in constant time, including the key expansion.
+    Bitsliced,
+    /// The x86-64 AES-NI instructions, one instruction per round. The fastest
+    /// backend and constant-time, but only available on CPUs that support it.
+    AesNi,
+}
+
+impl Backend {
+    /// Every available backend.
+    pub const ALL: [Self; 4] = [Self::Reference, Self::TTable, Self::Bitsliced, Self::AesNi];
+
+    /// Returns whether this backend can run on the current CPU.
+    #[must_use]
+    pub fn is_available(self) -> bool {
+        match self {
+            Self::Reference | Self::TTable | Self::Bitsliced => true,
+            Self::AesNi => aesni::is_available(),
+        }
+    }
+
+    /// Applies the S-box to each byte of a key schedule word.
+    pub(crate) fn sub_word(self, word: u32) -> u32 {
+        match self {
+            Self::Reference | Self::TTable => word.sbox_lookup(),
+            Self::Bitsliced => bitsliced::sub_word(word),
+            Self::AesNi => aesni::sub_word(word),
+        }
+    }
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        if cfg!(feature = "bitsliced") {
+            Self::Bitsliced
+        } else if aesni::is_available() {
+            Self::AesNi
+        } else {
+            Self::TTable
+        }
+    }
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Reference => "reference",
+            Self::TTable => "t-table",
+            Self::Bitsliced => "bitsliced",
+            Self::AesNi => "aes-ni",
+        };
+        f.write_str(s)
+    }
+}
@@ -0,0 +1,190 @@
+//! 32-bit T-table AES.
+//!
+//! Each table entry combines `SubBytes` and one column of `MixColumns` for a
+//! single input byte, so a round costs sixteen lookups and XORs on whole
+//! columns instead of separate passes over the state. The tables are indexed
+//! by secret bytes, so this backend is fast but not constant-time.
+
+use crate::{
+    constants::{INV_S_BOXES, S_BOXES},
+    key::Subkeys,
+    operations::{inv_mix_column, mix_column},
+};
+
+const BLOCK_SIZE: usize = 16;
+
+/// The S-box as a flat table.
+const SBOX: [u8; 256] = flatten(&S_BOXES);
+/// The inverse S-box as a flat table.
+const INV_SBOX: [u8; 256] = flatten(&INV_S_BOXES);
+
+/// `Te0[x]` is the column `(2, 1, 1, 3) * S[x]`; `Te1`-`Te3` are its rotations.
+const TE0: [u32; 256] = encryption_table(0);
+const TE1: [u32; 256] = encryption_table(1);
+const TE2: [u32; 256] = encryption_table(2);
+const TE3: [u32; 256] = encryption_table(3);
+
+/// `Td0[x]` is the column `(14, 9, 13, 11) * S^-1[x]`; `Td1`-`Td3` are its rotations.
+const TD0: [u32; 256] = decryption_table(0);
+const TD1: [u32; 256] = decryption_table(1);
+const TD2: [u32; 256] = decryption_table(2);
+const TD3: [u32; 256] = decryption_table(3);
+
+/// Encrypts a block with the expanded key.
+pub fn encrypt_block(subkeys: &Subkeys, block: [u8; BLOCK_SIZE]) -> [u8; BLOCK_SIZE] {
+    let rounds = subkeys.rounds();
+    let rk = |idx: usize| subkeys[idx].as_u32();
+    let mut s: [u32; 4] = std::array::from_fn(|c| column(&block, c) ^ rk(c));
+
+    for round in 1..rounds {
+        s = std::array::from_fn(|c| {
+            TE0[byte(s[c], 0)]
+                ^ TE1[byte(s[(c + 1) % 4], 1)]
+                ^ TE2[byte(s[(c + 2) % 4], 2)]
+                ^ TE3[byte(s[(c + 3) % 4], 3)]
+                ^ rk(4 * round + c)
+        });
+    }
+
+    // Final round: SubBytes, ShiftRows, AddRoundKey (no MixColumns)
+    let out: [u32; 4] = std::array::from_fn(|c| {
+        u32::from_be_bytes([
+            SBOX[byte(s[c], 0)],
+            SBOX[byte(s[(c + 1) % 4], 1)],
+            SBOX[byte(s[(c + 2) % 4], 2)],
+            SBOX[byte(s[(c + 3) % 4], 3)],
+        ]) ^ rk(4 * rounds + c)
+    });
+    to_block(out)
+}
+
+/// Decrypts a block with the expanded key using the equivalent inverse
+/// cipher (FIPS-197 section 5.3.5).
+pub fn decrypt_block(subkeys: &Subkeys, block: [u8; BLOCK_SIZE]) -> [u8; BLOCK_SIZE] {
+    let rounds = subkeys.rounds();
+    let dk = |idx: usize| subkeys.decryption_keys()[idx].as_u32();
+    let mut s: [u32; 4] = std::array::from_fn(|c| column(&block, c) ^ dk(4 * rounds + c));
+
+    for round in (1..rounds).rev() {
+        s = std::array::from_fn(|c| {
+            TD0[byte(s[c], 0)]
+                ^ TD1[byte(s[(c + 3) % 4], 1)]
+                ^ TD2[byte(s[(c + 2) % 4], 2)]
+                ^ TD3[byte(s[(c + 1) % 4], 3)]
+                ^ dk(4 * round + c)
+        });
+    }
+
+    let out: [u32; 4] = std::array::from_fn(|c| {
+        u32::from_be_bytes([
+            INV_SBOX[byte(s[c], 0)],
+            INV_SBOX[byte(s[(c + 3) % 4], 1)],
+            INV_SBOX[byte(s[(c + 2) % 4], 2)],
+            INV_SBOX[byte(s[(c + 1) % 4], 3)],
+        ]) ^ dk(c)
+    });
+    to_block(out)
+}
+
+/// Reads state column `c` as a big-endian word.
+const fn column(block: &[u8; BLOCK_SIZE], c: usize) -> u32 {
+    u32::from_be_bytes([
+        block[4 * c],
+        block[4 * c + 1],
+        block[4 * c + 2],
+        block[4 * c + 3],
+    ])
+}
+
+/// Returns byte `row` (0 is the most significant) of a column word.
+const fn byte(word: u32, row: usize) -> usize {
+    word.to_be_bytes()[row] as usize
+}
+
+fn to_block(columns: [u32; 4]) -> [u8; BLOCK_SIZE] {
+    let mut block = [0; BLOCK_SIZE];
+    for (bytes, word) in block.chunks_exact_mut(4).zip(columns) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    block
+}
+
+const fn flatten(table: &[[u8; 16]; 16]) -> [u8; 256] {
+    let mut flat = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        flat[i] = table[i >> 4][i & 0x0F];
+        i += 1;
+    }
+    flat
+}
+
+const fn encryption_table(rotation: u32) -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let column = mix_column([SBOX[i], 0, 0, 0]);
+        table[i] = u32::from_be_bytes(column).rotate_right(8 * rotation);
+        i += 1;
+    }
+    table
+}
+
+const fn decryption_table(rotation: u32) -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let column = inv_mix_column([INV_SBOX[i], 0, 0, 0]);
+        table[i] = u32::from_be_bytes(column).rotate_right(8 * rotation);
+        i += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Aes, Backend, Block128, key::Key};
+    use rstest::rstest;
+
+    // Well-known first entries of the T-tables
+    #[rstest]
+    #[case(&TE0, 0xC663_63A5)]
+    #[case(&TE1, 0xA5C6_6363)]
+    #[case(&TE2, 0x63A5_C663)]
+    #[case(&TE3, 0x6363_A5C6)]
+    #[case(&TD0, 0x51F4_A750)]
+    #[case(&TD1, 0x5051_F4A7)]
+    #[case(&TD2, 0xA750_51F4)]
+    #[case(&TD3, 0xF4A7_5051)]
+    fn table_entries(#[case] table: &[u32; 256], #[case] expected: u32) {
+        assert_eq!(table[0], expected);
+    }
+
+    // FIPS-197 Appendix C.1
+    #[test]
+    fn fips197_example() {
+        let subkeys = Subkeys::from_key(&Key::from(0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f_u128));
+        let plaintext = 0x0011_2233_4455_6677_8899_aabb_ccdd_eeff_u128.to_be_bytes();
+        let ciphertext = 0x69c4_e0d8_6a7b_0430_d8cd_b780_70b4_c55a_u128.to_be_bytes();
+
+        assert_eq!(encrypt_block(&subkeys, plaintext), ciphertext);
+        assert_eq!(decrypt_block(&subkeys, ciphertext), plaintext);
+    }
+
+    #[test]
+    fn matches_reference() {
+        let key = 0x2b7e_1516_28ae_d2a6_abf7_1588_09cf_4f3c_u128;
+        let reference = Aes::with_backend(key, Backend::Reference);
+        let subkeys = Subkeys::from_key(&Key::from(key));
+
+        let mut block = [0; BLOCK_SIZE];
+        for _ in 0..64 {
+            let expected = reference.encrypt_block(Block128::from_be_bytes(block));
+            let ciphertext = encrypt_block(&subkeys, block);
+            assert_eq!(ciphertext, expected.to_be_bytes());
+            assert_eq!(decrypt_block(&subkeys, ciphertext), block);
+            block = ciphertext;
+        }
+    }
+}
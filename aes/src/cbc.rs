@@ -3,16 +3,16 @@
 //! CBC mode combines each plaintext block with the previous ciphertext block
 //! (using XOR) before encryption. The first block uses an Initialization Vector (IV).
 
-use crate::{Aes, Block128, Iv, key::Key};
-use cipher_core::{CipherError, CipherResult, pkcs7_pad, pkcs7_unpad};
-
-const BLOCK_SIZE: usize = 16;
+use crate::{Aes, Iv, key::Key};
+use cipher_core::{Cbc, CipherMode, CipherResult};
 
 /// AES cipher in CBC (Cipher Block Chaining) mode.
 ///
 /// CBC mode provides semantic security by combining each plaintext block
 /// with the previous ciphertext block (via XOR) before encryption.
 ///
+/// This is the generic [`Cbc`] mode with PKCS#7 padding applied to [`Aes`].
+///
 /// # Example
 ///
 /// ```
@@ -27,19 +27,17 @@ const BLOCK_SIZE: usize = 16;
 /// let decrypted = cipher.decrypt(&ciphertext).unwrap();
 /// assert_eq!(decrypted, plaintext);
 /// ```
-pub struct AesCbc {
-    aes: Aes,
-    iv: Iv,
-}
+#[derive(Debug)]
+pub struct AesCbc(Cbc<Aes>);
 
 impl AesCbc {
     /// Creates a new AES-CBC cipher with the given key and IV.
     #[must_use]
+    #[allow(clippy::missing_panics_doc)]
     pub fn new(key: impl Into<Key>, iv: impl Into<Iv>) -> Self {
-        Self {
-            aes: Aes::from_key(key),
-            iv: iv.into(),
-        }
+        let iv = iv.into().to_be_bytes();
+        let cbc = Cbc::new(Aes::from_key(key), &iv).expect("IV is exactly one AES block");
+        Self(cbc)
     }
 
     /// Encrypts plaintext using CBC mode with PKCS#7 padding.
@@ -49,26 +47,8 @@ impl AesCbc {
     /// # Errors
     ///
     /// Returns `CipherError` if encryption fails.
-    #[allow(clippy::missing_panics_doc)]
     pub fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
-        let padded = pkcs7_pad(plaintext, BLOCK_SIZE);
-        let mut output = Vec::with_capacity(BLOCK_SIZE + padded.len());
-
-        // Prepend IV to output
-        output.extend_from_slice(&self.iv.to_be_bytes());
-
-        let mut prev_block = self.iv.to_block();
-
-        for chunk in padded.chunks_exact(BLOCK_SIZE) {
-            // chunks_exact guarantees exactly BLOCK_SIZE bytes
-            let plain_block = Block128::from_be_bytes(chunk.try_into().expect("exact chunk size"));
-            let xored = plain_block ^ prev_block.as_u128();
-            let encrypted = self.aes.encrypt_block(xored);
-            output.extend_from_slice(&encrypted.to_be_bytes());
-            prev_block = encrypted;
-        }
-
-        Ok(output)
+        self.0.encrypt(plaintext)
     }
 
     /// Decrypts ciphertext using CBC mode and removes PKCS#7 padding.
@@ -79,33 +59,20 @@ impl AesCbc {
     /// # Errors
     ///
     /// Returns `CipherError::InvalidBlockSize` if input length is not a multiple of 16
-    /// or is less than 32 bytes (IV + at least one block).
-    /// Returns `CipherError::InvalidPadding` if padding is invalid.
-    #[allow(clippy::missing_panics_doc)]
+    /// or is less than 16 bytes.
+    /// Returns `CipherError::InvalidPadding` if padding is invalid or missing.
     pub fn decrypt(&self, data: &[u8]) -> CipherResult<Vec<u8>> {
-        // Need at least IV (16 bytes) + one ciphertext block (16 bytes)
-        if data.len() < BLOCK_SIZE * 2 || !data.len().is_multiple_of(BLOCK_SIZE) {
-            return Err(CipherError::invalid_block_size(BLOCK_SIZE, data.len()));
-        }
-
-        // Extract IV from first block
-        let iv = Iv::from_be_bytes(data[..BLOCK_SIZE].try_into().expect("exact IV size"));
-        let ciphertext = &data[BLOCK_SIZE..];
-
-        let mut plaintext = Vec::with_capacity(ciphertext.len());
-        let mut prev_block = iv.to_block();
-
-        for chunk in ciphertext.chunks_exact(BLOCK_SIZE) {
-            // chunks_exact guarantees exactly BLOCK_SIZE bytes
-            let cipher_block = Block128::from_be_bytes(chunk.try_into().expect("exact chunk size"));
-            let decrypted = self.aes.decrypt_block(cipher_block);
-            let plain_block = decrypted ^ prev_block.as_u128();
-            plaintext.extend_from_slice(&plain_block.to_be_bytes());
-            prev_block = cipher_block;
-        }
-
-        let unpadded = pkcs7_unpad(&plaintext, BLOCK_SIZE)?;
-        Ok(unpadded.to_vec())
+        self.0.decrypt(data)
+    }
+}
+
+impl CipherMode for AesCbc {
+    fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        self.0.encrypt(plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> CipherResult<Vec<u8>> {
+        self.0.decrypt(ciphertext)
     }
 }
 
@@ -163,5 +130,9 @@ mod tests {
 
         let invalid = [0u8; 15];
         assert_err!(cipher.decrypt(&invalid));
+
+        // IV only, no ciphertext blocks
+        let iv_only = [0u8; 16];
+        assert_err!(cipher.decrypt(&iv_only));
     }
 }
@@ -0,0 +1,388 @@
+//! AES-CCM (Counter with CBC-MAC) authenticated encryption (NIST SP 800-38C, RFC 3610).
+//!
+//! CCM authenticates the associated data and plaintext with CBC-MAC, then
+//! encrypts the plaintext and the MAC with counter mode. The nonce length
+//! trades off against the maximum message length: a nonce of `n` bytes
+//! leaves `15 - n` bytes for the message length and block counter.
+
+use crate::{Aes, Block128, key::Key};
+use cipher_core::{Aead, CipherError, CipherResult, ct_eq};
+use std::{fmt::Debug, ops::RangeInclusive};
+use zeroize::Zeroize;
+
+const BLOCK_SIZE: usize = 16;
+
+/// Supported nonce lengths in bytes.
+pub const CCM_NONCE_SIZES: RangeInclusive<usize> = 7..=13;
+
+/// Supported tag lengths in bytes; the length must also be even.
+pub const CCM_TAG_SIZES: RangeInclusive<usize> = 4..=16;
+
+/// AES in CCM mode with a fixed nonce and tag length.
+///
+/// # Security
+///
+/// A nonce must never be reused with the same key.
+///
+/// # Example
+///
+/// ```
+/// use aes::AesCcm;
+/// use cipher_core::Aead;
+///
+/// let key = 0xc0c1c2c3_c4c5c6c7_c8c9cacb_cccdcecf_u128;
+/// let cipher = AesCcm::new(key, 13, 8).unwrap();
+/// let nonce = [0x00, 0x00, 0x00, 0x03, 0x02, 0x01, 0x00, 0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5];
+///
+/// let sealed = cipher.seal(&nonce, b"header", b"Hello, World!").unwrap();
+/// assert_eq!(sealed.len(), 13 + 8);
+///
+/// let opened = cipher.open(&nonce, b"header", &sealed).unwrap();
+/// assert_eq!(opened, b"Hello, World!");
+/// ```
+pub struct AesCcm {
+    aes: Aes,
+    nonce_size: usize,
+    tag_size: usize,
+}
+
+impl AesCcm {
+    /// Creates a new AES-CCM cipher.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if `nonce_size` is not 7 to 13 bytes.
+    /// Returns `CipherError::InvalidTagSize` if `tag_size` is not an even
+    /// number from 4 to 16.
+    pub fn new(key: impl Into<Key>, nonce_size: usize, tag_size: usize) -> CipherResult<Self> {
+        if !CCM_NONCE_SIZES.contains(&nonce_size) {
+            let expected = nonce_size.clamp(*CCM_NONCE_SIZES.start(), *CCM_NONCE_SIZES.end());
+            return Err(CipherError::invalid_iv_size(expected, nonce_size));
+        }
+        if !CCM_TAG_SIZES.contains(&tag_size) || !tag_size.is_multiple_of(2) {
+            return Err(CipherError::invalid_tag_size(tag_size));
+        }
+
+        Ok(Self {
+            aes: Aes::from_key(key),
+            nonce_size,
+            tag_size,
+        })
+    }
+
+    /// Returns the nonce length in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn nonce_size(&self) -> usize {
+        self.nonce_size
+    }
+
+    /// Size in bytes of the length field `q`; also the counter width.
+    #[inline]
+    const fn length_size(&self) -> usize {
+        BLOCK_SIZE - 1 - self.nonce_size
+    }
+
+    /// Encrypts `plaintext` and returns the ciphertext and the tag separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the nonce has the wrong length.
+    /// Returns `CipherError::CounterOverflow` if the plaintext length does not
+    /// fit in the `15 - nonce_size` byte length field.
+    pub fn encrypt_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> CipherResult<(Vec<u8>, Vec<u8>)> {
+        self.check_inputs(nonce, plaintext.len())?;
+
+        let tag = self.cbc_mac(nonce, associated_data, plaintext);
+        let mut ciphertext = plaintext.to_vec();
+        self.ctr(nonce, &mut ciphertext);
+        Ok((ciphertext, self.encrypt_tag(nonce, tag)))
+    }
+
+    /// Decrypts `ciphertext` and verifies `tag`.
+    ///
+    /// No plaintext is returned unless the tag matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the nonce has the wrong length.
+    /// Returns `CipherError::AuthenticationFailed` if the tag does not match.
+    pub fn decrypt_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8],
+    ) -> CipherResult<Vec<u8>> {
+        self.check_inputs(nonce, ciphertext.len())?;
+        if tag.len() != self.tag_size {
+            return Err(CipherError::AuthenticationFailed);
+        }
+
+        let mut plaintext = ciphertext.to_vec();
+        self.ctr(nonce, &mut plaintext);
+
+        let expected = self.encrypt_tag(nonce, self.cbc_mac(nonce, associated_data, &plaintext));
+        if !ct_eq(&expected, tag) {
+            plaintext.zeroize();
+            return Err(CipherError::AuthenticationFailed);
+        }
+        Ok(plaintext)
+    }
+
+    /// Flags for the counter blocks: `q - 1`, which is at most 7.
+    #[allow(clippy::cast_possible_truncation)]
+    const fn counter_flags(&self) -> u8 {
+        (self.length_size() - 1) as u8
+    }
+
+    /// Flags for `B0`: the `Adata` bit, the encoded tag length and `q - 1`.
+    #[allow(clippy::cast_possible_truncation)]
+    const fn b0_flags(&self, has_associated_data: bool) -> u8 {
+        let adata = if has_associated_data { 1 << 6 } else { 0 };
+        let tag = ((self.tag_size - 2) / 2) as u8;
+        adata | (tag << 3) | self.counter_flags()
+    }
+
+    const fn check_inputs(&self, nonce: &[u8], len: usize) -> CipherResult<()> {
+        if nonce.len() != self.nonce_size {
+            return Err(CipherError::invalid_iv_size(self.nonce_size, nonce.len()));
+        }
+
+        let length_bits = 8 * self.counter_flags() as u32 + 8;
+        if length_bits < usize::BITS && len >> length_bits != 0 {
+            return Err(CipherError::counter_overflow(length_bits));
+        }
+        Ok(())
+    }
+
+    /// CBC-MAC over `B0`, the encoded associated data and the padded plaintext.
+    fn cbc_mac(&self, nonce: &[u8], associated_data: &[u8], plaintext: &[u8]) -> Block128 {
+        let q = self.length_size();
+
+        // B0 = flags || N || Q
+        let mut b0 = [0; BLOCK_SIZE];
+        b0[0] = self.b0_flags(!associated_data.is_empty());
+        b0[1..=self.nonce_size].copy_from_slice(nonce);
+        b0[BLOCK_SIZE - q..].copy_from_slice(&(plaintext.len() as u128).to_be_bytes()[16 - q..]);
+
+        let mut mac = CbcMac::new(&self.aes, b0);
+        if !associated_data.is_empty() {
+            mac.update(&encode_ad_length(associated_data.len()));
+            mac.update(associated_data);
+            mac.pad();
+        }
+        mac.update(plaintext);
+        mac.pad();
+        mac.state
+    }
+
+    /// Counter block `i`: flags || N || [i]_q.
+    fn counter_block(&self, nonce: &[u8], counter: u128) -> Block128 {
+        let q = self.length_size();
+        let mut block = [0; BLOCK_SIZE];
+        block[0] = self.counter_flags();
+        block[1..=self.nonce_size].copy_from_slice(nonce);
+        block[BLOCK_SIZE - q..].copy_from_slice(&counter.to_be_bytes()[16 - q..]);
+        Block128::from_be_bytes(block)
+    }
+
+    /// Applies the keystream from counter block 1 onwards.
+    fn ctr(&self, nonce: &[u8], data: &mut [u8]) {
+        for (counter, chunk) in (1..).zip(data.chunks_mut(BLOCK_SIZE)) {
+            let keystream = self
+                .aes
+                .encrypt_block(self.counter_block(nonce, counter))
+                .to_be_bytes();
+            for (byte, key_byte) in chunk.iter_mut().zip(keystream) {
+                *byte ^= key_byte;
+            }
+        }
+    }
+
+    /// Truncates the MAC to the tag length and masks it with `S0`.
+    fn encrypt_tag(&self, nonce: &[u8], mac: Block128) -> Vec<u8> {
+        let s0 = self.aes.encrypt_block(self.counter_block(nonce, 0));
+        (mac.as_u128() ^ s0.as_u128()).to_be_bytes()[..self.tag_size].to_vec()
+    }
+}
+
+/// Omits the cipher, which holds key material.
+impl Debug for AesCcm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesCcm")
+            .field("nonce_size", &self.nonce_size)
+            .field("tag_size", &self.tag_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Aead for AesCcm {
+    fn tag_size(&self) -> usize {
+        self.tag_size
+    }
+
+    fn seal(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> CipherResult<Vec<u8>> {
+        let (mut sealed, tag) = self.encrypt_detached(nonce, associated_data, plaintext)?;
+        sealed.extend_from_slice(&tag);
+        Ok(sealed)
+    }
+
+    fn open(&self, nonce: &[u8], associated_data: &[u8], sealed: &[u8]) -> CipherResult<Vec<u8>> {
+        let Some(split) = sealed.len().checked_sub(self.tag_size) else {
+            return Err(CipherError::AuthenticationFailed);
+        };
+        let (ciphertext, tag) = sealed.split_at(split);
+        self.decrypt_detached(nonce, associated_data, ciphertext, tag)
+    }
+}
+
+/// Encodes the associated data length as a 2, 6 or 10-byte prefix.
+fn encode_ad_length(len: usize) -> Vec<u8> {
+    match (u16::try_from(len), u32::try_from(len)) {
+        (Ok(short), _) if short < 0xFF00 => short.to_be_bytes().to_vec(),
+        (_, Ok(medium)) => [[0xFF, 0xFE].as_slice(), &medium.to_be_bytes()].concat(),
+        _ => [[0xFF, 0xFF].as_slice(), &(len as u64).to_be_bytes()].concat(),
+    }
+}
+
+/// CBC-MAC that accepts data in arbitrary pieces.
+struct CbcMac<'a> {
+    aes: &'a Aes,
+    state: Block128,
+    buffer: [u8; BLOCK_SIZE],
+    filled: usize,
+}
+
+impl<'a> CbcMac<'a> {
+    fn new(aes: &'a Aes, first_block: [u8; BLOCK_SIZE]) -> Self {
+        Self {
+            aes,
+            state: aes.encrypt_block(Block128::from_be_bytes(first_block)),
+            buffer: [0; BLOCK_SIZE],
+            filled: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let take = (BLOCK_SIZE - self.filled).min(data.len());
+            self.buffer[self.filled..self.filled + take].copy_from_slice(&data[..take]);
+            self.filled += take;
+            data = &data[take..];
+            if self.filled == BLOCK_SIZE {
+                self.absorb();
+            }
+        }
+    }
+
+    /// Zero-pads and absorbs a partial block, if any.
+    fn pad(&mut self) {
+        if self.filled > 0 {
+            self.buffer[self.filled..].fill(0);
+            self.absorb();
+        }
+    }
+
+    fn absorb(&mut self) {
+        let block = Block128::from_be_bytes(self.buffer).as_u128() ^ self.state.as_u128();
+        self.state = self.aes.encrypt_block(Block128::new(block));
+        self.filled = 0;
+    }
+}
+
+impl Drop for CbcMac<'_> {
+    fn drop(&mut self) {
+        self.buffer.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_matches, assert_ok};
+    use rstest::rstest;
+
+    const KEY: u128 = 0x4041_4243_4445_4647_4849_4a4b_4c4d_4e4f;
+    const NONCE: [u8; 7] = [0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16];
+    const AAD: &[u8] = b"associated data";
+    const PLAINTEXT: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    #[rstest]
+    #[case(7, 4)]
+    #[case(8, 6)]
+    #[case(12, 16)]
+    #[case(13, 8)]
+    fn seal_open_roundtrip(#[case] nonce_size: usize, #[case] tag_size: usize) {
+        let cipher = assert_ok!(AesCcm::new(KEY, nonce_size, tag_size));
+        let nonce = vec![0xA5; nonce_size];
+
+        let sealed = assert_ok!(cipher.seal(&nonce, AAD, PLAINTEXT));
+        assert_eq!(sealed.len(), PLAINTEXT.len() + tag_size);
+        assert_eq!(assert_ok!(cipher.open(&nonce, AAD, &sealed)), PLAINTEXT);
+    }
+
+    #[rstest]
+    #[case(6, 8)]
+    #[case(14, 8)]
+    #[case(13, 2)]
+    #[case(13, 5)]
+    #[case(13, 18)]
+    fn invalid_parameters_fail(#[case] nonce_size: usize, #[case] tag_size: usize) {
+        assert_err!(AesCcm::new(KEY, nonce_size, tag_size));
+    }
+
+    #[test]
+    fn tampering_fails() {
+        let cipher = assert_ok!(AesCcm::new(KEY, 7, 8));
+        let sealed = assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT));
+
+        let mut tampered = sealed.clone();
+        tampered[0] ^= 1;
+        let err = assert_err!(cipher.open(&NONCE, AAD, &tampered));
+        assert_matches!(err, CipherError::AuthenticationFailed);
+
+        assert_err!(cipher.open(&NONCE, b"other data", &sealed));
+        assert_err!(cipher.open(&NONCE, AAD, &sealed[..sealed.len() - 1]));
+    }
+
+    #[test]
+    fn wrong_nonce_length_fails() {
+        let cipher = assert_ok!(AesCcm::new(KEY, 7, 8));
+        let err = assert_err!(cipher.seal(&[0; 8], AAD, PLAINTEXT));
+        assert_matches!(
+            err,
+            CipherError::InvalidIvSize {
+                expected: 7,
+                actual: 8
+            }
+        );
+    }
+
+    #[test]
+    fn message_longer_than_length_field_fails() {
+        // A 13-byte nonce leaves a 2-byte length field
+        let cipher = assert_ok!(AesCcm::new(KEY, 13, 8));
+        assert_ok!(cipher.seal(&[0; 13], &[], &vec![0; 0xFFFF]));
+        let err = assert_err!(cipher.seal(&[0; 13], &[], &vec![0; 0x1_0000]));
+        assert_matches!(err, CipherError::CounterOverflow { bits: 16 });
+    }
+
+    #[rstest]
+    #[case(0, &[0x00, 0x00])]
+    #[case(0xFEFF, &[0xFE, 0xFF])]
+    #[case(0xFF00, &[0xFF, 0xFE, 0x00, 0x00, 0xFF, 0x00])]
+    fn ad_length_encoding(#[case] len: usize, #[case] expected: &[u8]) {
+        assert_eq!(encode_ad_length(len), expected);
+    }
+}
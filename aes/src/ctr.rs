@@ -0,0 +1,307 @@
+//! AES-CTR (Counter) mode implementation.
+//!
+//! CTR mode turns AES into a stream cipher: successive counter blocks are
+//! encrypted and the resulting keystream is combined with the data using XOR.
+//! No padding is needed and every block can be processed independently, which allows
+//! seeking to any block offset.
+
+use crate::{Aes, Block128, Iv, key::Key};
+use cipher_core::{CipherError, CipherMode, CipherResult};
+
+const BLOCK_SIZE: usize = 16;
+
+/// How the 128-bit initial counter block is split into nonce and counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CounterLayout {
+    /// 96-bit nonce followed by a 32-bit big-endian block counter (RFC 3686, GCM).
+    ///
+    /// Only the low 32 bits are incremented, so at most 2^32 blocks can be
+    /// processed with one nonce.
+    #[default]
+    Nonce96Counter32,
+    /// The whole block is one 128-bit big-endian counter (NIST SP 800-38A).
+    Counter128,
+}
+
+impl CounterLayout {
+    /// Number of bits that are incremented per block.
+    #[inline]
+    #[must_use]
+    pub const fn counter_bits(self) -> u32 {
+        match self {
+            Self::Nonce96Counter32 => 32,
+            Self::Counter128 => 128,
+        }
+    }
+
+    /// Returns the counter block `offset` blocks after `initial`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::CounterOverflow` if the counter would wrap.
+    fn counter_block(self, initial: u128, offset: u128) -> CipherResult<Block128> {
+        let overflow = || CipherError::counter_overflow(self.counter_bits());
+        match self {
+            Self::Nonce96Counter32 => {
+                let mask = u128::from(u32::MAX);
+                let counter = (initial & mask).checked_add(offset).ok_or_else(overflow)?;
+                if counter > mask {
+                    return Err(overflow());
+                }
+                Ok(Block128::new((initial & !mask) | counter))
+            }
+            Self::Counter128 => initial
+                .checked_add(offset)
+                .map(Block128::new)
+                .ok_or_else(overflow),
+        }
+    }
+}
+
+/// AES cipher in CTR (Counter) mode.
+///
+/// [`encrypt`](Self::encrypt) prepends the initial counter block to the
+/// output: `[16-byte IV][ciphertext...]`, like [`AesCbc`](crate::AesCbc).
+/// For streaming and random access, [`apply_keystream_at`](Self::apply_keystream_at)
+/// transforms data in place starting at any block offset.
+///
+/// # Example
+///
+/// ```
+/// use aes::{AesCtr, Iv};
+///
+/// let key = 0x2b7e1516_28aed2a6_abf71588_09cf4f3c_u128;
+/// let iv = Iv::new(0xf0f1f2f3_f4f5f6f7_f8f9fafb_00000001_u128);
+/// let cipher = AesCtr::new(key, iv);
+///
+/// let plaintext = b"Hello, World!";
+/// let ciphertext = cipher.encrypt(plaintext).unwrap();
+/// assert_eq!(ciphertext.len(), 16 + plaintext.len());
+///
+/// let decrypted = cipher.decrypt(&ciphertext).unwrap();
+/// assert_eq!(decrypted, plaintext);
+/// ```
+pub struct AesCtr {
+    aes: Aes,
+    iv: Iv,
+    layout: CounterLayout,
+}
+
+impl AesCtr {
+    /// Creates a new AES-CTR cipher with a 96-bit nonce and 32-bit counter.
+    #[must_use]
+    pub fn new(key: impl Into<Key>, iv: impl Into<Iv>) -> Self {
+        Self::with_layout(key, iv, CounterLayout::default())
+    }
+
+    /// Creates a new AES-CTR cipher with the given counter layout.
+    #[must_use]
+    pub fn with_layout(key: impl Into<Key>, iv: impl Into<Iv>, layout: CounterLayout) -> Self {
+        Self {
+            aes: Aes::from_key(key),
+            iv: iv.into(),
+            layout,
+        }
+    }
+
+    /// Returns the counter layout.
+    #[inline]
+    #[must_use]
+    pub const fn layout(&self) -> CounterLayout {
+        self.layout
+    }
+
+    /// XORs `data` with the keystream, starting at the initial counter block.
+    ///
+    /// Encryption and decryption are the same operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::CounterOverflow` if `data` needs more blocks than
+    /// the counter can provide.
+    pub fn apply_keystream(&self, data: &mut [u8]) -> CipherResult<()> {
+        self.apply_keystream_at(0, data)
+    }
+
+    /// XORs `data` with the keystream, starting `block_offset` blocks after
+    /// the initial counter block.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::CounterOverflow` if the counter would wrap. In
+    /// that case `data` is left unchanged.
+    pub fn apply_keystream_at(&self, block_offset: u128, data: &mut [u8]) -> CipherResult<()> {
+        xor_keystream(
+            &self.aes,
+            self.layout,
+            self.iv.as_u128(),
+            block_offset,
+            data,
+        )
+    }
+
+    /// Encrypts plaintext of any length.
+    ///
+    /// The output format is: `[16-byte IV][ciphertext...]`
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::CounterOverflow` if the counter would wrap.
+    pub fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        let mut output = Vec::with_capacity(BLOCK_SIZE + plaintext.len());
+        output.extend_from_slice(&self.iv.to_be_bytes());
+        output.extend_from_slice(plaintext);
+        self.apply_keystream(&mut output[BLOCK_SIZE..])?;
+        Ok(output)
+    }
+
+    /// Decrypts ciphertext.
+    ///
+    /// Expects input format: `[16-byte IV][ciphertext...]`
+    /// The IV is extracted from the input; the IV stored in `self` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidBlockSize` if the input is shorter than the IV.
+    /// Returns `CipherError::CounterOverflow` if the counter would wrap.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn decrypt(&self, data: &[u8]) -> CipherResult<Vec<u8>> {
+        if data.len() < BLOCK_SIZE {
+            return Err(CipherError::invalid_block_size(BLOCK_SIZE, data.len()));
+        }
+
+        let (iv, ciphertext) = data.split_at(BLOCK_SIZE);
+        let iv = Iv::from_be_bytes(iv.try_into().expect("exact IV size"));
+
+        let mut plaintext = ciphertext.to_vec();
+        xor_keystream(&self.aes, self.layout, iv.as_u128(), 0, &mut plaintext)?;
+        Ok(plaintext)
+    }
+}
+
+impl CipherMode for AesCtr {
+    fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        Self::encrypt(self, plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> CipherResult<Vec<u8>> {
+        Self::decrypt(self, ciphertext)
+    }
+}
+
+fn xor_keystream(
+    aes: &Aes,
+    layout: CounterLayout,
+    iv: u128,
+    block_offset: u128,
+    data: &mut [u8],
+) -> CipherResult<()> {
+    // Check the last counter up front so a wrap never leaves `data` half processed
+    let blocks = data.len().div_ceil(BLOCK_SIZE) as u128;
+    if let Some(last) = blocks.checked_sub(1) {
+        let last_offset = block_offset
+            .checked_add(last)
+            .ok_or_else(|| CipherError::counter_overflow(layout.counter_bits()))?;
+        layout.counter_block(iv, last_offset)?;
+    }
+
+    for (idx, chunk) in (0..).zip(data.chunks_mut(BLOCK_SIZE)) {
+        let counter = layout.counter_block(iv, block_offset + idx)?;
+        let keystream = aes.encrypt_block(counter).to_be_bytes();
+        for (byte, key_byte) in chunk.iter_mut().zip(keystream) {
+            *byte ^= key_byte;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_matches, assert_ok};
+    use rstest::rstest;
+
+    const KEY: u128 = 0x2b7e_1516_28ae_d2a6_abf7_1588_09cf_4f3c;
+    const IV: u128 = 0xf0f1_f2f3_f4f5_f6f7_f8f9_fafb_fcfd_feff;
+
+    #[rstest]
+    #[case(CounterLayout::Nonce96Counter32)]
+    #[case(CounterLayout::Counter128)]
+    fn encrypt_decrypt_roundtrip(#[case] layout: CounterLayout) {
+        let cipher = AesCtr::with_layout(KEY, Iv::new(IV), layout);
+
+        let plaintext = b"The quick brown fox jumps over the lazy dog";
+        let ciphertext = assert_ok!(cipher.encrypt(plaintext));
+        assert_eq!(ciphertext.len(), 16 + plaintext.len());
+
+        let decrypted = assert_ok!(cipher.decrypt(&ciphertext));
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn seek_matches_keystream_tail() {
+        let cipher = AesCtr::new(KEY, Iv::new(IV));
+
+        let mut full = [0u8; 80];
+        assert_ok!(cipher.apply_keystream(&mut full));
+
+        let mut tail = [0u8; 32];
+        assert_ok!(cipher.apply_keystream_at(3, &mut tail));
+        assert_eq!(tail, full[48..]);
+    }
+
+    #[test]
+    fn counter_32_wrap_fails() {
+        let iv = Iv::new(0x0000_0000_0000_0000_0000_0000_FFFF_FFFF);
+        let cipher = AesCtr::new(KEY, iv);
+
+        let mut one_block = [0u8; 16];
+        assert_ok!(cipher.apply_keystream(&mut one_block));
+
+        let mut two_blocks = [0u8; 17];
+        let err = assert_err!(cipher.apply_keystream(&mut two_blocks));
+        assert_matches!(err, CipherError::CounterOverflow { bits: 32 });
+        assert_eq!(two_blocks, [0u8; 17], "data must be unchanged on overflow");
+    }
+
+    #[test]
+    fn counter_128_carries_past_32_bits() {
+        let iv = Iv::new(0x0000_0000_0000_0000_0000_0000_FFFF_FFFF);
+        let wide = AesCtr::with_layout(KEY, iv, CounterLayout::Counter128);
+        let next = AesCtr::with_layout(
+            KEY,
+            Iv::new(0x0000_0000_0000_0000_0000_0001_0000_0000),
+            CounterLayout::Counter128,
+        );
+
+        let mut two_blocks = [0u8; 32];
+        assert_ok!(wide.apply_keystream(&mut two_blocks));
+
+        let mut second = [0u8; 16];
+        assert_ok!(next.apply_keystream(&mut second));
+        assert_eq!(two_blocks[16..], second);
+    }
+
+    #[test]
+    fn counter_128_wrap_fails() {
+        let cipher = AesCtr::with_layout(KEY, Iv::new(u128::MAX), CounterLayout::Counter128);
+        let mut data = [0u8; 16];
+        assert_ok!(cipher.apply_keystream(&mut data));
+        assert_err!(cipher.apply_keystream_at(1, &mut data));
+    }
+
+    #[test]
+    fn empty_plaintext() {
+        let cipher = AesCtr::new(KEY, Iv::new(IV));
+        let ciphertext = assert_ok!(cipher.encrypt(&[]));
+        assert_eq!(ciphertext.len(), 16);
+        assert!(assert_ok!(cipher.decrypt(&ciphertext)).is_empty());
+    }
+
+    #[test]
+    fn decrypt_too_short_fails() {
+        let cipher = AesCtr::new(KEY, Iv::new(IV));
+        assert_err!(cipher.decrypt(&[0u8; 15]));
+    }
+}
@@ -0,0 +1,273 @@
+//! AES-GCM (Galois/Counter Mode) authenticated encryption (NIST SP 800-38D).
+//!
+//! GCM encrypts with a 32-bit counter mode and authenticates the associated
+//! data and ciphertext with [`Ghash`], producing a 128-bit tag.
+
+use crate::{Aes, Block128, Ghash, key::Key};
+use cipher_core::{Aead, CipherError, CipherResult, ct_eq};
+
+const BLOCK_SIZE: usize = 16;
+
+/// Length in bytes of the authentication tag.
+pub const GCM_TAG_SIZE: usize = 16;
+
+/// Recommended nonce length in bytes; other lengths are hashed into the counter.
+pub const GCM_NONCE_SIZE: usize = 12;
+
+/// Largest plaintext that can be encrypted under one nonce: 2^32 - 2 blocks.
+const MAX_PLAINTEXT_LEN: u64 = ((1 << 32) - 2) * BLOCK_SIZE as u64;
+
+/// AES in Galois/Counter Mode.
+///
+/// Provides confidentiality for the plaintext and integrity for both the
+/// ciphertext and the associated data. The tag is checked in constant time
+/// before any plaintext is released.
+///
+/// # Security
+///
+/// A nonce must never be reused with the same key.
+///
+/// # Example
+///
+/// ```
+/// use aes::AesGcm;
+/// use cipher_core::Aead;
+///
+/// let cipher = AesGcm::new(0xfeffe992_8665731c_6d6a8f94_67308308_u128);
+/// let nonce = [0xca, 0xfe, 0xba, 0xbe, 0xfa, 0xce, 0xdb, 0xad, 0xde, 0xca, 0xf8, 0x88];
+///
+/// let sealed = cipher.seal(&nonce, b"header", b"Hello, World!").unwrap();
+/// assert_eq!(sealed.len(), 13 + 16);
+///
+/// let opened = cipher.open(&nonce, b"header", &sealed).unwrap();
+/// assert_eq!(opened, b"Hello, World!");
+///
+/// assert!(cipher.open(&nonce, b"other header", &sealed).is_err());
+/// ```
+pub struct AesGcm {
+    aes: Aes,
+    hash_key: Block128,
+}
+
+impl AesGcm {
+    /// Creates a new AES-GCM cipher with the given key.
+    #[must_use]
+    pub fn new(key: impl Into<Key>) -> Self {
+        let aes = Aes::from_key(key);
+        let hash_key = aes.encrypt_block(Block128::new(0));
+        Self { aes, hash_key }
+    }
+
+    /// Encrypts `plaintext` and returns the ciphertext and the tag separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the nonce is empty.
+    /// Returns `CipherError::CounterOverflow` if the plaintext is longer than
+    /// 2^32 - 2 blocks.
+    pub fn encrypt_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> CipherResult<(Vec<u8>, [u8; GCM_TAG_SIZE])> {
+        if plaintext.len() as u64 > MAX_PLAINTEXT_LEN {
+            return Err(CipherError::counter_overflow(32));
+        }
+
+        let j0 = self.pre_counter_block(nonce)?;
+        let mut ciphertext = plaintext.to_vec();
+        self.gctr(inc32(j0), &mut ciphertext);
+
+        let tag = self.compute_tag(j0, associated_data, &ciphertext);
+        Ok((ciphertext, tag))
+    }
+
+    /// Verifies `tag` and decrypts `ciphertext`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the nonce is empty.
+    /// Returns `CipherError::AuthenticationFailed` if the tag does not match.
+    pub fn decrypt_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8],
+    ) -> CipherResult<Vec<u8>> {
+        let j0 = self.pre_counter_block(nonce)?;
+        let expected = self.compute_tag(j0, associated_data, ciphertext);
+        if !ct_eq(&expected, tag) {
+            return Err(CipherError::AuthenticationFailed);
+        }
+
+        let mut plaintext = ciphertext.to_vec();
+        self.gctr(inc32(j0), &mut plaintext);
+        Ok(plaintext)
+    }
+
+    /// Derives the pre-counter block `J0` from the nonce.
+    fn pre_counter_block(&self, nonce: &[u8]) -> CipherResult<Block128> {
+        if nonce.is_empty() {
+            return Err(CipherError::invalid_iv_size(GCM_NONCE_SIZE, 0));
+        }
+
+        if nonce.len() == GCM_NONCE_SIZE {
+            // J0 = nonce || 0^31 || 1
+            let mut block = [0; BLOCK_SIZE];
+            block[..GCM_NONCE_SIZE].copy_from_slice(nonce);
+            block[BLOCK_SIZE - 1] = 1;
+            return Ok(Block128::from_be_bytes(block));
+        }
+
+        // J0 = GHASH(nonce || 0^(s + 64) || [len(nonce)]_64)
+        let mut ghash = Ghash::new(self.hash_key);
+        ghash.update_padded(nonce);
+        ghash.update_block(Block128::new(bit_len(nonce)));
+        Ok(ghash.finalize())
+    }
+
+    /// Counter mode keystream starting at `counter`, incrementing the low 32 bits.
+    fn gctr(&self, mut counter: Block128, data: &mut [u8]) {
+        for chunk in data.chunks_mut(BLOCK_SIZE) {
+            let keystream = self.aes.encrypt_block(counter).to_be_bytes();
+            for (byte, key_byte) in chunk.iter_mut().zip(keystream) {
+                *byte ^= key_byte;
+            }
+            counter = inc32(counter);
+        }
+    }
+
+    fn compute_tag(
+        &self,
+        j0: Block128,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+    ) -> [u8; GCM_TAG_SIZE] {
+        let mut ghash = Ghash::new(self.hash_key);
+        ghash.update_padded(associated_data);
+        ghash.update_padded(ciphertext);
+        ghash.update_block(Block128::new(
+            (bit_len(associated_data) << 64) | bit_len(ciphertext),
+        ));
+
+        let mask = self.aes.encrypt_block(j0);
+        (ghash.finalize() ^ mask.as_u128()).to_be_bytes()
+    }
+}
+
+impl Aead for AesGcm {
+    fn tag_size(&self) -> usize {
+        GCM_TAG_SIZE
+    }
+
+    fn seal(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> CipherResult<Vec<u8>> {
+        let (mut sealed, tag) = self.encrypt_detached(nonce, associated_data, plaintext)?;
+        sealed.extend_from_slice(&tag);
+        Ok(sealed)
+    }
+
+    fn open(&self, nonce: &[u8], associated_data: &[u8], sealed: &[u8]) -> CipherResult<Vec<u8>> {
+        let Some(split) = sealed.len().checked_sub(GCM_TAG_SIZE) else {
+            return Err(CipherError::AuthenticationFailed);
+        };
+        let (ciphertext, tag) = sealed.split_at(split);
+        self.decrypt_detached(nonce, associated_data, ciphertext, tag)
+    }
+}
+
+/// Increments the rightmost 32 bits of the block modulo 2^32.
+const fn inc32(block: Block128) -> Block128 {
+    let value = block.as_u128();
+    let mask = u32::MAX as u128;
+    let counter = (value & mask).wrapping_add(1) & mask;
+    Block128::new((value & !mask) | counter)
+}
+
+/// Length of `data` in bits, as used in the final GHASH block.
+const fn bit_len(data: &[u8]) -> u128 {
+    data.len() as u128 * 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_matches, assert_ok};
+
+    const KEY: u128 = 0xFEFF_E992_8665_731C_6D6A_8F94_6730_8308;
+    const NONCE: [u8; 12] = [
+        0xCA, 0xFE, 0xBA, 0xBE, 0xFA, 0xCE, 0xDB, 0xAD, 0xDE, 0xCA, 0xF8, 0x88,
+    ];
+    const AAD: &[u8] = b"associated data";
+    const PLAINTEXT: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn seal_open_roundtrip() {
+        let cipher = AesGcm::new(KEY);
+        let sealed = assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT));
+        assert_eq!(sealed.len(), PLAINTEXT.len() + GCM_TAG_SIZE);
+
+        let opened = assert_ok!(cipher.open(&NONCE, AAD, &sealed));
+        assert_eq!(opened, PLAINTEXT);
+    }
+
+    #[test]
+    fn tampered_ciphertext_fails() {
+        let cipher = AesGcm::new(KEY);
+        let mut sealed = assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT));
+        sealed[3] ^= 0x01;
+
+        let err = assert_err!(cipher.open(&NONCE, AAD, &sealed));
+        assert_matches!(err, CipherError::AuthenticationFailed);
+    }
+
+    #[test]
+    fn tampered_tag_fails() {
+        let cipher = AesGcm::new(KEY);
+        let mut sealed = assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT));
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x80;
+        assert_err!(cipher.open(&NONCE, AAD, &sealed));
+    }
+
+    #[test]
+    fn tampered_associated_data_fails() {
+        let cipher = AesGcm::new(KEY);
+        let sealed = assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT));
+        assert_err!(cipher.open(&NONCE, b"associated date", &sealed));
+    }
+
+    #[test]
+    fn wrong_nonce_fails() {
+        let cipher = AesGcm::new(KEY);
+        let sealed = assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT));
+        assert_err!(cipher.open(&[0; 12], AAD, &sealed));
+    }
+
+    #[test]
+    fn truncated_input_fails() {
+        let cipher = AesGcm::new(KEY);
+        assert_err!(cipher.open(&NONCE, AAD, &[0; GCM_TAG_SIZE - 1]));
+    }
+
+    #[test]
+    fn empty_nonce_fails() {
+        let cipher = AesGcm::new(KEY);
+        assert_err!(cipher.seal(&[], AAD, PLAINTEXT));
+    }
+
+    #[test]
+    fn inc32_wraps_low_word_only() {
+        let block = Block128::new(0x0000_0000_0000_0001_FFFF_FFFF_FFFF_FFFF);
+        assert_eq!(
+            inc32(block).as_u128(),
+            0x0000_0000_0000_0001_FFFF_FFFF_0000_0000
+        );
+    }
+}
@@ -0,0 +1,333 @@
+//! AES-GCM-SIV nonce-misuse-resistant authenticated encryption (RFC 8452).
+//!
+//! Each nonce derives a fresh message-authentication key and message-encryption
+//! key from the key-generating key. The tag is computed with [`Polyval`] over
+//! the associated data and plaintext and then used as the initial counter,
+//! so repeating a nonce only reveals whether the same message was encrypted
+//! twice under it.
+
+use crate::{Aes, Block128, KeySize, Polyval, key::Key};
+use cipher_core::{Aead, CipherError, CipherResult, ct_eq};
+use std::fmt::Debug;
+use zeroize::{Zeroize, Zeroizing};
+
+const BLOCK_SIZE: usize = 16;
+
+/// Length in bytes of the authentication tag.
+pub const GCM_SIV_TAG_SIZE: usize = 16;
+
+/// Length in bytes of the nonce.
+pub const GCM_SIV_NONCE_SIZE: usize = 12;
+
+/// Largest plaintext that can be encrypted under one nonce: 2^36 bytes, the
+/// span of the 32-bit block counter.
+const MAX_PLAINTEXT_LEN: u64 = 1 << 36;
+
+/// AES-GCM-SIV with a 128 or 256-bit key-generating key.
+///
+/// # Example
+///
+/// ```
+/// use aes::AesGcmSiv;
+/// use cipher_core::Aead;
+///
+/// let cipher = AesGcmSiv::new(0x01000000_00000000_00000000_00000000_u128).unwrap();
+/// let nonce = [0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+///
+/// let sealed = cipher.seal(&nonce, b"header", b"Hello, World!").unwrap();
+/// assert_eq!(sealed.len(), 13 + 16);
+///
+/// let opened = cipher.open(&nonce, b"header", &sealed).unwrap();
+/// assert_eq!(opened, b"Hello, World!");
+/// ```
+pub struct AesGcmSiv {
+    aes: Aes,
+}
+
+impl AesGcmSiv {
+    /// Creates AES-GCM-SIV from the key-generating key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` for 192-bit keys, which RFC 8452
+    /// does not define.
+    pub fn new(key: impl Into<Key>) -> CipherResult<Self> {
+        let key = key.into();
+        if key.size() == KeySize::Aes192 {
+            return Err(CipherError::invalid_key_size(
+                KeySize::Aes256.bytes(),
+                key.size().bytes(),
+            ));
+        }
+
+        Ok(Self {
+            aes: Aes::from_key(key),
+        })
+    }
+
+    /// Returns the size of the key-generating key.
+    #[inline]
+    #[must_use]
+    pub const fn key_size(&self) -> KeySize {
+        self.aes.key_size()
+    }
+
+    /// Encrypts `plaintext` and returns the ciphertext and the tag separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the nonce is not 12 bytes.
+    /// Returns `CipherError::CounterOverflow` if the plaintext is longer than
+    /// 2^36 bytes.
+    pub fn encrypt_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> CipherResult<(Vec<u8>, [u8; GCM_SIV_TAG_SIZE])> {
+        let nonce = check_inputs(nonce, plaintext.len())?;
+
+        let (polyval, encryption) = self.derive_keys(&nonce);
+        let tag = compute_tag(&encryption, polyval, &nonce, associated_data, plaintext);
+
+        let mut ciphertext = plaintext.to_vec();
+        apply_ctr(&encryption, tag, &mut ciphertext);
+        Ok((ciphertext, tag))
+    }
+
+    /// Decrypts `ciphertext` and verifies `tag`.
+    ///
+    /// No plaintext is returned unless the tag matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the nonce is not 12 bytes.
+    /// Returns `CipherError::CounterOverflow` if the ciphertext is longer than
+    /// 2^36 bytes.
+    /// Returns `CipherError::AuthenticationFailed` if the tag does not match.
+    pub fn decrypt_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8],
+    ) -> CipherResult<Vec<u8>> {
+        let nonce = check_inputs(nonce, ciphertext.len())?;
+        let Ok(tag) = <[u8; GCM_SIV_TAG_SIZE]>::try_from(tag) else {
+            return Err(CipherError::AuthenticationFailed);
+        };
+
+        let (polyval, encryption) = self.derive_keys(&nonce);
+        let mut plaintext = ciphertext.to_vec();
+        apply_ctr(&encryption, tag, &mut plaintext);
+
+        let expected = compute_tag(&encryption, polyval, &nonce, associated_data, &plaintext);
+        if !ct_eq(&expected, &tag) {
+            plaintext.zeroize();
+            return Err(CipherError::AuthenticationFailed);
+        }
+        Ok(plaintext)
+    }
+
+    /// Derives the per-nonce authentication and encryption keys.
+    ///
+    /// Each key is built from the first half of `AES(K, LE32(i) || nonce)`
+    /// for consecutive counters `i`.
+    fn derive_keys(&self, nonce: &[u8; GCM_SIV_NONCE_SIZE]) -> (Polyval, Aes) {
+        let halves = 2 + self.key_size().bytes() / 8;
+        let mut material = Zeroizing::new(Vec::with_capacity(halves * 8));
+        for counter in (0u32..).take(halves) {
+            let mut block = [0; BLOCK_SIZE];
+            block[..4].copy_from_slice(&counter.to_le_bytes());
+            block[4..].copy_from_slice(nonce);
+            let output = self.aes.encrypt_block(Block128::from_be_bytes(block));
+            material.extend_from_slice(&output.to_be_bytes()[..8]);
+        }
+
+        let (authentication, encryption) = material.split_at(BLOCK_SIZE);
+        let mut hash_key = [0; BLOCK_SIZE];
+        hash_key.copy_from_slice(authentication);
+        let polyval = Polyval::new(hash_key);
+        hash_key.zeroize();
+
+        let encryption = Key::from_slice(encryption).expect("derived key is 16 or 32 bytes");
+        (polyval, Aes::from_key(encryption))
+    }
+}
+
+/// Omits the cipher, which holds key material.
+impl Debug for AesGcmSiv {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesGcmSiv")
+            .field("key_size", &self.key_size())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Aead for AesGcmSiv {
+    fn tag_size(&self) -> usize {
+        GCM_SIV_TAG_SIZE
+    }
+
+    fn seal(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> CipherResult<Vec<u8>> {
+        let (mut sealed, tag) = self.encrypt_detached(nonce, associated_data, plaintext)?;
+        sealed.extend_from_slice(&tag);
+        Ok(sealed)
+    }
+
+    fn open(&self, nonce: &[u8], associated_data: &[u8], sealed: &[u8]) -> CipherResult<Vec<u8>> {
+        let Some(split) = sealed.len().checked_sub(GCM_SIV_TAG_SIZE) else {
+            return Err(CipherError::AuthenticationFailed);
+        };
+        let (ciphertext, tag) = sealed.split_at(split);
+        self.decrypt_detached(nonce, associated_data, ciphertext, tag)
+    }
+}
+
+fn check_inputs(nonce: &[u8], len: usize) -> CipherResult<[u8; GCM_SIV_NONCE_SIZE]> {
+    let Ok(nonce) = <[u8; GCM_SIV_NONCE_SIZE]>::try_from(nonce) else {
+        return Err(CipherError::invalid_iv_size(
+            GCM_SIV_NONCE_SIZE,
+            nonce.len(),
+        ));
+    };
+    if len as u64 > MAX_PLAINTEXT_LEN {
+        return Err(CipherError::counter_overflow(32));
+    }
+    Ok(nonce)
+}
+
+/// Hashes the padded associated data, padded plaintext and their bit lengths
+/// with [`Polyval`], masks the result with the nonce and encrypts it.
+fn compute_tag(
+    encryption: &Aes,
+    mut polyval: Polyval,
+    nonce: &[u8; GCM_SIV_NONCE_SIZE],
+    associated_data: &[u8],
+    plaintext: &[u8],
+) -> [u8; GCM_SIV_TAG_SIZE] {
+    polyval.update_padded(associated_data);
+    polyval.update_padded(plaintext);
+
+    let mut lengths = [0; BLOCK_SIZE];
+    lengths[..8].copy_from_slice(&bit_len(associated_data).to_le_bytes());
+    lengths[8..].copy_from_slice(&bit_len(plaintext).to_le_bytes());
+    polyval.update_block(lengths);
+
+    let mut s = polyval.finalize();
+    for (byte, nonce_byte) in s.iter_mut().zip(nonce) {
+        *byte ^= nonce_byte;
+    }
+    s[BLOCK_SIZE - 1] &= 0x7F;
+    encryption
+        .encrypt_block(Block128::from_be_bytes(s))
+        .to_be_bytes()
+}
+
+/// Applies the keystream; the counter block is the tag with its top bit set,
+/// and only its first 32 bits, read little-endian, are incremented.
+fn apply_ctr(encryption: &Aes, tag: [u8; GCM_SIV_TAG_SIZE], data: &mut [u8]) {
+    let mut counter_block = tag;
+    counter_block[BLOCK_SIZE - 1] |= 0x80;
+    let initial = u32::from_le_bytes([
+        counter_block[0],
+        counter_block[1],
+        counter_block[2],
+        counter_block[3],
+    ]);
+
+    for (counter, chunk) in (0u32..)
+        .map(|i| initial.wrapping_add(i))
+        .zip(data.chunks_mut(BLOCK_SIZE))
+    {
+        counter_block[..4].copy_from_slice(&counter.to_le_bytes());
+        let keystream = encryption
+            .encrypt_block(Block128::from_be_bytes(counter_block))
+            .to_be_bytes();
+        for (byte, key_byte) in chunk.iter_mut().zip(keystream) {
+            *byte ^= key_byte;
+        }
+    }
+}
+
+const fn bit_len(data: &[u8]) -> u64 {
+    (data.len() as u64) * 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_matches, assert_ok};
+
+    const KEY: u128 = 0x0100_0000_0000_0000_0000_0000_0000_0000;
+    const NONCE: [u8; GCM_SIV_NONCE_SIZE] = [3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    const AAD: &[u8] = b"header";
+    const PLAINTEXT: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn seal_open_roundtrip() {
+        let cipher = assert_ok!(AesGcmSiv::new(KEY));
+        let sealed = assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT));
+        assert_eq!(sealed.len(), PLAINTEXT.len() + GCM_SIV_TAG_SIZE);
+        assert_eq!(assert_ok!(cipher.open(&NONCE, AAD, &sealed)), PLAINTEXT);
+    }
+
+    #[test]
+    fn repeated_nonce_only_reveals_equal_messages() {
+        let cipher = assert_ok!(AesGcmSiv::new(KEY));
+        let first = assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT));
+        assert_eq!(assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT)), first);
+
+        // A different message under the same nonce gets a different tag, so
+        // its keystream is unrelated and XORing the ciphertexts leaks nothing
+        let mut other = PLAINTEXT.to_vec();
+        other[0] ^= 1;
+        let second = assert_ok!(cipher.seal(&NONCE, AAD, &other));
+        let keystream = |sealed: &[u8], plaintext: &[u8]| -> Vec<u8> {
+            sealed.iter().zip(plaintext).map(|(c, p)| c ^ p).collect()
+        };
+        assert_ne!(keystream(&first, PLAINTEXT), keystream(&second, &other));
+    }
+
+    #[test]
+    fn tampering_fails() {
+        let cipher = assert_ok!(AesGcmSiv::new(KEY));
+        let sealed = assert_ok!(cipher.seal(&NONCE, AAD, PLAINTEXT));
+
+        let mut tampered = sealed.clone();
+        tampered[0] ^= 1;
+        let err = assert_err!(cipher.open(&NONCE, AAD, &tampered));
+        assert_matches!(err, CipherError::AuthenticationFailed);
+
+        let mut tampered = sealed.clone();
+        tampered[sealed.len() - 1] ^= 1;
+        assert_err!(cipher.open(&NONCE, AAD, &tampered));
+
+        assert_err!(cipher.open(&NONCE, b"other data", &sealed));
+        assert_err!(cipher.open(&[0; 12], AAD, &sealed));
+        assert_err!(cipher.open(&NONCE, AAD, &sealed[..GCM_SIV_TAG_SIZE - 1]));
+    }
+
+    #[test]
+    fn wrong_nonce_length_fails() {
+        let cipher = assert_ok!(AesGcmSiv::new(KEY));
+        let err = assert_err!(cipher.seal(&[0; 16], AAD, PLAINTEXT));
+        assert_matches!(
+            err,
+            CipherError::InvalidIvSize {
+                expected: 12,
+                actual: 16
+            }
+        );
+    }
+
+    #[test]
+    fn aes192_key_fails() {
+        assert_err!(AesGcmSiv::new([0; 24]));
+    }
+}
@@ -0,0 +1,166 @@
+//! Arithmetic in GF(2^8), the finite field AES is built on.
+//!
+//! Elements are bytes in the polynomial basis: bit `i` is the coefficient of
+//! `x^i`, and products are reduced modulo the AES polynomial
+//! `x^8 + x^4 + x^3 + x + 1` ([`MODULUS`]). Addition is XOR.
+//!
+//! Every function is a `const fn`, so the AES tables are derived from them at
+//! compile time and the same code serves other byte-oriented constructions
+//! such as Reed–Solomon codes and secret sharing. Multiplication and inversion
+//! do not branch on the values of their operands.
+//!
+//! # Example
+//! ```
+//! use aes::gf256;
+//!
+//! // FIPS-197 section 4.2
+//! assert_eq!(gf256::mul(0x57, 0x83), 0xC1);
+//! assert_eq!(gf256::mul(0x53, gf256::inv(0x53)), 0x01);
+//! ```
+
+/// The AES reduction polynomial `x^8 + x^4 + x^3 + x + 1`.
+pub const MODULUS: u16 = 0x11B;
+
+/// Adds two elements, which is the same as subtracting them.
+#[inline]
+#[must_use]
+pub const fn add(a: u8, b: u8) -> u8 {
+    a ^ b
+}
+
+/// Multiplies by `x` (`{02}`), the `xtime` operation of FIPS-197.
+///
+/// If the high bit is set, the shifted value is reduced by [`MODULUS`].
+#[inline]
+#[must_use]
+pub const fn xtime(a: u8) -> u8 {
+    (a << 1) ^ (0x1B & (a >> 7).wrapping_neg())
+}
+
+/// Multiplies two elements with shift-and-add over the bits of `b`.
+#[must_use]
+pub const fn mul(mut a: u8, b: u8) -> u8 {
+    let mut product = 0;
+    let mut bit = 0;
+    while bit < 8 {
+        product ^= a & ((b >> bit) & 1).wrapping_neg();
+        a = xtime(a);
+        bit += 1;
+    }
+    product
+}
+
+/// Raises `a` to the power `exp` by square-and-multiply.
+///
+/// `pow(0, 0)` is 1.
+#[must_use]
+pub const fn pow(a: u8, mut exp: u32) -> u8 {
+    let mut result = 1;
+    let mut base = a;
+    while exp > 0 {
+        if exp & 1 != 0 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Returns the multiplicative inverse of `a`, computed as `a^254`.
+///
+/// Zero has no inverse and maps to zero, as the AES S-box requires.
+#[must_use]
+pub const fn inv(a: u8) -> u8 {
+    pow(a, 254)
+}
+
+/// Divides `a` by `b`, or returns `None` if `b` is zero.
+#[must_use]
+pub const fn div(a: u8, b: u8) -> Option<u8> {
+    if b == 0 { None } else { Some(mul(a, inv(b))) }
+}
+
+/// Returns the products of every element with `factor`, indexed by element.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn mul_table(factor: u8) -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = mul(i as u8, factor);
+        i += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0x57, 0x13, 0xFE)] // Example from FIPS-197 4.2.1
+    #[case(0x57, 0x83, 0xC1)] // Example from FIPS-197 4.2
+    #[case(0x57, 0x01, 0x57)] // Identity
+    #[case(0x57, 0x02, 0xAE)] // x2 (xtime)
+    #[case(0x57, 0x04, 0x47)] // x4
+    #[case(0x57, 0x08, 0x8E)] // x8
+    #[case(0x57, 0x10, 0x07)] // x16
+    #[case(0x57, 0x00, 0x00)]
+    fn multiplication(#[case] a: u8, #[case] b: u8, #[case] expected: u8) {
+        assert_eq!(mul(a, b), expected, "mul({a:02x}, {b:02x}) failed");
+        assert_eq!(mul(b, a), expected, "mul({b:02x}, {a:02x}) failed");
+    }
+
+    #[test]
+    fn xtime_matches_polynomial_reduction() {
+        for a in 0..=u8::MAX {
+            let shifted = u16::from(a) << 1;
+            let reduced = if shifted & 0x100 == 0 {
+                shifted
+            } else {
+                shifted ^ MODULUS
+            };
+            assert_eq!(u16::from(xtime(a)), reduced, "{a:02x}");
+        }
+    }
+
+    #[test]
+    fn every_nonzero_element_has_an_inverse() {
+        assert_eq!(inv(0), 0);
+        for a in 1..=u8::MAX {
+            assert_eq!(mul(a, inv(a)), 1, "{a:02x}");
+        }
+    }
+
+    #[test]
+    fn generator_powers_cover_the_group() {
+        // {03} generates the multiplicative group of order 255
+        let mut seen = [false; 256];
+        for exp in 0..255 {
+            seen[usize::from(pow(0x03, exp))] = true;
+        }
+        assert!(!seen[0]);
+        assert!(seen[1..].iter().all(|&hit| hit));
+        assert_eq!(pow(0x03, 255), 1);
+        assert_eq!(pow(0x00, 0), 1);
+    }
+
+    #[test]
+    fn division_inverts_multiplication() {
+        assert_eq!(div(0x57, 0), None);
+        for b in 1..=u8::MAX {
+            assert_eq!(div(mul(0xC1, b), b), Some(0xC1), "{b:02x}");
+        }
+    }
+
+    #[test]
+    fn multiplication_table() {
+        let table = mul_table(0x13);
+        assert_eq!(table[0x57], 0xFE);
+        for (a, &product) in (0..=u8::MAX).zip(&table) {
+            assert_eq!(product, mul(a, 0x13));
+        }
+    }
+}
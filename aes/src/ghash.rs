@@ -0,0 +1,125 @@
+//! GHASH universal hash over GF(2^128), as used by GCM (NIST SP 800-38D).
+//!
+//! Blocks are interpreted as polynomials with the most significant bit of the
+//! first byte as the coefficient of x^0 (GCM's "reflected" bit order) and are
+//! reduced modulo x^128 + x^7 + x^2 + x + 1.
+
+use crate::Block128;
+use zeroize::ZeroizeOnDrop;
+
+const BLOCK_SIZE: usize = 16;
+
+/// Reduction polynomial x^7 + x^2 + x + 1 in GCM's bit order.
+const R: u128 = 0xE1 << 120;
+
+/// Multiplies two elements of GF(2^128) in GCM's bit order.
+///
+/// Uses masks instead of branches, so the running time does not depend on
+/// the operands.
+#[must_use]
+pub const fn gf128_mul(lhs: u128, rhs: u128) -> u128 {
+    let mut product = 0;
+    let mut shifted = rhs;
+    let mut degree = 0;
+
+    while degree < 128 {
+        // Coefficient `degree` of lhs is bit 127 - degree of the integer
+        let bit = (lhs >> (127 - degree)) & 1;
+        product ^= shifted & 0u128.wrapping_sub(bit);
+
+        // Multiply by x: shift towards higher degrees and reduce
+        let carry = shifted & 1;
+        shifted = (shifted >> 1) ^ (R & 0u128.wrapping_sub(carry));
+        degree += 1;
+    }
+
+    product
+}
+
+/// Incremental GHASH computation keyed by the hash subkey `H`.
+///
+/// `Y_i = (Y_{i-1} ^ X_i) * H` for every 128-bit input block `X_i`.
+#[derive(ZeroizeOnDrop)]
+pub struct Ghash {
+    h: u128,
+    state: u128,
+}
+
+impl Ghash {
+    /// Creates a GHASH instance with hash subkey `h`.
+    ///
+    /// In GCM the subkey is the encryption of the all-zero block.
+    #[inline]
+    #[must_use]
+    pub const fn new(h: Block128) -> Self {
+        Self {
+            h: h.as_u128(),
+            state: 0,
+        }
+    }
+
+    /// Absorbs a single 128-bit block.
+    pub const fn update_block(&mut self, block: Block128) {
+        self.state = gf128_mul(self.state ^ block.as_u128(), self.h);
+    }
+
+    /// Absorbs `data`, zero-padding the final partial block.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        for chunk in data.chunks(BLOCK_SIZE) {
+            let mut block = [0; BLOCK_SIZE];
+            block[..chunk.len()].copy_from_slice(chunk);
+            self.update_block(Block128::from_be_bytes(block));
+        }
+    }
+
+    /// Returns the current hash value.
+    #[inline]
+    #[must_use]
+    pub const fn finalize(&self) -> Block128 {
+        Block128::new(self.state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// The polynomial "1" is the block with only the first bit set.
+    const ONE: u128 = 1 << 127;
+
+    #[rstest]
+    #[case(0x66E9_4BD4_EF8A_2C3B_884C_FA59_CA34_2B2E)]
+    #[case(0x0388_DACE_60B6_A392_F328_C2B9_71B2_FE78)]
+    fn multiplicative_identity(#[case] x: u128) {
+        assert_eq!(gf128_mul(x, ONE), x);
+        assert_eq!(gf128_mul(ONE, x), x);
+        assert_eq!(gf128_mul(x, 0), 0);
+    }
+
+    #[test]
+    fn multiplication_commutes() {
+        let a = 0x66E9_4BD4_EF8A_2C3B_884C_FA59_CA34_2B2E;
+        let b = 0x0388_DACE_60B6_A392_F328_C2B9_71B2_FE78;
+        assert_eq!(gf128_mul(a, b), gf128_mul(b, a));
+    }
+
+    #[test]
+    fn reduction_wraps_x127() {
+        // x^127 * x = x^128 = x^7 + x^2 + x + 1
+        assert_eq!(gf128_mul(1, ONE >> 1), R);
+    }
+
+    // GCM specification test case 2: GHASH(H, {}, C)
+    #[test]
+    fn gcm_test_case_2() {
+        let h = Block128::new(0x66E9_4BD4_EF8A_2C3B_884C_FA59_CA34_2B2E);
+        let mut ghash = Ghash::new(h);
+        ghash.update_padded(&0x0388_DACE_60B6_A392_F328_C2B9_71B2_FE78_u128.to_be_bytes());
+        ghash.update_block(Block128::new(0x80));
+        assert_eq!(
+            ghash.finalize().as_u128(),
+            0xF38C_BB1A_D692_23DC_C345_7AE5_B6B0_F885
+        );
+    }
+}
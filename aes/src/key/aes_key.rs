@@ -1,64 +1,86 @@
-use crate::Block128;
-use std::fmt::Debug;
+use crate::{Block128, key::KeySize};
+use cipher_core::{CipherError, parse_block_bytes};
+use std::{fmt::Debug, str::FromStr};
 use zeroize::ZeroizeOnDrop;
 
-/// 128-bit Key for AES
+const MAX_KEY_SIZE: usize = 32;
+
+/// 128, 192 or 256-bit Key for AES
 #[derive(ZeroizeOnDrop)]
-pub struct Key([u8; 16]);
+pub struct Key {
+    bytes: [u8; MAX_KEY_SIZE],
+    #[zeroize(skip)]
+    size: KeySize,
+}
 
 impl Key {
-    #[inline]
-    #[must_use]
-    pub const fn from_array(bytes: [u8; 16]) -> Self {
-        Self(bytes)
+    /// Creates a key from exactly 16, 24 or 32 bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` if `bytes` is not a valid AES key length.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, CipherError> {
+        let size = KeySize::from_bytes(bytes.len()).ok_or_else(|| {
+            let expected = KeySize::fitting(bytes.len()).map_or(MAX_KEY_SIZE, KeySize::bytes);
+            CipherError::invalid_key_size(expected, bytes.len())
+        })?;
+
+        Ok(Self::from_sized(bytes, size))
     }
 
-    #[inline]
-    #[must_use]
-    pub const fn as_array(&self) -> &[u8; 16] {
-        &self.0
+    /// Builds a key from `bytes`, which must be `size` bytes long.
+    fn from_sized(bytes: &[u8], size: KeySize) -> Self {
+        let mut key = [0; MAX_KEY_SIZE];
+        key[..size.bytes()].copy_from_slice(bytes);
+        Self { bytes: key, size }
     }
 
+    /// Returns the key size.
     #[inline]
     #[must_use]
-    pub const fn as_2d(&self) -> [[u8; 4]; 4] {
-        [
-            [self.0[0], self.0[1], self.0[2], self.0[3]],
-            [self.0[4], self.0[5], self.0[6], self.0[7]],
-            [self.0[8], self.0[9], self.0[10], self.0[11]],
-            [self.0[12], self.0[13], self.0[14], self.0[15]],
-        ]
+    pub const fn size(&self) -> KeySize {
+        self.size
     }
 
+    /// Returns the key bytes.
     #[inline]
     #[must_use]
-    pub const fn as_u128(&self) -> u128 {
-        u128::from_be_bytes(self.0)
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.size.bytes()]
     }
-}
 
-impl From<[[u8; 4]; 4]> for Key {
-    fn from(matrix: [[u8; 4]; 4]) -> Self {
-        let mut bytes = [0; 16];
-        for (idx, row) in matrix.iter().enumerate() {
-            bytes[idx * 4..(idx + 1) * 4].copy_from_slice(row);
-        }
-        Self(bytes)
+    /// Returns the key as big-endian 32-bit words.
+    pub fn words(&self) -> impl Iterator<Item = u32> + '_ {
+        self.as_bytes()
+            .chunks_exact(4)
+            .map(|word| u32::from_be_bytes([word[0], word[1], word[2], word[3]]))
     }
 }
 
 impl From<[u8; 16]> for Key {
     fn from(bytes: [u8; 16]) -> Self {
-        Self(bytes)
+        Self::from_sized(&bytes, KeySize::Aes128)
     }
 }
 
-impl From<&[u8]> for Key {
-    fn from(value: &[u8]) -> Self {
-        let mut bytes = [0; 16];
-        let len = value.len().min(16);
-        bytes[..len].copy_from_slice(&value[..len]);
-        bytes.into()
+impl From<[u8; 24]> for Key {
+    fn from(bytes: [u8; 24]) -> Self {
+        Self::from_sized(&bytes, KeySize::Aes192)
+    }
+}
+
+impl From<[u8; 32]> for Key {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self::from_sized(&bytes, KeySize::Aes256)
+    }
+}
+
+/// Accepts exactly 16, 24 or 32 bytes, like [`Key::from_slice`].
+impl TryFrom<&[u8]> for Key {
+    type Error = CipherError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
     }
 }
 
@@ -74,26 +96,88 @@ impl From<Block128> for Key {
     }
 }
 
-impl From<Key> for [u8; 16] {
-    fn from(key: Key) -> Self {
-        key.0
-    }
-}
-
-impl From<Key> for [[u8; 4]; 4] {
-    fn from(key: Key) -> Self {
-        key.as_2d()
+/// Parses a key in hex (`0x`), binary (`0b`) or ASCII format.
+///
+/// The input must be exactly 16, 24 or 32 bytes wide: 32, 48 or 64 hex
+/// digits, or as many ASCII characters. Leading zeros count towards the
+/// width, so nothing is padded or truncated.
+impl FromStr for Key {
+    type Err = CipherError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_slice(&parse_block_bytes(s)?)
     }
 }
 
 impl AsRef<[u8]> for Key {
     fn as_ref(&self) -> &[u8] {
-        &self.0
+        self.as_bytes()
     }
 }
 
 impl Debug for Key {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("Key([REDACTED])")
+        write!(f, "Key({}, [REDACTED])", self.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_ok};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("0x2B7E151628AED2A6ABF7158809CF4F3C", KeySize::Aes128)]
+    #[case("0x8E73B0F7DA0E6452C810F32B809079E562F8EAD2522C6B7B", KeySize::Aes192)]
+    #[case(
+        "0x603DEB1015CA71BE2B73AEF0857D77811F352C073B6108D72D9810A30914DFF4",
+        KeySize::Aes256
+    )]
+    #[case("0x000102030405060708090A0B0C0D0E0F", KeySize::Aes128)]
+    #[case("a 24 character long key!", KeySize::Aes192)]
+    fn key_size_from_str(#[case] input: &str, #[case] expected: KeySize) {
+        let key = assert_ok!(input.parse::<Key>());
+        assert_eq!(key.size(), expected);
+        assert_eq!(key.as_bytes().len(), expected.bytes());
+    }
+
+    #[rstest]
+    #[case("0x01")]
+    #[case(format!("0x{}", "AB".repeat(17)))]
+    #[case(format!("0x{}", "AB".repeat(33)))]
+    #[case("a 23 character long key")]
+    fn key_from_str_invalid_length_fails(#[case] input: impl AsRef<str>) {
+        let input = input.as_ref();
+        let len = parse_block_bytes(input).map_or(0, |bytes| bytes.len());
+        let err = assert_err!(input.parse::<Key>());
+        assert!(matches!(err, CipherError::InvalidKeySize { actual, .. } if actual == len));
+    }
+
+    #[rstest]
+    #[case(16)]
+    #[case(24)]
+    #[case(32)]
+    fn key_from_slice(#[case] len: usize) {
+        let key = assert_ok!(Key::from_slice(&vec![0xAA; len]));
+        assert_eq!(key.as_bytes().len(), len);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(15)]
+    #[case(17)]
+    #[case(20)]
+    #[case(33)]
+    #[case(48)]
+    fn key_from_slice_invalid_length_fails(#[case] len: usize) {
+        assert_err!(Key::from_slice(&vec![0xAA; len]));
+        assert_err!(Key::try_from(vec![0xAA; len].as_slice()));
+    }
+
+    #[test]
+    fn key_words() {
+        let key = Key::from(0x2B7E_1516_28AE_D2A6_ABF7_1588_09CF_4F3C_u128);
+        let words = key.words().collect::<Vec<_>>();
+        assert_eq!(words, [0x2B7E_1516, 0x28AE_D2A6, 0xABF7_1588, 0x09CF_4F3C]);
     }
 }
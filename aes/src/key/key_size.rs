@@ -0,0 +1,83 @@
+use std::fmt::Display;
+
+/// AES key length as defined by FIPS-197.
+///
+/// The key length determines the number of 32-bit key words (`Nk`) and the
+/// number of rounds (`Nr`) of the cipher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum KeySize {
+    /// 128-bit key: `Nk = 4`, `Nr = 10`.
+    #[default]
+    Aes128,
+    /// 192-bit key: `Nk = 6`, `Nr = 12`.
+    Aes192,
+    /// 256-bit key: `Nk = 8`, `Nr = 14`.
+    Aes256,
+}
+
+impl KeySize {
+    /// All supported key sizes, from smallest to largest.
+    pub const ALL: [Self; 3] = [Self::Aes128, Self::Aes192, Self::Aes256];
+
+    /// Key length in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn bytes(self) -> usize {
+        match self {
+            Self::Aes128 => 16,
+            Self::Aes192 => 24,
+            Self::Aes256 => 32,
+        }
+    }
+
+    /// Key length in bits.
+    #[inline]
+    #[must_use]
+    pub const fn bits(self) -> usize {
+        self.bytes() * 8
+    }
+
+    /// Number of 32-bit words in the key (`Nk`).
+    #[inline]
+    #[must_use]
+    pub const fn words(self) -> usize {
+        self.bytes() / 4
+    }
+
+    /// Number of cipher rounds (`Nr`).
+    #[inline]
+    #[must_use]
+    pub const fn rounds(self) -> usize {
+        self.words() + 6
+    }
+
+    /// Returns the key size with exactly `len` bytes, if any.
+    #[inline]
+    #[must_use]
+    pub const fn from_bytes(len: usize) -> Option<Self> {
+        match len {
+            16 => Some(Self::Aes128),
+            24 => Some(Self::Aes192),
+            32 => Some(Self::Aes256),
+            _ => None,
+        }
+    }
+
+    /// Returns the smallest key size that can hold `len` bytes, if any.
+    #[inline]
+    #[must_use]
+    pub const fn fitting(len: usize) -> Option<Self> {
+        match len {
+            0..=16 => Some(Self::Aes128),
+            17..=24 => Some(Self::Aes192),
+            25..=32 => Some(Self::Aes256),
+            _ => None,
+        }
+    }
+}
+
+impl Display for KeySize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AES-{}", self.bits())
+    }
+}
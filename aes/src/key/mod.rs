@@ -1,6 +1,18 @@
+//! AES keys and the expanded key schedule.
+//!
+//! [`Subkeys`] exposes the round keys read-only, and
+//! [`Subkeys::recover_key`] runs the schedule backwards to get the cipher key
+//! back from recovered round key words.
+
 mod aes_key;
+mod key_size;
 mod subkey;
 mod subkeys;
 
 use cipher_core::secret_key;
-pub use {aes_key::Key, subkey::Subkey, subkeys::Subkeys};
+pub use {
+    aes_key::Key,
+    key_size::KeySize,
+    subkey::Subkey,
+    subkeys::{MAX_ROUNDS, SubkeyChunks, SubkeyChunksRev, Subkeys},
+};
@@ -8,6 +8,7 @@ secret_key! {
 
 impl Subkey {
     /// Zero value.
+    #[must_use]
     pub const fn zero() -> Self {
         Self(0)
     }
@@ -16,6 +17,7 @@ impl Subkey {
     /// wrapping the truncated bits to the end of the resulting integer.
     ///
     /// Please note this isn't the same operation as the `<<` shifting operator!
+    #[must_use]
     pub const fn rotate_left(self, n: u32) -> Self {
         Self(self.0.rotate_left(n))
     }
@@ -24,6 +26,7 @@ impl Subkey {
     /// wrapping the truncated bits to the beginning of the resulting integer.
     ///
     /// Please note this isn't the same operation as the `>>` shifting operator!
+    #[must_use]
     pub const fn rotate_right(self, n: u32) -> Self {
         Self(self.0.rotate_right(n))
     }
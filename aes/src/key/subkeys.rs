@@ -1,73 +1,254 @@
 use crate::{
     constants::RCON,
-    key::{Key, subkey::Subkey},
+    key::{Key, KeySize, subkey::Subkey},
     sbox::SboxLookup,
 };
+use cipher_core::{CipherError, CipherResult};
 use std::{
     fmt::Debug,
     iter::Rev,
     ops::Index,
-    slice::{ChunksExact, Iter, IterMut},
+    slice::{ChunksExact, Iter},
 };
-
-const SUBKEY_COUNT: usize = 44;
-
-// #[derive(Default)]
-pub struct Subkeys([Subkey; SUBKEY_COUNT]);
+use zeroize::Zeroizing;
+
+/// Most rounds a key schedule can provide, as many as AES-256 uses.
+pub const MAX_ROUNDS: usize = 14;
+
+/// Number of subkeys for the most rounds (15 round keys x 4).
+const MAX_SUBKEY_COUNT: usize = 4 * (MAX_ROUNDS + 1);
+
+/// The expanded key schedule: `4 * (Nr + 1)` words, four per round key.
+///
+/// Read-only once expanded, so the round keys can be inspected without
+/// changing the cipher that uses them.
+pub struct Subkeys {
+    keys: [Subkey; MAX_SUBKEY_COUNT],
+    /// Round keys for the equivalent inverse cipher (FIPS-197 section
+    /// 5.3.5): the middle round keys with `InvMixColumns` applied.
+    decryption_keys: [Subkey; MAX_SUBKEY_COUNT],
+    len: usize,
+    size: KeySize,
+}
 
 impl Subkeys {
-    /// Generates 44 round subkeys from the given key.
+    /// Generates `4 * (Nr + 1)` round subkeys from the given key.
+    ///
+    /// That is 44 subkeys for AES-128, 52 for AES-192 and 60 for AES-256.
     #[must_use]
     pub fn from_key(key: &Key) -> Self {
-        let mut subkeys = [const { Subkey::zero() }; 44];
+        Self::from_key_with(key, u32::sbox_lookup)
+    }
+
+    /// Generates the round subkeys, applying the S-box through `sub_word`.
+    ///
+    /// This lets a constant-time backend keep table lookups out of the key
+    /// expansion as well.
+    #[must_use]
+    pub fn from_key_with(key: &Key, sub_word: impl Fn(u32) -> u32) -> Self {
+        Self::from_key_with_rounds(key, key.size().rounds(), sub_word)
+    }
+
+    /// Generates round subkeys for `rounds` rounds instead of the standard
+    /// number for the key size.
+    ///
+    /// A shorter schedule is a prefix of the standard one; a longer one keeps
+    /// expanding with the same rule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rounds` is greater than [`MAX_ROUNDS`].
+    #[must_use]
+    pub fn from_key_with_rounds(key: &Key, rounds: usize, sub_word: impl Fn(u32) -> u32) -> Self {
+        assert!(rounds <= MAX_ROUNDS, "at most {MAX_ROUNDS} rounds");
+        let size = key.size();
+        let nk = size.words();
+        let len = 4 * (rounds + 1);
+        let mut subkeys = [const { Subkey::zero() }; MAX_SUBKEY_COUNT];
 
         // Load initial key
-        for (idx, &key) in key.as_2d().iter().enumerate() {
-            subkeys[idx] = Subkey::from_u32(u32::from_be_bytes(key));
+        for (idx, word) in key.words().enumerate() {
+            subkeys[idx] = Subkey::from_u32(word);
         }
 
-        for (round, &rcon) in RCON.iter().enumerate() {
-            let idx = round * 4 + 4;
+        for idx in nk..len {
+            let temp = schedule_temp(subkeys[idx - 1], idx, nk, &sub_word);
+            subkeys[idx] = subkeys[idx - nk] ^ Subkey::from_u32(temp);
+        }
 
-            subkeys[idx] = subkeys[idx - 4] ^ expand(subkeys[idx - 1], rcon);
-            subkeys[idx + 1] = subkeys[idx] ^ subkeys[idx - 3];
-            subkeys[idx + 2] = subkeys[idx + 1] ^ subkeys[idx - 2];
-            subkeys[idx + 3] = subkeys[idx + 2] ^ subkeys[idx - 1];
+        let mut decryption_keys = subkeys;
+        for key in decryption_keys.iter_mut().take(len - 4).skip(4) {
+            *key = Subkey::from_u32(inv_mix_word(key.as_u32()));
         }
 
-        Self(subkeys)
+        Self {
+            keys: subkeys,
+            decryption_keys,
+            len,
+            size,
+        }
+    }
+
+    /// Returns the key size these subkeys were expanded from.
+    #[inline]
+    #[must_use]
+    pub const fn key_size(&self) -> KeySize {
+        self.size
+    }
+
+    /// Returns the number of cipher rounds (`Nr`).
+    #[inline]
+    #[must_use]
+    pub const fn rounds(&self) -> usize {
+        self.len / 4 - 1
+    }
+
+    /// Returns the number of subkeys.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no subkeys.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the subkeys in order.
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[Subkey] {
+        &self.keys[..self.len]
     }
 
     /// Returns an iterator over the subkeys.
     pub fn iter(&self) -> Iter<'_, Subkey> {
-        self.0.iter()
+        self.as_slice().iter()
     }
 
     /// Returns a reverse iterator over the subkeys.
     pub fn iter_rev(&self) -> Rev<Iter<'_, Subkey>> {
-        self.0.iter().rev()
+        self.as_slice().iter().rev()
     }
 
-    /// Returns a mutable iterator over the subkeys.
-    pub fn iter_mut(&mut self) -> IterMut<'_, Subkey> {
-        self.0.iter_mut()
+    /// Returns the first element of the slice, or `None` if it is empty.
+    #[must_use]
+    pub fn first(&self) -> Option<&Subkey> {
+        self.as_slice().first()
     }
 
-    /// Returns the first element of the slice, or `None` if it is empty.
-    pub const fn first(&self) -> Option<&Subkey> {
-        self.0.first()
+    /// Returns the round key of `round` as 16 bytes, or `None` if the
+    /// schedule has fewer rounds.
+    #[must_use]
+    pub fn round_key(&self, round: usize) -> Option<[u8; 16]> {
+        let words = self.chunks().nth(round)?;
+        let mut bytes = [0; 16];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        Some(bytes)
+    }
+
+    /// Recovers an AES-128 key from the round key of any single round by
+    /// running the key schedule backwards.
+    ///
+    /// One round key holds four schedule words, which is all of an AES-128
+    /// key but not enough for longer keys; use
+    /// [`recover_key`](Self::recover_key) with words from two consecutive
+    /// round keys for AES-192 and AES-256.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidRounds` if `round` is greater than
+    /// [`MAX_ROUNDS`].
+    pub fn recover_key_from_round(round: usize, round_key: [u8; 16]) -> CipherResult<Key> {
+        if round > MAX_ROUNDS {
+            return Err(CipherError::invalid_rounds(0, MAX_ROUNDS, round));
+        }
+        let mut words = Zeroizing::new([0; 4]);
+        for (word, bytes) in words.iter_mut().zip(round_key.chunks_exact(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        Self::recover_key(KeySize::Aes128, 4 * round, words.as_slice())
+    }
+
+    /// Recovers a cipher key of `size` from `Nk` consecutive key schedule
+    /// words, the first of which is word `start` of the schedule.
+    ///
+    /// Each schedule word is the XOR of the word `Nk` places earlier and a
+    /// function of the word just before it, so `Nk` consecutive words
+    /// determine every earlier word down to the key itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` if `words` does not hold exactly
+    /// `Nk` words, or `CipherError::InvalidRounds` if they run past the end of
+    /// the longest schedule.
+    pub fn recover_key(size: KeySize, start: usize, words: &[u32]) -> CipherResult<Key> {
+        let nk = size.words();
+        if words.len() != nk {
+            return Err(CipherError::invalid_key_size(size.bytes(), 4 * words.len()));
+        }
+        if start + nk > MAX_SUBKEY_COUNT {
+            return Err(CipherError::invalid_rounds(
+                0,
+                (MAX_SUBKEY_COUNT - nk) / 4,
+                start / 4,
+            ));
+        }
+
+        let mut schedule = Zeroizing::new([0; MAX_SUBKEY_COUNT]);
+        schedule[start..start + nk].copy_from_slice(words);
+        for idx in (nk..start + nk).rev() {
+            let prev = Subkey::from_u32(schedule[idx - 1]);
+            schedule[idx - nk] = schedule[idx] ^ schedule_temp(prev, idx, nk, u32::sbox_lookup);
+        }
+
+        let mut bytes = Zeroizing::new(vec![0; size.bytes()]);
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(schedule.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        Key::from_slice(&bytes)
     }
 
     #[inline]
     #[must_use]
     pub fn chunks(&self) -> SubkeyChunks<'_> {
-        SubkeyChunks(self.0.chunks_exact(4))
+        SubkeyChunks(self.as_slice().chunks_exact(4))
     }
 
     #[inline]
     #[must_use]
     pub fn chunks_rev(&self) -> SubkeyChunksRev<'_> {
-        SubkeyChunksRev(self.0.chunks_exact(4).rev())
+        SubkeyChunksRev(self.as_slice().chunks_exact(4).rev())
+    }
+
+    /// Returns the round keys of the equivalent inverse cipher, in the same
+    /// order as the encryption subkeys.
+    ///
+    /// Round keys 0 and `Nr` are unchanged; the ones in between have
+    /// `InvMixColumns` applied, so decryption rounds can run `InvSubBytes`,
+    /// `InvShiftRows`, `InvMixColumns` and `AddRoundKey` in the same order as
+    /// encryption.
+    #[inline]
+    #[must_use]
+    pub fn decryption_keys(&self) -> &[Subkey] {
+        &self.decryption_keys[..self.len]
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn decryption_chunks(&self) -> SubkeyChunks<'_> {
+        SubkeyChunks(self.decryption_keys().chunks_exact(4))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn decryption_chunks_rev(&self) -> SubkeyChunksRev<'_> {
+        SubkeyChunksRev(self.decryption_keys().chunks_exact(4).rev())
     }
 }
 
@@ -79,18 +260,10 @@ impl<'a> IntoIterator for &'a Subkeys {
     }
 }
 
-impl<'a> IntoIterator for &'a mut Subkeys {
-    type Item = &'a mut Subkey;
-    type IntoIter = IterMut<'a, Subkey>;
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
-    }
-}
-
 impl Index<usize> for Subkeys {
     type Output = Subkey;
     fn index(&self, index: usize) -> &Self::Output {
-        &self.0[index]
+        &self.as_slice()[index]
     }
 }
 
@@ -100,6 +273,7 @@ impl Debug for Subkeys {
     }
 }
 
+/// Iterator over the round keys of a [`Subkeys`], four words at a time.
 pub struct SubkeyChunks<'a>(ChunksExact<'a, Subkey>);
 
 impl<'a> Iterator for SubkeyChunks<'a> {
@@ -111,6 +285,7 @@ impl<'a> Iterator for SubkeyChunks<'a> {
     }
 }
 
+/// Iterator over the round keys of a [`Subkeys`] from the last round back.
 pub struct SubkeyChunksRev<'a>(Rev<ChunksExact<'a, Subkey>>);
 
 impl<'a> Iterator for SubkeyChunksRev<'a> {
@@ -122,23 +297,56 @@ impl<'a> Iterator for SubkeyChunksRev<'a> {
     }
 }
 
-fn expand(subkey: Subkey, rcon: u32) -> Subkey {
+/// Returns the value that is XOR-ed with word `idx - Nk` to give word `idx` of the
+/// schedule, computed from the previous word `prev`.
+fn schedule_temp(prev: Subkey, idx: usize, nk: usize, sub_word: impl Fn(u32) -> u32) -> u32 {
+    if idx.is_multiple_of(nk) {
+        expand(prev, RCON[idx / nk - 1], sub_word).as_u32()
+    } else if nk > 6 && idx % nk == 4 {
+        // AES-256 applies an extra SubWord halfway through each key block
+        sub_word(prev.as_u32())
+    } else {
+        prev.as_u32()
+    }
+}
+
+fn expand(subkey: Subkey, rcon: u32, sub_word: impl Fn(u32) -> u32) -> Subkey {
     let rotated = subkey.rotate_left(8);
-    let substituted = rotated.as_u32().sbox_lookup();
+    let substituted = sub_word(rotated.as_u32());
     Subkey::from_u32(substituted ^ rcon)
 }
 
+/// Multiplies each byte of a word by `x` in GF(2^8).
+const fn xtime_word(word: u32) -> u32 {
+    ((word & 0x7F7F_7F7F) << 1) ^ (((word >> 7) & 0x0101_0101) * 0x1B)
+}
+
+/// Applies `InvMixColumns` to a round key column.
+///
+/// Works on whole words without table lookups, so the cached decryption
+/// keys keep the constant-time key expansion of the bitsliced backend.
+/// `InvMixColumns` is `MixColumns` after adding `4 * (c0 ^ c2)` to rows 0
+/// and 2 and `4 * (c1 ^ c3)` to rows 1 and 3.
+const fn inv_mix_word(word: u32) -> u32 {
+    let word = word ^ xtime_word(xtime_word(word ^ word.rotate_left(16)));
+    let rotated = word.rotate_left(8);
+    xtime_word(word ^ rotated) ^ rotated ^ word.rotate_left(16) ^ word.rotate_left(24)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     const TEST_KEY: u128 = 0x0F15_71C9_47D9_E859_1CB7_ADD6_AF7F_6798;
 
-    impl PartialEq<[[u8; 4]; 44]> for Subkeys {
-        fn eq(&self, other: &[[u8; 4]; 44]) -> bool {
-            self.iter()
-                .zip(other)
-                .all(|(a, &b)| a.as_u32() == u32::from_be_bytes(b))
+    impl<const N: usize> PartialEq<[[u8; 4]; N]> for Subkeys {
+        fn eq(&self, other: &[[u8; 4]; N]) -> bool {
+            self.len() == N
+                && self
+                    .iter()
+                    .zip(other)
+                    .all(|(a, &b)| a.as_u32() == u32::from_be_bytes(b))
         }
     }
 
@@ -203,12 +411,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inv_mix_word_matches_inv_mix_column() {
+        use crate::operations::{inv_mix_column, mix_column};
+
+        for byte in 0..=u8::MAX {
+            let column = [byte, byte.rotate_left(1) ^ 0x63, !byte, byte ^ 0xA5];
+            let word = u32::from_be_bytes(column);
+            assert_eq!(
+                inv_mix_word(word).to_be_bytes(),
+                inv_mix_column(column),
+                "{word:08x}"
+            );
+            assert_eq!(inv_mix_word(u32::from_be_bytes(mix_column(column))), word);
+        }
+    }
+
+    #[test]
+    fn decryption_keys_transform_middle_rounds() {
+        use crate::operations::inv_mix_column;
+
+        let subkeys = Subkeys::from_key(&Key::from(TEST_KEY));
+        let decryption_keys = subkeys.decryption_keys();
+        assert_eq!(decryption_keys.len(), subkeys.len());
+        assert_eq!(decryption_keys[..4], subkeys.as_slice()[..4]);
+        assert_eq!(decryption_keys[40..], subkeys.as_slice()[40..]);
+        for idx in 4..40 {
+            let expected = inv_mix_column(subkeys[idx].to_be_bytes());
+            assert_eq!(decryption_keys[idx].to_be_bytes(), expected, "word {idx}");
+        }
+    }
+
     #[test]
     fn subkey_expansion_cound() {
         let key = Key::from(0x2B7E_1516_28AE_D2A6_ABF7_1588_09CF_4F3C);
         let subkeys = Subkeys::from_key(&key);
         assert_eq!(
-            subkeys.0.len(),
+            subkeys.len(),
             44,
             "Expected 44 subkeys for AES-128 (11 rounds x 4)"
         );
@@ -255,4 +494,137 @@ mod tests {
             );
         }
     }
+
+    #[rstest]
+    #[case(0x2B7E_1516_28AE_D2A6_ABF7_1588_09CF_4F3C_u128.to_be_bytes().to_vec(), 44, 0xB663_0CA6)]
+    #[case(
+        vec![
+            0x8E, 0x73, 0xB0, 0xF7, 0xDA, 0x0E, 0x64, 0x52, 0xC8, 0x10, 0xF3, 0x2B, 0x80, 0x90,
+            0x79, 0xE5, 0x62, 0xF8, 0xEA, 0xD2, 0x52, 0x2C, 0x6B, 0x7B,
+        ],
+        52,
+        0x0100_2202
+    )]
+    #[case(
+        vec![
+            0x60, 0x3D, 0xEB, 0x10, 0x15, 0xCA, 0x71, 0xBE, 0x2B, 0x73, 0xAE, 0xF0, 0x85, 0x7D,
+            0x77, 0x81, 0x1F, 0x35, 0x2C, 0x07, 0x3B, 0x61, 0x08, 0xD7, 0x2D, 0x98, 0x10, 0xA3,
+            0x09, 0x14, 0xDF, 0xF4,
+        ],
+        60,
+        0x706C_631E
+    )]
+    fn expansion_per_key_size(
+        #[case] key: Vec<u8>,
+        #[case] expected_len: usize,
+        #[case] expected_last: u32,
+    ) {
+        // FIPS-197 Appendix A key expansion examples
+        let key = Key::from_slice(&key).expect("valid key");
+        let subkeys = Subkeys::from_key(&key);
+        assert_eq!(subkeys.len(), expected_len);
+        assert_eq!(subkeys.rounds(), key.size().rounds());
+        assert_eq!(subkeys.key_size(), key.size());
+        assert_eq!(subkeys[expected_len - 1].as_u32(), expected_last);
+    }
+
+    const APPENDIX_A_KEYS: [&[u8]; 3] = [
+        &[
+            0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF,
+            0x4F, 0x3C,
+        ],
+        &[
+            0x8E, 0x73, 0xB0, 0xF7, 0xDA, 0x0E, 0x64, 0x52, 0xC8, 0x10, 0xF3, 0x2B, 0x80, 0x90,
+            0x79, 0xE5, 0x62, 0xF8, 0xEA, 0xD2, 0x52, 0x2C, 0x6B, 0x7B,
+        ],
+        &[
+            0x60, 0x3D, 0xEB, 0x10, 0x15, 0xCA, 0x71, 0xBE, 0x2B, 0x73, 0xAE, 0xF0, 0x85, 0x7D,
+            0x77, 0x81, 0x1F, 0x35, 0x2C, 0x07, 0x3B, 0x61, 0x08, 0xD7, 0x2D, 0x98, 0x10, 0xA3,
+            0x09, 0x14, 0xDF, 0xF4,
+        ],
+    ];
+
+    // FIPS-197 Appendix C keys
+    const APPENDIX_C_KEY: [u8; 32] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D,
+        0x1E, 0x1F,
+    ];
+
+    #[test]
+    fn round_key_bytes() {
+        let subkeys = Subkeys::from_key(&Key::from_slice(APPENDIX_A_KEYS[0]).expect("valid key"));
+        assert_eq!(
+            subkeys.round_key(0).map(u128::from_be_bytes),
+            Some(0x2B7E_1516_28AE_D2A6_ABF7_1588_09CF_4F3C)
+        );
+        assert_eq!(
+            subkeys.round_key(10).map(u128::from_be_bytes),
+            Some(0xD014_F9A8_C9EE_2589_E13F_0CC8_B663_0CA6)
+        );
+        assert_eq!(subkeys.round_key(11), None);
+    }
+
+    #[rstest]
+    #[case(APPENDIX_A_KEYS[0])]
+    #[case(APPENDIX_A_KEYS[1])]
+    #[case(APPENDIX_A_KEYS[2])]
+    #[case(&APPENDIX_C_KEY[..16])]
+    #[case(&APPENDIX_C_KEY[..24])]
+    #[case(&APPENDIX_C_KEY)]
+    #[case(&TEST_KEY.to_be_bytes())]
+    fn recover_key_roundtrips(#[case] key: &[u8]) {
+        let key = Key::from_slice(key).expect("valid key");
+        let size = key.size();
+        let nk = size.words();
+        let subkeys = Subkeys::from_key(&key);
+        let words = subkeys.iter().map(Subkey::as_u32).collect::<Vec<_>>();
+
+        for start in 0..=words.len() - nk {
+            let recovered =
+                Subkeys::recover_key(size, start, &words[start..start + nk]).expect("in range");
+            assert_eq!(recovered.as_bytes(), key.as_bytes(), "start word {start}");
+            assert_eq!(Subkeys::from_key(&recovered).as_slice(), subkeys.as_slice());
+        }
+    }
+
+    #[rstest]
+    #[case(APPENDIX_A_KEYS[0])]
+    #[case(&APPENDIX_C_KEY[..16])]
+    #[case(&TEST_KEY.to_be_bytes())]
+    fn recover_key_from_any_round(#[case] key: &[u8]) {
+        let key = Key::from_slice(key).expect("valid key");
+        let subkeys = Subkeys::from_key(&key);
+
+        for round in 0..=subkeys.rounds() {
+            let round_key = subkeys.round_key(round).expect("round in schedule");
+            let recovered = Subkeys::recover_key_from_round(round, round_key).expect("in range");
+            assert_eq!(recovered.as_bytes(), key.as_bytes(), "round {round}");
+        }
+    }
+
+    #[test]
+    fn recover_key_from_extended_schedule() {
+        let key = Key::from_slice(APPENDIX_A_KEYS[0]).expect("valid key");
+        let subkeys = Subkeys::from_key_with_rounds(&key, MAX_ROUNDS, u32::sbox_lookup);
+        let last = subkeys.round_key(MAX_ROUNDS).expect("round in schedule");
+        let recovered = Subkeys::recover_key_from_round(MAX_ROUNDS, last).expect("in range");
+        assert_eq!(recovered.as_bytes(), key.as_bytes());
+    }
+
+    #[test]
+    fn recover_key_rejects_bad_input() {
+        assert!(matches!(
+            Subkeys::recover_key(KeySize::Aes192, 0, &[0; 4]),
+            Err(CipherError::InvalidKeySize { .. })
+        ));
+        assert!(matches!(
+            Subkeys::recover_key(KeySize::Aes256, MAX_SUBKEY_COUNT - 7, &[0; 8]),
+            Err(CipherError::InvalidRounds { .. })
+        ));
+        assert!(matches!(
+            Subkeys::recover_key_from_round(MAX_ROUNDS + 1, [0; 16]),
+            Err(CipherError::InvalidRounds { .. })
+        ));
+    }
 }
@@ -0,0 +1,354 @@
+//! AES Key Wrap (RFC 3394, NIST SP 800-38F KW) and Key Wrap with Padding
+//! (RFC 5649, KWP).
+//!
+//! Key wrapping encrypts key material under a key-encryption key (KEK). The
+//! wrapped output is 8 bytes longer than the input and carries an integrity
+//! check value, which is verified before any key material is released.
+
+use crate::{Aes, Block128, KeySize, key::Key};
+use cipher_core::{CipherError, CipherResult, ct_eq};
+use std::fmt::Debug;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Size in bytes of a semiblock, the unit key wrap works in.
+const SEMIBLOCK_SIZE: usize = 8;
+
+/// Default initial value for KW (RFC 3394 section 2.2.3.1).
+const KW_IV: [u8; SEMIBLOCK_SIZE] = [0xA6; SEMIBLOCK_SIZE];
+
+/// Constant prefix of the alternative initial value for KWP (RFC 5649 section 3).
+const KWP_IV_PREFIX: [u8; 4] = [0xA6, 0x59, 0x59, 0xA6];
+
+/// AES key wrapping with a key-encryption key.
+///
+/// [`wrap`](Self::wrap) and [`unwrap`](Self::unwrap) implement KW, which takes
+/// key data that is a multiple of 8 bytes and at least 16 bytes long.
+/// [`wrap_padded`](Self::wrap_padded) and [`unwrap_padded`](Self::unwrap_padded)
+/// implement KWP, which accepts key data of any non-zero length.
+///
+/// # Example
+///
+/// ```
+/// use aes::AesKw;
+///
+/// let kw = AesKw::new(0x00010203_04050607_08090a0b_0c0d0e0f_u128);
+/// let key = 0x00112233_44556677_8899aabb_ccddeeff_u128.to_be_bytes();
+///
+/// let wrapped = kw.wrap(&key).unwrap();
+/// assert_eq!(wrapped.len(), 24);
+/// assert_eq!(kw.unwrap(&wrapped).unwrap(), key);
+/// ```
+pub struct AesKw {
+    aes: Aes,
+}
+
+impl AesKw {
+    /// Creates a key wrapper from the key-encryption key.
+    #[must_use]
+    pub fn new(kek: impl Into<Key>) -> Self {
+        Self {
+            aes: Aes::from_key(kek),
+        }
+    }
+
+    /// Returns the size of the key-encryption key.
+    #[inline]
+    #[must_use]
+    pub const fn key_size(&self) -> KeySize {
+        self.aes.key_size()
+    }
+
+    /// Wraps `key_data` with KW (RFC 3394).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidBlockSize` if `key_data` is shorter than
+    /// 16 bytes or not a multiple of 8 bytes.
+    pub fn wrap(&self, key_data: &[u8]) -> CipherResult<Vec<u8>> {
+        if key_data.len() < 2 * SEMIBLOCK_SIZE || !key_data.len().is_multiple_of(SEMIBLOCK_SIZE) {
+            return Err(CipherError::invalid_block_size(
+                key_data
+                    .len()
+                    .max(2 * SEMIBLOCK_SIZE)
+                    .next_multiple_of(SEMIBLOCK_SIZE),
+                key_data.len(),
+            ));
+        }
+        Ok(self.wrap_semiblocks(KW_IV, key_data))
+    }
+
+    /// Unwraps KW output and checks its integrity check value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidBlockSize` if `wrapped` is shorter than
+    /// 24 bytes or not a multiple of 8 bytes.
+    /// Returns `CipherError::AuthenticationFailed` if the integrity check fails.
+    pub fn unwrap(&self, wrapped: &[u8]) -> CipherResult<Vec<u8>> {
+        if wrapped.len() < 3 * SEMIBLOCK_SIZE || !wrapped.len().is_multiple_of(SEMIBLOCK_SIZE) {
+            return Err(CipherError::invalid_block_size(
+                wrapped
+                    .len()
+                    .max(3 * SEMIBLOCK_SIZE)
+                    .next_multiple_of(SEMIBLOCK_SIZE),
+                wrapped.len(),
+            ));
+        }
+
+        let (iv, mut key_data) = self.unwrap_semiblocks(wrapped);
+        if !ct_eq(&iv, &KW_IV) {
+            key_data.zeroize();
+            return Err(CipherError::AuthenticationFailed);
+        }
+        Ok(key_data)
+    }
+
+    /// Wraps `key_data` with KWP (RFC 5649).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidBlockSize` if `key_data` is empty or
+    /// longer than `u32::MAX` bytes.
+    pub fn wrap_padded(&self, key_data: &[u8]) -> CipherResult<Vec<u8>> {
+        let Ok(length) = u32::try_from(key_data.len()) else {
+            return Err(CipherError::invalid_block_size(
+                u32::MAX as usize,
+                key_data.len(),
+            ));
+        };
+        if length == 0 {
+            return Err(CipherError::invalid_block_size(1, 0));
+        }
+
+        let mut iv = [0; SEMIBLOCK_SIZE];
+        iv[..4].copy_from_slice(&KWP_IV_PREFIX);
+        iv[4..].copy_from_slice(&length.to_be_bytes());
+
+        let mut padded = Zeroizing::new(key_data.to_vec());
+        padded.resize(key_data.len().next_multiple_of(SEMIBLOCK_SIZE), 0);
+
+        if padded.len() == SEMIBLOCK_SIZE {
+            // A single semiblock is encrypted directly as `AIV || P`
+            let mut block = [0; 2 * SEMIBLOCK_SIZE];
+            block[..SEMIBLOCK_SIZE].copy_from_slice(&iv);
+            block[SEMIBLOCK_SIZE..].copy_from_slice(&padded);
+            let wrapped = self.aes.encrypt_block(Block128::from_be_bytes(block));
+            block.zeroize();
+            return Ok(wrapped.to_be_bytes().to_vec());
+        }
+        Ok(self.wrap_semiblocks(iv, &padded))
+    }
+
+    /// Unwraps KWP output and checks its integrity check value, length and
+    /// padding.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidBlockSize` if `wrapped` is shorter than
+    /// 16 bytes or not a multiple of 8 bytes.
+    /// Returns `CipherError::AuthenticationFailed` if any check fails.
+    pub fn unwrap_padded(&self, wrapped: &[u8]) -> CipherResult<Vec<u8>> {
+        if wrapped.len() < 2 * SEMIBLOCK_SIZE || !wrapped.len().is_multiple_of(SEMIBLOCK_SIZE) {
+            return Err(CipherError::invalid_block_size(
+                wrapped
+                    .len()
+                    .max(2 * SEMIBLOCK_SIZE)
+                    .next_multiple_of(SEMIBLOCK_SIZE),
+                wrapped.len(),
+            ));
+        }
+
+        // A single block was encrypted directly as `AIV || P`
+        let (iv, mut padded) = <[u8; 2 * SEMIBLOCK_SIZE]>::try_from(wrapped).map_or_else(
+            |_| self.unwrap_semiblocks(wrapped),
+            |block| {
+                let mut block = self
+                    .aes
+                    .decrypt_block(Block128::from_be_bytes(block))
+                    .to_be_bytes();
+                let result = split_semiblock(&block);
+                block.zeroize();
+                result
+            },
+        );
+
+        let length = u32::from_be_bytes([iv[4], iv[5], iv[6], iv[7]]) as usize;
+        let valid_length = length <= padded.len() && length + SEMIBLOCK_SIZE > padded.len();
+        let valid_padding = valid_length && padded[length..].iter().all(|&byte| byte == 0);
+        if !ct_eq(&iv[..4], &KWP_IV_PREFIX) || !valid_padding {
+            padded.zeroize();
+            return Err(CipherError::AuthenticationFailed);
+        }
+
+        padded.truncate(length);
+        Ok(padded)
+    }
+
+    /// Wrapping process `W` over `n >= 2` semiblocks with initial value `iv`.
+    fn wrap_semiblocks(&self, iv: [u8; SEMIBLOCK_SIZE], key_data: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(SEMIBLOCK_SIZE + key_data.len());
+        output.extend_from_slice(&iv);
+        output.extend_from_slice(key_data);
+
+        let n = key_data.len() / SEMIBLOCK_SIZE;
+        let mut a = iv;
+        for step in 0..6 {
+            for i in 1..=n {
+                let r = &mut output[i * SEMIBLOCK_SIZE..(i + 1) * SEMIBLOCK_SIZE];
+                let b = self.aes.encrypt_block(concat(a, r)).to_be_bytes();
+
+                a.copy_from_slice(&b[..SEMIBLOCK_SIZE]);
+                xor_counter(&mut a, n * step + i);
+                r.copy_from_slice(&b[SEMIBLOCK_SIZE..]);
+            }
+        }
+
+        output[..SEMIBLOCK_SIZE].copy_from_slice(&a);
+        output
+    }
+
+    /// Unwrapping process `W^-1`, returning the recovered initial value and
+    /// key data.
+    fn unwrap_semiblocks(&self, wrapped: &[u8]) -> ([u8; SEMIBLOCK_SIZE], Vec<u8>) {
+        let (mut a, mut key_data) = split_semiblock(wrapped);
+
+        let n = key_data.len() / SEMIBLOCK_SIZE;
+        for step in (0..6).rev() {
+            for i in (1..=n).rev() {
+                let r = &mut key_data[(i - 1) * SEMIBLOCK_SIZE..i * SEMIBLOCK_SIZE];
+                xor_counter(&mut a, n * step + i);
+                let b = self.aes.decrypt_block(concat(a, r)).to_be_bytes();
+
+                a.copy_from_slice(&b[..SEMIBLOCK_SIZE]);
+                r.copy_from_slice(&b[SEMIBLOCK_SIZE..]);
+            }
+        }
+
+        (a, key_data)
+    }
+}
+
+/// Omits the cipher, which holds key material.
+impl Debug for AesKw {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesKw")
+            .field("key_size", &self.key_size())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Splits off the leading semiblock of `data`.
+fn split_semiblock(data: &[u8]) -> ([u8; SEMIBLOCK_SIZE], Vec<u8>) {
+    let (head, tail) = data.split_at(SEMIBLOCK_SIZE);
+    let mut semiblock = [0; SEMIBLOCK_SIZE];
+    semiblock.copy_from_slice(head);
+    (semiblock, tail.to_vec())
+}
+
+/// Joins the integrity register and one semiblock into a cipher block.
+fn concat(a: [u8; SEMIBLOCK_SIZE], r: &[u8]) -> Block128 {
+    let mut block = [0; 2 * SEMIBLOCK_SIZE];
+    block[..SEMIBLOCK_SIZE].copy_from_slice(&a);
+    block[SEMIBLOCK_SIZE..].copy_from_slice(r);
+    Block128::from_be_bytes(block)
+}
+
+/// XORs the step counter `t`, as a 64-bit big-endian integer, into `a`.
+fn xor_counter(a: &mut [u8; SEMIBLOCK_SIZE], t: usize) {
+    for (byte, t_byte) in a.iter_mut().zip((t as u64).to_be_bytes()) {
+        *byte ^= t_byte;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_matches, assert_ok};
+    use rstest::rstest;
+
+    const KEK: u128 = 0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f;
+
+    #[rstest]
+    #[case(16)]
+    #[case(24)]
+    #[case(40)]
+    fn kw_roundtrip(#[case] len: usize) {
+        let kw = AesKw::new(KEK);
+        let key_data: Vec<u8> = (0..=u8::MAX).cycle().take(len).collect();
+
+        let wrapped = assert_ok!(kw.wrap(&key_data));
+        assert_eq!(wrapped.len(), len + SEMIBLOCK_SIZE);
+        assert_eq!(assert_ok!(kw.unwrap(&wrapped)), key_data);
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(7)]
+    #[case(8)]
+    #[case(9)]
+    #[case(16)]
+    #[case(33)]
+    fn kwp_roundtrip(#[case] len: usize) {
+        let kw = AesKw::new(KEK);
+        let key_data: Vec<u8> = (0..=u8::MAX).cycle().take(len).collect();
+
+        let wrapped = assert_ok!(kw.wrap_padded(&key_data));
+        assert_eq!(wrapped.len(), len.next_multiple_of(8) + SEMIBLOCK_SIZE);
+        assert_eq!(assert_ok!(kw.unwrap_padded(&wrapped)), key_data);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(8)]
+    #[case(20)]
+    fn kw_invalid_length_fails(#[case] len: usize) {
+        let kw = AesKw::new(KEK);
+        let err = assert_err!(kw.wrap(&vec![0; len]));
+        assert_matches!(err, CipherError::InvalidBlockSize { .. });
+    }
+
+    #[test]
+    fn kwp_empty_fails() {
+        assert_err!(AesKw::new(KEK).wrap_padded(&[]));
+    }
+
+    #[test]
+    fn kw_tampering_fails() {
+        let kw = AesKw::new(KEK);
+        let mut wrapped = assert_ok!(kw.wrap(&[0x5A; 16]));
+        wrapped[12] ^= 1;
+        let err = assert_err!(kw.unwrap(&wrapped));
+        assert_matches!(err, CipherError::AuthenticationFailed);
+    }
+
+    #[test]
+    fn kw_and_kwp_are_not_interchangeable() {
+        let kw = AesKw::new(KEK);
+        let wrapped = assert_ok!(kw.wrap(&[0x5A; 16]));
+        assert_err!(kw.unwrap_padded(&wrapped));
+
+        let wrapped = assert_ok!(kw.wrap_padded(&[0x5A; 16]));
+        assert_err!(kw.unwrap(&wrapped));
+    }
+
+    #[test]
+    fn kwp_rejects_wrong_length_indicator() {
+        // Wrap 16 bytes of data with a length indicator claiming 8 bytes:
+        // the trailing "padding" is not zero and must be rejected.
+        let kw = AesKw::new(KEK);
+        let mut iv = [0; SEMIBLOCK_SIZE];
+        iv[..4].copy_from_slice(&KWP_IV_PREFIX);
+        iv[4..].copy_from_slice(&8u32.to_be_bytes());
+        let forged = kw.wrap_semiblocks(iv, &[0x5A; 16]);
+
+        let err = assert_err!(kw.unwrap_padded(&forged));
+        assert_matches!(err, CipherError::AuthenticationFailed);
+    }
+
+    #[test]
+    fn unwrap_short_input_fails() {
+        let kw = AesKw::new(KEK);
+        assert_err!(kw.unwrap(&[0; 16]));
+        assert_err!(kw.unwrap_padded(&[0; 8]));
+    }
+}
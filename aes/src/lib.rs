@@ -1,6 +1,26 @@
 //! AES (Advanced Encryption Standard) implementation.
 //!
-//! Provides AES-128 block cipher with 128-bit keys and blocks.
+//! Provides the AES block cipher with 128-bit blocks and 128, 192 or 256-bit keys,
+//! the original [`Rijndael`] cipher with 128 to 256-bit blocks and keys,
+//! [`AesGcm`] and [`AesCcm`] authenticated encryption, [`AesSiv`] and
+//! [`AesGcmSiv`] nonce-misuse-resistant authenticated encryption,
+//! [`AesXts`] sector encryption, and [`AesKw`] key wrapping.
+//!
+//! [`Aes`] runs on a selectable [`Backend`]: the AES-NI instructions (the
+//! default when the CPU supports them), fast T-tables (the portable default),
+//! the readable FIPS-197 reference rounds, or constant-time bitsliced rounds.
+//! Enable the `bitsliced` feature to make the bitsliced backend the default.
+//!
+//! [`Aes::trace_encrypt`] records every intermediate state of an encryption
+//! as a [`Trace`], for checking hand computations against FIPS-197 Appendix B.
+//! Enable the `serde` feature to serialize traces.
+//!
+//! The [`gf256`] module provides the GF(2^8) field arithmetic that the S-box,
+//! round constants and `MixColumns` tables are generated from.
+//!
+//! The [`key`] module exposes the expanded round keys through
+//! [`Aes::subkeys`] and an inverse key schedule that recovers the cipher key
+//! from a round key.
 //!
 //! # Example
 //! ```
@@ -12,12 +32,44 @@
 //! ```
 
 mod aes;
+mod backend;
 mod block;
 mod cbc;
+mod ccm;
 mod constants;
+mod ctr;
+mod gcm;
+mod gcm_siv;
+pub mod gf256;
+mod ghash;
 mod iv;
-mod key;
+pub mod key;
+mod kw;
 mod operations;
+mod polyval;
+mod rijndael;
 mod sbox;
+mod siv;
+mod trace;
+mod xts;
 
-pub use {aes::Aes, block::Block32, block::Block128, cbc::AesCbc, iv::Iv};
+pub use {
+    aes::Aes,
+    backend::Backend,
+    block::Block32,
+    block::Block128,
+    cbc::AesCbc,
+    ccm::{AesCcm, CCM_NONCE_SIZES, CCM_TAG_SIZES},
+    ctr::{AesCtr, CounterLayout},
+    gcm::{AesGcm, GCM_NONCE_SIZE, GCM_TAG_SIZE},
+    gcm_siv::{AesGcmSiv, GCM_SIV_NONCE_SIZE, GCM_SIV_TAG_SIZE},
+    ghash::{Ghash, gf128_mul},
+    iv::Iv,
+    key::{Key, KeySize},
+    kw::AesKw,
+    polyval::Polyval,
+    rijndael::Rijndael,
+    siv::{AesSiv, SIV_MAX_COMPONENTS, SIV_TAG_SIZE},
+    trace::{RoundTrace, State, Trace},
+    xts::AesXts,
+};
@@ -4,7 +4,7 @@ mod row_shift;
 mod sbox_lookup;
 
 pub use {
-    column_mix::{inv_mix_columns, mix_columns},
+    column_mix::{inv_mix_column, inv_mix_columns, mix_column, mix_columns},
     round_key::add_round_key,
     row_shift::{inv_shift_rows, shift_rows},
     sbox_lookup::{inv_sub_bytes, sub_bytes},
@@ -0,0 +1,105 @@
+//! POLYVAL universal hash over GF(2^128), as used by AES-GCM-SIV (RFC 8452).
+//!
+//! POLYVAL is GHASH with the byte order reversed: blocks are little-endian
+//! polynomials reduced modulo x^128 + x^127 + x^126 + x^121 + 1, and each
+//! step multiplies by `H * x^-128`. It is computed here through [`gf128_mul`]
+//! using the mapping from RFC 8452 Appendix A.
+
+use crate::gf128_mul;
+use zeroize::ZeroizeOnDrop;
+
+const BLOCK_SIZE: usize = 16;
+
+/// Reduction polynomial x^7 + x^2 + x + 1 in GCM's bit order.
+const R: u128 = 0xE1 << 120;
+
+/// Incremental POLYVAL computation keyed by `H`.
+///
+/// `S_i = (S_{i-1} ^ X_i) * H * x^-128` for every 128-bit input block `X_i`.
+#[derive(ZeroizeOnDrop)]
+pub struct Polyval {
+    /// `mulX_GHASH(ByteReverse(H))`, the equivalent GHASH key.
+    h: u128,
+    /// The running value, byte-reversed into GHASH's order.
+    state: u128,
+}
+
+impl Polyval {
+    /// Creates a POLYVAL instance with the 16-byte key `h`.
+    #[inline]
+    #[must_use]
+    pub const fn new(h: [u8; BLOCK_SIZE]) -> Self {
+        Self {
+            h: mul_x_ghash(u128::from_le_bytes(h)),
+            state: 0,
+        }
+    }
+
+    /// Absorbs a single 128-bit block.
+    pub const fn update_block(&mut self, block: [u8; BLOCK_SIZE]) {
+        self.state = gf128_mul(self.state ^ u128::from_le_bytes(block), self.h);
+    }
+
+    /// Absorbs `data`, zero-padding the final partial block.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        for chunk in data.chunks(BLOCK_SIZE) {
+            let mut block = [0; BLOCK_SIZE];
+            block[..chunk.len()].copy_from_slice(chunk);
+            self.update_block(block);
+        }
+    }
+
+    /// Returns the current hash value.
+    #[inline]
+    #[must_use]
+    pub const fn finalize(&self) -> [u8; BLOCK_SIZE] {
+        self.state.to_le_bytes()
+    }
+}
+
+/// Multiplies by x in GCM's bit order.
+const fn mul_x_ghash(value: u128) -> u128 {
+    (value >> 1) ^ (R & 0u128.wrapping_sub(value & 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn block(value: u128) -> [u8; BLOCK_SIZE] {
+        value.to_be_bytes()
+    }
+
+    // RFC 8452 Appendix A
+    #[test]
+    fn rfc8452_example() {
+        let mut polyval = Polyval::new(block(0x2562_9347_5892_4276_1d31_f826_ba4b_757b));
+        polyval.update_block(block(0x4f4f_9566_8c83_dfb6_4017_62bb_2d01_a262));
+        polyval.update_block(block(0xd1a2_4ddd_2721_d006_bbe4_5f20_d3c9_f362));
+        assert_eq!(
+            polyval.finalize(),
+            block(0xf7a3_b47b_8461_19fa_e5b7_866c_f5e5_b77e)
+        );
+    }
+
+    #[test]
+    fn empty_input_is_zero() {
+        let polyval = Polyval::new(block(0x2562_9347_5892_4276_1d31_f826_ba4b_757b));
+        assert_eq!(polyval.finalize(), [0; BLOCK_SIZE]);
+    }
+
+    #[test]
+    fn padding_matches_explicit_block() {
+        let h = block(0x2562_9347_5892_4276_1d31_f826_ba4b_757b);
+        let mut padded = Polyval::new(h);
+        padded.update_padded(&[0xAB; 20]);
+
+        let mut explicit = Polyval::new(h);
+        explicit.update_block([0xAB; BLOCK_SIZE]);
+        let mut last = [0; BLOCK_SIZE];
+        last[..4].fill(0xAB);
+        explicit.update_block(last);
+
+        assert_eq!(padded.finalize(), explicit.finalize());
+    }
+}
@@ -0,0 +1,368 @@
+use crate::{
+    gf256::xtime,
+    key::Subkey,
+    operations::{inv_mix_column, mix_column},
+    sbox::SboxLookup,
+};
+use cipher_core::{BlockCipher, CipherAction, CipherError, CipherResult, Output};
+use std::fmt::Debug;
+use zeroize::ZeroizeOnDrop;
+
+/// Smallest block/key length in 32-bit words.
+const MIN_WORDS: usize = 4;
+/// Largest block/key length in 32-bit words.
+const MAX_WORDS: usize = 8;
+
+/// The original Rijndael block cipher.
+///
+/// Supports every block and key length from the Rijndael proposal:
+/// 128, 160, 192, 224 or 256 bits each (`Nb` and `Nk` from 4 to 8 words).
+/// AES is the subset with `Nb = 4` and `Nk` of 4, 6 or 8.
+///
+/// The state is a 4 x `Nb` byte matrix, filled column by column, so the
+/// `ShiftRows` offsets depend on the block length.
+///
+/// # Example
+/// ```
+/// use aes::Rijndael;
+/// use cipher_core::BlockCipher;
+///
+/// let cipher = Rijndael::new(&[0u8; 20], 24).unwrap();
+/// let ciphertext = cipher.encrypt(&[0u8; 24]).unwrap();
+/// assert_eq!(ciphertext.len(), 24);
+/// ```
+#[derive(ZeroizeOnDrop)]
+pub struct Rijndael {
+    round_keys: Vec<Subkey>,
+    #[zeroize(skip)]
+    block_words: usize,
+    #[zeroize(skip)]
+    key_words: usize,
+}
+
+impl Rijndael {
+    /// Creates a cipher from a 16, 20, 24, 28 or 32-byte key and a block size
+    /// of 16, 20, 24, 28 or 32 bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` or `CipherError::InvalidBlockSize`
+    /// if either length is not supported.
+    pub fn new(key: &[u8], block_size: usize) -> CipherResult<Self> {
+        let key_words = words(key.len())
+            .ok_or_else(|| CipherError::invalid_key_size(closest(key.len()), key.len()))?;
+        let block_words = words(block_size)
+            .ok_or_else(|| CipherError::invalid_block_size(closest(block_size), block_size))?;
+
+        let round_keys = expand_key(key, block_words, key_words);
+
+        Ok(Self {
+            round_keys,
+            block_words,
+            key_words,
+        })
+    }
+
+    /// Returns the number of 32-bit words in a block (`Nb`).
+    #[inline]
+    #[must_use]
+    pub const fn block_words(&self) -> usize {
+        self.block_words
+    }
+
+    /// Returns the number of 32-bit words in the key (`Nk`).
+    #[inline]
+    #[must_use]
+    pub const fn key_words(&self) -> usize {
+        self.key_words
+    }
+
+    /// Returns the number of rounds: `max(Nb, Nk) + 6`.
+    #[inline]
+    #[must_use]
+    pub const fn rounds(&self) -> usize {
+        rounds(self.block_words, self.key_words)
+    }
+
+    fn encrypt_block(&self, state: &mut [u8]) {
+        let rounds = self.rounds();
+        self.add_round_key(state, 0);
+
+        for round in 1..rounds {
+            sub_bytes(state);
+            self.shift_rows(state);
+            mix_columns(state);
+            self.add_round_key(state, round);
+        }
+
+        // Final round: SubBytes, ShiftRows, AddRoundKey (no MixColumns)
+        sub_bytes(state);
+        self.shift_rows(state);
+        self.add_round_key(state, rounds);
+    }
+
+    fn decrypt_block(&self, state: &mut [u8]) {
+        let rounds = self.rounds();
+        self.add_round_key(state, rounds);
+
+        for round in (1..rounds).rev() {
+            self.inv_shift_rows(state);
+            inv_sub_bytes(state);
+            self.add_round_key(state, round);
+            inv_mix_columns(state);
+        }
+
+        self.inv_shift_rows(state);
+        inv_sub_bytes(state);
+        self.add_round_key(state, 0);
+    }
+
+    fn add_round_key(&self, state: &mut [u8], round: usize) {
+        let keys = &self.round_keys[round * self.block_words..(round + 1) * self.block_words];
+        for (column, key) in state.chunks_exact_mut(4).zip(keys) {
+            for (byte, key_byte) in column.iter_mut().zip(key.to_be_bytes()) {
+                *byte ^= key_byte;
+            }
+        }
+    }
+
+    /// Row `r` is rotated left by `C_r` columns.
+    fn shift_rows(&self, state: &mut [u8]) {
+        let nb = self.block_words;
+        let input = state.to_vec();
+        for (row, offset) in shift_offsets(nb).into_iter().enumerate() {
+            for col in 0..nb {
+                state[4 * col + row] = input[4 * ((col + offset) % nb) + row];
+            }
+        }
+    }
+
+    /// Inverse of [`Self::shift_rows`] - rotates rows right instead of left.
+    fn inv_shift_rows(&self, state: &mut [u8]) {
+        let nb = self.block_words;
+        let input = state.to_vec();
+        for (row, offset) in shift_offsets(nb).into_iter().enumerate() {
+            for col in 0..nb {
+                state[4 * ((col + offset) % nb) + row] = input[4 * col + row];
+            }
+        }
+    }
+}
+
+impl Debug for Rijndael {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Rijndael(block: {} bits, key: {} bits, [REDACTED])",
+            self.block_words * 32,
+            self.key_words * 32
+        )
+    }
+}
+
+impl BlockCipher for Rijndael {
+    fn block_size(&self) -> usize {
+        self.block_words * 4
+    }
+
+    fn transform_impl(&self, block: &[u8], action: CipherAction) -> CipherResult<Output> {
+        let mut state = block.to_vec();
+        match action {
+            CipherAction::Encrypt => self.encrypt_block(&mut state),
+            CipherAction::Decrypt => self.decrypt_block(&mut state),
+        }
+        Ok(state.into())
+    }
+}
+
+/// Returns the number of 32-bit words for a supported byte length.
+const fn words(len: usize) -> Option<usize> {
+    if len.is_multiple_of(4) && len / 4 >= MIN_WORDS && len / 4 <= MAX_WORDS {
+        Some(len / 4)
+    } else {
+        None
+    }
+}
+
+/// Returns the closest supported byte length, used in error messages.
+const fn closest(len: usize) -> usize {
+    let words = len.div_ceil(4);
+    if words < MIN_WORDS {
+        MIN_WORDS * 4
+    } else if words > MAX_WORDS {
+        MAX_WORDS * 4
+    } else {
+        words * 4
+    }
+}
+
+const fn rounds(block_words: usize, key_words: usize) -> usize {
+    if block_words > key_words {
+        block_words + 6
+    } else {
+        key_words + 6
+    }
+}
+
+/// `ShiftRows` offsets `C0..C3` for a block of `nb` words.
+///
+/// AES only uses the `Nb = 4` row. Rijndael increases the offsets of the
+/// lower rows for the two largest blocks.
+const fn shift_offsets(nb: usize) -> [usize; 4] {
+    match nb {
+        7 => [0, 1, 2, 4],
+        8 => [0, 1, 3, 4],
+        _ => [0, 1, 2, 3],
+    }
+}
+
+/// Expands the key into `Nb * (Nr + 1)` round key words.
+fn expand_key(key: &[u8], nb: usize, nk: usize) -> Vec<Subkey> {
+    let len = nb * (rounds(nb, nk) + 1);
+    let mut keys = Vec::with_capacity(len);
+
+    // Load initial key
+    keys.extend(
+        key.chunks_exact(4)
+            .map(|word| Subkey::from_u32(u32::from_be_bytes([word[0], word[1], word[2], word[3]]))),
+    );
+
+    // Large blocks with small keys need more round constants than AES' table
+    // holds, so they are generated on the fly.
+    let mut rcon = 0x01_u8;
+    for idx in nk..len {
+        let prev = keys[idx - 1];
+        let temp = if idx % nk == 0 {
+            let rotated = prev.rotate_left(8).as_u32().sbox_lookup();
+            let word = Subkey::from_u32(rotated ^ (u32::from(rcon) << 24));
+            rcon = xtime(rcon);
+            word
+        } else if nk > 6 && idx % nk == 4 {
+            Subkey::from_u32(prev.as_u32().sbox_lookup())
+        } else {
+            prev
+        };
+        keys.push(keys[idx - nk] ^ temp);
+    }
+
+    keys
+}
+
+fn sub_bytes(state: &mut [u8]) {
+    for byte in state {
+        *byte = byte.sbox_lookup();
+    }
+}
+
+fn inv_sub_bytes(state: &mut [u8]) {
+    for byte in state {
+        *byte = byte.inv_sbox_lookup();
+    }
+}
+
+fn mix_columns(state: &mut [u8]) {
+    for column in state.chunks_exact_mut(4) {
+        let mixed = mix_column([column[0], column[1], column[2], column[3]]);
+        column.copy_from_slice(&mixed);
+    }
+}
+
+fn inv_mix_columns(state: &mut [u8]) {
+    for column in state.chunks_exact_mut(4) {
+        let mixed = inv_mix_column([column[0], column[1], column[2], column[3]]);
+        column.copy_from_slice(&mixed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Aes, Key};
+    use claims::{assert_err, assert_ok};
+    use rstest::rstest;
+
+    const KEY: [u8; 32] = [
+        0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F,
+        0x3C, 0x76, 0x2E, 0x71, 0x60, 0xF3, 0x8B, 0x4D, 0xA5, 0x6A, 0x78, 0x4D, 0x90, 0x45, 0x19,
+        0x0C, 0xFE,
+    ];
+    const PLAINTEXT: [u8; 32] = [
+        0x32, 0x43, 0xF6, 0xA8, 0x88, 0x5A, 0x30, 0x8D, 0x31, 0x31, 0x98, 0xA2, 0xE0, 0x37, 0x07,
+        0x34, 0x4A, 0x40, 0x93, 0x82, 0x22, 0x99, 0xF3, 0x1D, 0x00, 0x82, 0xEF, 0xA9, 0x8E, 0xC4,
+        0xE6, 0xC8,
+    ];
+
+    #[rstest]
+    #[case(16)]
+    #[case(24)]
+    #[case(32)]
+    fn matches_aes_for_128_bit_blocks(#[case] key_len: usize) {
+        let rijndael = assert_ok!(Rijndael::new(&KEY[..key_len], 16));
+        let aes = Aes::new(assert_ok!(Key::from_slice(&KEY[..key_len])));
+
+        let expected = assert_ok!(aes.encrypt(&PLAINTEXT[..16]));
+        let ciphertext = assert_ok!(rijndael.encrypt(&PLAINTEXT[..16]));
+        assert_eq!(ciphertext, expected);
+        assert_eq!(rijndael.rounds(), aes.rounds());
+    }
+
+    #[rstest]
+    fn roundtrip_all_sizes(
+        #[values(16, 20, 24, 28, 32)] block_size: usize,
+        #[values(16, 20, 24, 28, 32)] key_len: usize,
+    ) {
+        let cipher = assert_ok!(Rijndael::new(&KEY[..key_len], block_size));
+        assert_eq!(cipher.block_size(), block_size);
+        assert_eq!(cipher.rounds(), block_size.max(key_len) / 4 + 6);
+
+        let plaintext = &PLAINTEXT[..block_size];
+        let ciphertext = assert_ok!(cipher.encrypt(plaintext));
+        assert_ne!(ciphertext.as_slice(), plaintext);
+
+        let decrypted = assert_ok!(cipher.decrypt(&ciphertext));
+        assert_eq!(decrypted.as_slice(), plaintext);
+    }
+
+    #[rstest]
+    #[case(4, [0, 1, 2, 3])]
+    #[case(5, [0, 1, 2, 3])]
+    #[case(6, [0, 1, 2, 3])]
+    #[case(7, [0, 1, 2, 4])]
+    #[case(8, [0, 1, 3, 4])]
+    fn shift_row_offsets(#[case] nb: usize, #[case] expected: [usize; 4]) {
+        assert_eq!(shift_offsets(nb), expected);
+    }
+
+    #[test]
+    fn shift_rows_is_inverse() {
+        let cipher = assert_ok!(Rijndael::new(&KEY[..16], 32));
+        let mut state = PLAINTEXT;
+        cipher.shift_rows(&mut state);
+        assert_ne!(state, PLAINTEXT);
+        cipher.inv_shift_rows(&mut state);
+        assert_eq!(state, PLAINTEXT);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(12)]
+    #[case(18)]
+    #[case(36)]
+    fn invalid_key_size_fails(#[case] key_len: usize) {
+        assert_err!(Rijndael::new(&vec![0; key_len], 16));
+    }
+
+    #[rstest]
+    #[case(8)]
+    #[case(17)]
+    #[case(64)]
+    fn invalid_block_size_fails(#[case] block_size: usize) {
+        assert_err!(Rijndael::new(&KEY[..16], block_size));
+    }
+
+    #[test]
+    fn wrong_input_length_fails() {
+        let cipher = assert_ok!(Rijndael::new(&KEY[..16], 20));
+        assert_err!(cipher.encrypt(&PLAINTEXT[..16]));
+    }
+}
@@ -0,0 +1,385 @@
+//! AES-SIV deterministic authenticated encryption (RFC 5297).
+//!
+//! SIV computes a synthetic IV over the associated data and plaintext with
+//! S2V, a CMAC-based PRF on a vector of strings. That IV is both the
+//! authentication tag and the initial counter for CTR encryption, so the
+//! same inputs always give the same output and a repeated nonce only reveals
+//! that two messages were identical.
+
+use crate::{Aes, Block128, KeySize, key::Key};
+use cipher_core::{Aead, CipherError, CipherResult, Cmac, Mac, ct_eq, dbl};
+use std::fmt::Debug;
+use zeroize::{Zeroize, Zeroizing};
+
+const BLOCK_SIZE: usize = 16;
+
+/// Length in bytes of the synthetic IV, which is also the tag.
+pub const SIV_TAG_SIZE: usize = 16;
+
+/// Maximum number of associated data components; S2V accepts at most 127
+/// strings and the plaintext is always the last one.
+pub const SIV_MAX_COMPONENTS: usize = 126;
+
+/// Clears bits 63 and 31 of the synthetic IV before it is used as a counter,
+/// so implementations with 32 or 64-bit counters interoperate.
+const CTR_MASK: u128 = 0xffff_ffff_ffff_ffff_7fff_ffff_7fff_ffff;
+
+/// AES-SIV with a CMAC key (`K1`) and a CTR key (`K2`) of the same size.
+///
+/// Encryption takes any number of associated data components, up to
+/// [`SIV_MAX_COMPONENTS`]. For nonce-based use the nonce is passed as the last
+/// component; leaving it out gives deterministic encryption.
+///
+/// # Example
+///
+/// ```
+/// use aes::AesSiv;
+///
+/// let siv = AesSiv::new(
+///     0xfffefdfc_fbfaf9f8_f7f6f5f4_f3f2f1f0_u128,
+///     0xf0f1f2f3_f4f5f6f7_f8f9fafb_fcfdfeff_u128,
+/// )
+/// .unwrap();
+///
+/// let sealed = siv.encrypt(&[b"header", b"nonce"], b"Hello, World!").unwrap();
+/// assert_eq!(sealed.len(), 16 + 13);
+///
+/// let opened = siv.decrypt(&[b"header", b"nonce"], &sealed).unwrap();
+/// assert_eq!(opened, b"Hello, World!");
+/// ```
+pub struct AesSiv {
+    /// Cipher keyed with `K1`, for the CMAC calls of S2V.
+    mac: Aes,
+    ctr: Aes,
+}
+
+impl AesSiv {
+    /// Creates AES-SIV from the CMAC key (`K1`) and CTR key (`K2`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` if the keys differ in size.
+    pub fn new(mac_key: impl Into<Key>, ctr_key: impl Into<Key>) -> CipherResult<Self> {
+        let (mac_key, ctr_key) = (mac_key.into(), ctr_key.into());
+        if ctr_key.size() != mac_key.size() {
+            return Err(CipherError::invalid_key_size(
+                mac_key.size().bytes(),
+                ctr_key.size().bytes(),
+            ));
+        }
+
+        Ok(Self {
+            mac: Aes::from_key(mac_key),
+            ctr: Aes::from_key(ctr_key),
+        })
+    }
+
+    /// Creates AES-SIV from a combined 256, 384 or 512-bit key `K1 || K2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` if `key` is not 32, 48 or 64 bytes.
+    pub fn from_slice(key: &[u8]) -> CipherResult<Self> {
+        if !matches!(key.len(), 32 | 48 | 64) {
+            return Err(CipherError::invalid_key_size(64, key.len()));
+        }
+
+        let (mac_key, ctr_key) = key.split_at(key.len() / 2);
+        Self::new(Key::from_slice(mac_key)?, Key::from_slice(ctr_key)?)
+    }
+
+    /// Returns the size of each of the two keys.
+    #[inline]
+    #[must_use]
+    pub const fn key_size(&self) -> KeySize {
+        self.ctr.key_size()
+    }
+
+    /// Encrypts `plaintext`, returning `[SIV][ciphertext...]` as in RFC 5297.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::TooManyComponents` if there are more than
+    /// [`SIV_MAX_COMPONENTS`] associated data components.
+    pub fn encrypt(&self, associated_data: &[&[u8]], plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        let (ciphertext, siv) = self.encrypt_detached(associated_data, plaintext)?;
+        let mut output = Vec::with_capacity(SIV_TAG_SIZE + ciphertext.len());
+        output.extend_from_slice(&siv);
+        output.extend_from_slice(&ciphertext);
+        Ok(output)
+    }
+
+    /// Decrypts `[SIV][ciphertext...]` produced by [`encrypt`](Self::encrypt).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::TooManyComponents` if there are more than
+    /// [`SIV_MAX_COMPONENTS`] associated data components.
+    /// Returns `CipherError::AuthenticationFailed` if the input is shorter
+    /// than the SIV or the SIV does not match.
+    pub fn decrypt(&self, associated_data: &[&[u8]], data: &[u8]) -> CipherResult<Vec<u8>> {
+        if data.len() < SIV_TAG_SIZE {
+            return Err(CipherError::AuthenticationFailed);
+        }
+        let (siv, ciphertext) = data.split_at(SIV_TAG_SIZE);
+        self.decrypt_detached(associated_data, ciphertext, siv)
+    }
+
+    /// Encrypts `plaintext` and returns the ciphertext and the SIV separately.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::TooManyComponents` if there are more than
+    /// [`SIV_MAX_COMPONENTS`] associated data components.
+    pub fn encrypt_detached(
+        &self,
+        associated_data: &[&[u8]],
+        plaintext: &[u8],
+    ) -> CipherResult<(Vec<u8>, [u8; SIV_TAG_SIZE])> {
+        check_components(associated_data)?;
+
+        let siv = self.s2v(associated_data, plaintext)?;
+        let mut ciphertext = plaintext.to_vec();
+        self.apply_ctr(siv, &mut ciphertext);
+        Ok((ciphertext, siv.to_be_bytes()))
+    }
+
+    /// Decrypts `ciphertext` and verifies `siv`.
+    ///
+    /// No plaintext is returned unless the SIV matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::TooManyComponents` if there are more than
+    /// [`SIV_MAX_COMPONENTS`] associated data components.
+    /// Returns `CipherError::AuthenticationFailed` if the SIV does not match.
+    pub fn decrypt_detached(
+        &self,
+        associated_data: &[&[u8]],
+        ciphertext: &[u8],
+        siv: &[u8],
+    ) -> CipherResult<Vec<u8>> {
+        check_components(associated_data)?;
+        let Ok(siv) = <[u8; SIV_TAG_SIZE]>::try_from(siv) else {
+            return Err(CipherError::AuthenticationFailed);
+        };
+
+        let mut plaintext = ciphertext.to_vec();
+        self.apply_ctr(Block128::from_be_bytes(siv), &mut plaintext);
+
+        let expected = self.s2v(associated_data, &plaintext)?.to_be_bytes();
+        if !ct_eq(&expected, &siv) {
+            plaintext.zeroize();
+            return Err(CipherError::AuthenticationFailed);
+        }
+        Ok(plaintext)
+    }
+
+    /// S2V over the associated data components followed by the plaintext.
+    fn s2v(&self, associated_data: &[&[u8]], plaintext: &[u8]) -> CipherResult<Block128> {
+        let mut mac = Cmac::new(&self.mac)?;
+        mac.update(&[0; BLOCK_SIZE])?;
+        let mut d = Zeroizing::new(mac.finalize()?);
+        for component in associated_data {
+            mac.reset();
+            mac.update(component)?;
+            let mut next = dbl(&d)?;
+            xor_into(&mut next, &mac.finalize()?);
+            d = next;
+        }
+
+        mac.reset();
+        if plaintext.len() >= BLOCK_SIZE {
+            // xorend: XOR D into the last 16 bytes of the plaintext
+            let (head, tail) = plaintext.split_at(plaintext.len() - BLOCK_SIZE);
+            let mut last = Zeroizing::new(tail.to_vec());
+            xor_into(&mut last, &d);
+            mac.update(head)?;
+            mac.update(&last)?;
+        } else {
+            let mut padded = Zeroizing::new([0; BLOCK_SIZE]);
+            padded[..plaintext.len()].copy_from_slice(plaintext);
+            padded[plaintext.len()] = 0x80;
+            let mut t = dbl(&d)?;
+            xor_into(&mut t, &*padded);
+            mac.update(&t)?;
+        }
+
+        let siv = mac.finalize()?;
+        Ok(Block128::from_be_bytes(
+            siv.try_into().expect("CMAC tag is one block"),
+        ))
+    }
+
+    /// Applies the CTR keystream starting from the masked SIV.
+    fn apply_ctr(&self, siv: Block128, data: &mut [u8]) {
+        let mut counter = siv.as_u128() & CTR_MASK;
+        for chunk in data.chunks_mut(BLOCK_SIZE) {
+            let keystream = self.ctr.encrypt_block(Block128::new(counter)).to_be_bytes();
+            for (byte, key_byte) in chunk.iter_mut().zip(keystream) {
+                *byte ^= key_byte;
+            }
+            counter = counter.wrapping_add(1);
+        }
+    }
+}
+
+/// Omits the keys.
+impl Debug for AesSiv {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesSiv")
+            .field("key_size", &self.key_size())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Aead for AesSiv {
+    fn tag_size(&self) -> usize {
+        SIV_TAG_SIZE
+    }
+
+    /// Seals with `associated_data` as the first S2V component and `nonce`,
+    /// if not empty, as the second; an empty nonce gives deterministic SIV.
+    /// The output is `[SIV][ciphertext...]`, as for [`AesSiv::encrypt`].
+    fn seal(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> CipherResult<Vec<u8>> {
+        self.encrypt(&components(nonce, associated_data), plaintext)
+    }
+
+    fn open(&self, nonce: &[u8], associated_data: &[u8], sealed: &[u8]) -> CipherResult<Vec<u8>> {
+        self.decrypt(&components(nonce, associated_data), sealed)
+    }
+}
+
+/// S2V components for the [`Aead`] interface.
+fn components<'a>(nonce: &'a [u8], associated_data: &'a [u8]) -> Vec<&'a [u8]> {
+    if nonce.is_empty() {
+        vec![associated_data]
+    } else {
+        vec![associated_data, nonce]
+    }
+}
+
+const fn check_components(associated_data: &[&[u8]]) -> CipherResult<()> {
+    if associated_data.len() > SIV_MAX_COMPONENTS {
+        return Err(CipherError::too_many_components(
+            SIV_MAX_COMPONENTS,
+            associated_data.len(),
+        ));
+    }
+    Ok(())
+}
+
+/// XORs `src` into `dst` byte by byte.
+fn xor_into(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_matches, assert_ok};
+    use rstest::rstest;
+
+    const K1: u128 = 0xfffe_fdfc_fbfa_f9f8_f7f6_f5f4_f3f2_f1f0;
+    const K2: u128 = 0xf0f1_f2f3_f4f5_f6f7_f8f9_fafb_fcfd_feff;
+    const AAD: &[u8] = b"header";
+    const PLAINTEXT: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(15)]
+    #[case(16)]
+    #[case(17)]
+    #[case(64)]
+    fn roundtrip(#[case] len: usize) {
+        let siv = assert_ok!(AesSiv::new(K1, K2));
+        let plaintext = vec![0xA5; len];
+
+        let sealed = assert_ok!(siv.encrypt(&[AAD], &plaintext));
+        assert_eq!(sealed.len(), SIV_TAG_SIZE + len);
+        assert_eq!(assert_ok!(siv.decrypt(&[AAD], &sealed)), plaintext);
+    }
+
+    #[test]
+    fn encryption_is_deterministic() {
+        let siv = assert_ok!(AesSiv::new(K1, K2));
+        let first = assert_ok!(siv.encrypt(&[AAD], PLAINTEXT));
+        assert_eq!(assert_ok!(siv.encrypt(&[AAD], PLAINTEXT)), first);
+        assert_ne!(assert_ok!(siv.encrypt(&[AAD, b"nonce"], PLAINTEXT)), first);
+    }
+
+    #[test]
+    fn component_boundaries_are_authenticated() {
+        let siv = assert_ok!(AesSiv::new(K1, K2));
+        let split = assert_ok!(siv.encrypt(&[b"head", b"er"], PLAINTEXT));
+        let joined = assert_ok!(siv.encrypt(&[AAD], PLAINTEXT));
+        assert_ne!(split, joined);
+        assert_err!(siv.decrypt(&[AAD], &split));
+    }
+
+    #[test]
+    fn tampering_fails() {
+        let siv = assert_ok!(AesSiv::new(K1, K2));
+        let sealed = assert_ok!(siv.encrypt(&[AAD], PLAINTEXT));
+
+        let mut tampered = sealed.clone();
+        tampered[SIV_TAG_SIZE] ^= 1;
+        let err = assert_err!(siv.decrypt(&[AAD], &tampered));
+        assert_matches!(err, CipherError::AuthenticationFailed);
+
+        assert_err!(siv.decrypt(&[b"other data"], &sealed));
+        assert_err!(siv.decrypt(&[], &sealed));
+        assert_err!(siv.decrypt(&[AAD], &sealed[..SIV_TAG_SIZE - 1]));
+    }
+
+    #[test]
+    fn aead_prepends_siv() {
+        let siv = assert_ok!(AesSiv::new(K1, K2));
+        let sealed = assert_ok!(siv.seal(b"nonce", AAD, PLAINTEXT));
+        assert_eq!(sealed, assert_ok!(siv.encrypt(&[AAD, b"nonce"], PLAINTEXT)));
+        assert_eq!(
+            assert_ok!(siv.seal(&[], AAD, PLAINTEXT)),
+            assert_ok!(siv.encrypt(&[AAD], PLAINTEXT))
+        );
+
+        assert_eq!(assert_ok!(siv.open(b"nonce", AAD, &sealed)), PLAINTEXT);
+        assert_err!(siv.open(b"other", AAD, &sealed));
+    }
+
+    #[test]
+    fn too_many_components_fails() {
+        let siv = assert_ok!(AesSiv::new(K1, K2));
+        let components = [AAD; SIV_MAX_COMPONENTS + 1];
+        assert_ok!(siv.encrypt(&components[..SIV_MAX_COMPONENTS], PLAINTEXT));
+        let err = assert_err!(siv.encrypt(&components, PLAINTEXT));
+        assert_matches!(
+            err,
+            CipherError::TooManyComponents {
+                max: SIV_MAX_COMPONENTS,
+                actual: 127
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(16)]
+    #[case(24)]
+    #[case(33)]
+    #[case(128)]
+    fn invalid_combined_key_fails(#[case] len: usize) {
+        assert_err!(AesSiv::from_slice(&vec![0; len]));
+    }
+
+    #[test]
+    fn mismatched_keys_fail() {
+        assert_err!(AesSiv::new(K1, [0; 32]));
+    }
+}
@@ -0,0 +1,385 @@
+//! Round-by-round record of an AES encryption.
+//!
+//! A [`Trace`] holds the state after every step of every round, laid out like
+//! the cipher example in FIPS-197 Appendix B, so hand computations can be
+//! checked one step at a time. Its [`Display`] output reproduces the
+//! Appendix B table; with the `serde` feature it also serializes, with each
+//! state written as a hex string.
+
+use crate::{
+    Block128,
+    key::{Subkey, Subkeys},
+    operations::{add_round_key, mix_columns, shift_rows, sub_bytes},
+};
+use std::fmt::{Debug, Display};
+
+/// The 4x4 byte state, stored column by column as in the cipher input.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct State([u8; 16]);
+
+impl State {
+    #[inline]
+    #[must_use]
+    pub const fn new(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn to_bytes(self) -> [u8; 16] {
+        self.0
+    }
+
+    /// Returns the byte in `row` and `column` of the state matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` or `column` is not below 4.
+    #[inline]
+    #[must_use]
+    pub const fn get(self, row: usize, column: usize) -> u8 {
+        assert!(row < 4 && column < 4, "state index out of range");
+        self.0[4 * column + row]
+    }
+
+    /// Formats one row of the state matrix, e.g. `19 a0 9a e9`.
+    fn row(self, row: usize) -> String {
+        (0..4)
+            .map(|column| format!("{:02x}", self.get(row, column)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Packs a round key's four words into a state.
+    fn from_round_key(key: &[Subkey; 4]) -> Self {
+        let mut bytes = [0; 16];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(key) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        Self(bytes)
+    }
+}
+
+impl From<Block128> for State {
+    fn from(block: Block128) -> Self {
+        Self(block.to_be_bytes())
+    }
+}
+
+/// Writes the state as 32 lowercase hex digits.
+impl Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl Debug for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "State({self})")
+    }
+}
+
+/// The steps of a single round.
+///
+/// Round 0 only adds the first round key to the input, so it has no
+/// `SubBytes`, `ShiftRows` or `MixColumns` state. The final round has no
+/// `MixColumns` state either, unless the cipher was built to keep it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundTrace {
+    /// Round number, from 0 to the number of rounds.
+    pub round: usize,
+    /// State at the start of the round.
+    pub start: State,
+    /// State after `SubBytes`.
+    pub sub_bytes: Option<State>,
+    /// State after `ShiftRows`.
+    pub shift_rows: Option<State>,
+    /// State after `MixColumns`.
+    pub mix_columns: Option<State>,
+    /// Round key added at the end of the round.
+    pub round_key: State,
+    /// State after `AddRoundKey`, which starts the next round.
+    pub add_round_key: State,
+}
+
+/// Every intermediate state of one block encryption.
+///
+/// Created by [`Aes::trace_encrypt`](crate::Aes::trace_encrypt).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trace {
+    /// The plaintext block.
+    pub input: State,
+    /// Round 0 (the initial `AddRoundKey`) followed by each cipher round.
+    pub rounds: Vec<RoundTrace>,
+    /// The ciphertext block.
+    pub output: State,
+}
+
+impl Trace {
+    /// Encrypts `block` with the FIPS-197 reference rounds, recording every step.
+    pub(crate) fn encrypt(subkeys: &Subkeys, block: [u8; 16], final_mix_columns: bool) -> Self {
+        let rounds = subkeys.rounds();
+        let mut keys = subkeys.chunks();
+        let mut state = Block128::from_be_bytes(block);
+        let mut trace = Vec::with_capacity(rounds + 1);
+
+        for round in 0..=rounds {
+            let key = keys.next().expect("Round key");
+            let start = state;
+            let (mut after_sub_bytes, mut after_shift_rows, mut after_mix_columns) =
+                (None, None, None);
+
+            if round > 0 {
+                state = sub_bytes(state);
+                after_sub_bytes = Some(state.into());
+                state = shift_rows(state);
+                after_shift_rows = Some(state.into());
+                // The final round has no MixColumns unless asked to keep it
+                if round < rounds || final_mix_columns {
+                    state = mix_columns(state);
+                    after_mix_columns = Some(state.into());
+                }
+            }
+            state = add_round_key(state, key);
+
+            trace.push(RoundTrace {
+                round,
+                start: start.into(),
+                sub_bytes: after_sub_bytes,
+                shift_rows: after_shift_rows,
+                mix_columns: after_mix_columns,
+                round_key: State::from_round_key(key),
+                add_round_key: state.into(),
+            });
+        }
+
+        Self {
+            input: State::new(block),
+            rounds: trace,
+            output: state.into(),
+        }
+    }
+}
+
+/// Width of one state matrix column in the table, `xx xx xx xx`.
+const CELL_WIDTH: usize = 11;
+
+/// Writes the FIPS-197 Appendix B table: one row of 4x4 matrices per round,
+/// then the output.
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Round   {:CELL_WIDTH$}   {:CELL_WIDTH$}   {:CELL_WIDTH$}   {:CELL_WIDTH$}   Round Key",
+            "Start of", "After", "After", "After"
+        )?;
+        writeln!(
+            f,
+            "Number  {:CELL_WIDTH$}   {:CELL_WIDTH$}   {:CELL_WIDTH$}   {:CELL_WIDTH$}   Value",
+            "Round", "SubBytes", "ShiftRows", "MixColumns"
+        )?;
+
+        for round in &self.rounds {
+            let label = if round.round == 0 {
+                "input".to_string()
+            } else {
+                round.round.to_string()
+            };
+            let cells = [
+                Some(round.start),
+                round.sub_bytes,
+                round.shift_rows,
+                round.mix_columns,
+                Some(round.round_key),
+            ];
+            write_matrices(f, &label, &cells)?;
+        }
+
+        write_matrices(f, "output", &[Some(self.output)])
+    }
+}
+
+/// Writes states side by side as 4x4 matrices, with `label` beside the
+/// second row. Missing states leave their column blank.
+fn write_matrices(
+    f: &mut std::fmt::Formatter<'_>,
+    label: &str,
+    cells: &[Option<State>],
+) -> std::fmt::Result {
+    writeln!(f)?;
+    for row in 0..4 {
+        let label = if row == 1 { label } else { "" };
+        let line = cells
+            .iter()
+            .map(|cell| cell.map_or_else(|| " ".repeat(CELL_WIDTH), |state| state.row(row)))
+            .collect::<Vec<_>>()
+            .join("   ");
+        writeln!(f, "{}", format!("{label:8}{line}").trim_end())?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for State {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for State {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        if hex.len() != 32 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(serde::de::Error::custom(
+                "expected a state of 32 hex digits",
+            ));
+        }
+        u128::from_str_radix(&hex, 16)
+            .map(|value| Self(value.to_be_bytes()))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Aes, Backend};
+    use rstest::rstest;
+
+    const KEY: u128 = 0x2b7e_1516_28ae_d2a6_abf7_1588_09cf_4f3c;
+    const INPUT: u128 = 0x3243_f6a8_885a_308d_3131_98a2_e037_0734;
+    const OUTPUT: u128 = 0x3925_841d_02dc_09fb_dc11_8597_196a_0b32;
+
+    fn state(value: u128) -> State {
+        State::new(value.to_be_bytes())
+    }
+
+    fn fips197_trace() -> Trace {
+        Aes::new(KEY).trace_encrypt(INPUT.to_be_bytes())
+    }
+
+    // FIPS-197 Appendix B
+    #[test]
+    fn records_appendix_b() {
+        let trace = fips197_trace();
+        assert_eq!(trace.input, state(INPUT));
+        assert_eq!(trace.output, state(OUTPUT));
+        assert_eq!(trace.rounds.len(), 11);
+
+        let initial = &trace.rounds[0];
+        assert_eq!(initial.start, state(INPUT));
+        assert_eq!(initial.round_key, state(KEY));
+        assert_eq!(initial.sub_bytes, None);
+        assert_eq!(
+            initial.add_round_key,
+            state(0x193d_e3be_a0f4_e22b_9ac6_8d2a_e9f8_4808)
+        );
+
+        let first = &trace.rounds[1];
+        assert_eq!(first.start, initial.add_round_key);
+        assert_eq!(
+            first.sub_bytes,
+            Some(state(0xd427_11ae_e0bf_98f1_b8b4_5de5_1e41_5230))
+        );
+        assert_eq!(
+            first.shift_rows,
+            Some(state(0xd4bf_5d30_e0b4_52ae_b841_11f1_1e27_98e5))
+        );
+        assert_eq!(
+            first.mix_columns,
+            Some(state(0x0466_81e5_e0cb_199a_48f8_d37a_2806_264c))
+        );
+        assert_eq!(
+            first.round_key,
+            state(0xa0fa_fe17_8854_2cb1_23a3_3939_2a6c_7605)
+        );
+
+        let last = &trace.rounds[10];
+        assert_eq!(last.mix_columns, None);
+        assert_eq!(
+            last.round_key,
+            state(0xd014_f9a8_c9ee_2589_e13f_0cc8_b663_0ca6)
+        );
+        assert_eq!(last.add_round_key, trace.output);
+    }
+
+    #[rstest]
+    #[case("0x000102030405060708090a0b0c0d0e0f", 10)]
+    #[case("0x000102030405060708090a0b0c0d0e0f1011121314151617", 12)]
+    #[case(
+        "0x000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        14
+    )]
+    fn output_matches_cipher(
+        #[case] key: &str,
+        #[case] rounds: usize,
+        #[values(
+            Backend::Reference,
+            Backend::TTable,
+            Backend::Bitsliced,
+            Backend::AesNi
+        )]
+        backend: Backend,
+    ) {
+        use std::str::FromStr;
+
+        let aes = Aes::with_backend(crate::Key::from_str(key).expect("valid key"), backend);
+        let block = 0x0011_2233_4455_6677_8899_aabb_ccdd_eeff_u128;
+        let trace = aes.trace_encrypt(block.to_be_bytes());
+
+        assert_eq!(trace.rounds.len(), rounds + 1);
+        assert_eq!(
+            Block128::new(u128::from_be_bytes(trace.output.to_bytes())),
+            aes.encrypt_block(Block128::new(block))
+        );
+        for pair in trace.rounds.windows(2) {
+            assert_eq!(pair[1].start, pair[0].add_round_key);
+        }
+    }
+
+    #[test]
+    fn state_is_column_major() {
+        let input = state(INPUT);
+        assert_eq!(input.get(0, 0), 0x32);
+        assert_eq!(input.get(1, 0), 0x43);
+        assert_eq!(input.get(0, 1), 0x88);
+        assert_eq!(input.get(3, 3), 0x34);
+        assert_eq!(input.row(0), "32 88 31 e0");
+        assert_eq!(input.to_string(), "3243f6a8885a308d313198a2e0370734");
+    }
+
+    #[test]
+    fn text_matches_appendix_b_layout() {
+        let text = fips197_trace().to_string();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert!(lines[0].starts_with("Round   Start of"));
+        assert!(lines[1].starts_with("Number  Round"));
+        // Round 0 has only the input and the first round key
+        assert_eq!(
+            lines[3..7],
+            [
+                "        32 88 31 e0                                             2b 28 ab 09",
+                "input   43 5a 31 37                                             7e ae f7 cf",
+                "        f6 30 98 07                                             15 d2 15 4f",
+                "        a8 8d a2 34                                             16 a6 88 3c",
+            ]
+        );
+        assert_eq!(
+            lines[9],
+            "1       3d f4 c6 f8   27 bf b4 41   bf b4 41 27   66 cb f8 06   fa 54 a3 6c"
+        );
+        assert_eq!(
+            lines[lines.len() - 4..],
+            [
+                "        39 02 dc 19",
+                "output  25 dc 11 6a",
+                "        84 09 85 0b",
+                "        1d fb 97 32",
+            ]
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! XTS-AES tweakable block cipher mode (IEEE 1619).
+//!
+//! XTS encrypts storage in fixed-size data units (sectors). Each sector is
+//! encrypted independently under a tweak derived from its data-unit number,
+//! so any sector can be read or rewritten without touching the others, and
+//! the ciphertext is exactly as long as the plaintext.
+
+use crate::{Aes, Block128, KeySize, key::Key};
+use cipher_core::{CipherAction, CipherError, CipherResult};
+use std::fmt::Debug;
+
+const BLOCK_SIZE: usize = 16;
+
+/// Feedback constant for multiplication by `α` in GF(2^128): x^7 + x^2 + x + 1.
+const GF_128_FDBK: u128 = 0x87;
+
+/// XTS-AES-128 or XTS-AES-256 with separate data and tweak keys.
+///
+/// Data units must be at least one block (16 bytes) long. Units that are not
+/// a multiple of the block size are handled with ciphertext stealing.
+///
+/// # Example
+///
+/// ```
+/// use aes::AesXts;
+///
+/// let xts = AesXts::new(
+///     0x27182818_28459045_23536028_74713526_u128,
+///     0x31415926_53589793_23846264_33832795_u128,
+/// )
+/// .unwrap();
+///
+/// let mut sector = *b"Sector payload of any length >= 16";
+/// xts.encrypt_sector(7, &mut sector).unwrap();
+/// xts.decrypt_sector(7, &mut sector).unwrap();
+/// assert_eq!(&sector, b"Sector payload of any length >= 16");
+/// ```
+pub struct AesXts {
+    data: Aes,
+    tweak: Aes,
+}
+
+impl AesXts {
+    /// Creates XTS-AES from the data key (`Key1`) and tweak key (`Key2`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` if the keys differ in size or
+    /// are 192-bit keys, which IEEE 1619 does not define.
+    pub fn new(data_key: impl Into<Key>, tweak_key: impl Into<Key>) -> CipherResult<Self> {
+        let (data_key, tweak_key) = (data_key.into(), tweak_key.into());
+        let size = data_key.size();
+        if size == KeySize::Aes192 {
+            return Err(CipherError::invalid_key_size(
+                KeySize::Aes256.bytes(),
+                size.bytes(),
+            ));
+        }
+        if tweak_key.size() != size {
+            return Err(CipherError::invalid_key_size(
+                size.bytes(),
+                tweak_key.size().bytes(),
+            ));
+        }
+
+        Ok(Self {
+            data: Aes::from_key(data_key),
+            tweak: Aes::from_key(tweak_key),
+        })
+    }
+
+    /// Creates XTS-AES from a combined 256 or 512-bit key `Key1 || Key2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` if `key` is not 32 or 64 bytes.
+    pub fn from_slice(key: &[u8]) -> CipherResult<Self> {
+        if key.len() != 32 && key.len() != 64 {
+            return Err(CipherError::invalid_key_size(64, key.len()));
+        }
+
+        let (data_key, tweak_key) = key.split_at(key.len() / 2);
+        Self::new(Key::from_slice(data_key)?, Key::from_slice(tweak_key)?)
+    }
+
+    /// Returns the size of each of the two keys.
+    #[inline]
+    #[must_use]
+    pub const fn key_size(&self) -> KeySize {
+        self.data.key_size()
+    }
+
+    /// Encrypts one data unit in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidBlockSize` if `data` is shorter than 16 bytes.
+    pub fn encrypt_sector(&self, sector: u128, data: &mut [u8]) -> CipherResult<()> {
+        self.process_sector(sector, data, CipherAction::Encrypt)
+    }
+
+    /// Decrypts one data unit in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidBlockSize` if `data` is shorter than 16 bytes.
+    pub fn decrypt_sector(&self, sector: u128, data: &mut [u8]) -> CipherResult<()> {
+        self.process_sector(sector, data, CipherAction::Decrypt)
+    }
+
+    /// Encrypts consecutive data units of `sector_size` bytes in place,
+    /// starting at data-unit number `first_sector`.
+    ///
+    /// The final data unit may be shorter than `sector_size`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidBlockSize` if `sector_size` or the final
+    /// data unit is shorter than 16 bytes, and `CipherError::CounterOverflow`
+    /// if the data-unit number would wrap. In both cases `data` is left unchanged.
+    pub fn encrypt_sectors(
+        &self,
+        first_sector: u128,
+        sector_size: usize,
+        data: &mut [u8],
+    ) -> CipherResult<()> {
+        self.process_sectors(first_sector, sector_size, data, CipherAction::Encrypt)
+    }
+
+    /// Decrypts consecutive data units of `sector_size` bytes in place,
+    /// starting at data-unit number `first_sector`.
+    ///
+    /// # Errors
+    ///
+    /// See [`encrypt_sectors`](Self::encrypt_sectors).
+    pub fn decrypt_sectors(
+        &self,
+        first_sector: u128,
+        sector_size: usize,
+        data: &mut [u8],
+    ) -> CipherResult<()> {
+        self.process_sectors(first_sector, sector_size, data, CipherAction::Decrypt)
+    }
+
+    fn process_sectors(
+        &self,
+        first_sector: u128,
+        sector_size: usize,
+        data: &mut [u8],
+        action: CipherAction,
+    ) -> CipherResult<()> {
+        if sector_size < BLOCK_SIZE {
+            return Err(CipherError::invalid_block_size(BLOCK_SIZE, sector_size));
+        }
+        // Validate up front so an error never leaves `data` half processed
+        let tail = data.len() % sector_size;
+        if tail != 0 && tail < BLOCK_SIZE {
+            return Err(CipherError::invalid_block_size(BLOCK_SIZE, tail));
+        }
+        let sectors = data.len().div_ceil(sector_size) as u128;
+        if sectors > 0 && first_sector.checked_add(sectors - 1).is_none() {
+            return Err(CipherError::counter_overflow(128));
+        }
+
+        for (idx, chunk) in (0..).zip(data.chunks_mut(sector_size)) {
+            self.process_sector(first_sector + idx, chunk, action)?;
+        }
+        Ok(())
+    }
+
+    fn process_sector(
+        &self,
+        sector: u128,
+        data: &mut [u8],
+        action: CipherAction,
+    ) -> CipherResult<()> {
+        if data.len() < BLOCK_SIZE {
+            return Err(CipherError::invalid_block_size(BLOCK_SIZE, data.len()));
+        }
+
+        // The data-unit number is encoded little-endian, as is the tweak
+        let encrypted = self
+            .tweak
+            .encrypt_block(Block128::from_be_bytes(sector.to_le_bytes()));
+        let mut tweak = u128::from_le_bytes(encrypted.to_be_bytes());
+
+        let remainder = data.len() % BLOCK_SIZE;
+        let full_blocks = data.len() / BLOCK_SIZE;
+        // With a partial final block, the last full block takes part in stealing
+        let plain_blocks = if remainder == 0 {
+            full_blocks
+        } else {
+            full_blocks - 1
+        };
+
+        let (head, tail) = data.split_at_mut(plain_blocks * BLOCK_SIZE);
+        for chunk in head.chunks_exact_mut(BLOCK_SIZE) {
+            let block = self.xts_block(to_block(chunk), tweak, action);
+            chunk.copy_from_slice(&block);
+            tweak = mul_alpha(tweak);
+        }
+
+        if remainder != 0 {
+            self.steal(tail, tweak, action);
+        }
+        Ok(())
+    }
+
+    /// Ciphertext stealing over the last full block and the partial block
+    /// in `tail`, where `tweak` belongs to the last full block.
+    fn steal(&self, tail: &mut [u8], tweak: u128, action: CipherAction) {
+        let remainder = tail.len() - BLOCK_SIZE;
+        let next_tweak = mul_alpha(tweak);
+        // Decryption undoes the final full block first, so the tweaks swap
+        let (first_tweak, second_tweak) = match action {
+            CipherAction::Encrypt => (tweak, next_tweak),
+            CipherAction::Decrypt => (next_tweak, tweak),
+        };
+
+        let (full, partial) = tail.split_at_mut(BLOCK_SIZE);
+        let first = self.xts_block(to_block(full), first_tweak, action);
+
+        let mut stolen = first;
+        stolen[..remainder].copy_from_slice(partial);
+        partial.copy_from_slice(&first[..remainder]);
+
+        full.copy_from_slice(&self.xts_block(stolen, second_tweak, action));
+    }
+
+    /// `C = E_K1(P ^ T) ^ T`, or its inverse.
+    fn xts_block(
+        &self,
+        block: [u8; BLOCK_SIZE],
+        tweak: u128,
+        action: CipherAction,
+    ) -> [u8; BLOCK_SIZE] {
+        let tweak = tweak.to_le_bytes();
+        let input = Block128::from_be_bytes(xor_block(block, tweak));
+        let output = match action {
+            CipherAction::Encrypt => self.data.encrypt_block(input),
+            CipherAction::Decrypt => self.data.decrypt_block(input),
+        };
+        xor_block(output.to_be_bytes(), tweak)
+    }
+}
+
+/// Omits the keys.
+impl Debug for AesXts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesXts")
+            .field("key_size", &self.key_size())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Multiplies the tweak by `α` (the polynomial x) in GF(2^128).
+///
+/// The tweak is a little-endian integer, so this is a left shift with the
+/// carry folded back into the lowest byte.
+const fn mul_alpha(tweak: u128) -> u128 {
+    (tweak << 1) ^ (GF_128_FDBK & 0u128.wrapping_sub(tweak >> 127))
+}
+
+const fn to_block(chunk: &[u8]) -> [u8; BLOCK_SIZE] {
+    let mut block = [0; BLOCK_SIZE];
+    block.copy_from_slice(chunk);
+    block
+}
+
+fn xor_block(mut block: [u8; BLOCK_SIZE], other: [u8; BLOCK_SIZE]) -> [u8; BLOCK_SIZE] {
+    for (byte, other) in block.iter_mut().zip(other) {
+        *byte ^= other;
+    }
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_matches, assert_ok};
+    use rstest::rstest;
+
+    const KEY1: u128 = 0x2718_2818_2845_9045_2353_6028_7471_3526;
+    const KEY2: u128 = 0x3141_5926_5358_9793_2384_6264_3383_2795;
+
+    #[test]
+    fn mul_alpha_shifts_and_reduces() {
+        assert_eq!(mul_alpha(1), 2);
+        assert_eq!(mul_alpha(1 << 127), GF_128_FDBK);
+        assert_eq!(mul_alpha((1 << 127) | 1), GF_128_FDBK ^ 2);
+    }
+
+    #[rstest]
+    #[case(16)]
+    #[case(17)]
+    #[case(31)]
+    #[case(32)]
+    #[case(47)]
+    #[case(512)]
+    fn sector_roundtrip(#[case] len: usize) {
+        let xts = assert_ok!(AesXts::new(KEY1, KEY2));
+        let plaintext = (0..=u8::MAX).cycle().take(len).collect::<Vec<_>>();
+
+        let mut data = plaintext.clone();
+        assert_ok!(xts.encrypt_sector(42, &mut data));
+        assert_ne!(data, plaintext);
+        assert_ok!(xts.decrypt_sector(42, &mut data));
+        assert_eq!(data, plaintext);
+    }
+
+    #[test]
+    fn sectors_use_distinct_tweaks() {
+        let xts = assert_ok!(AesXts::new(KEY1, KEY2));
+        let mut data = [0u8; 64];
+        assert_ok!(xts.encrypt_sectors(0, 32, &mut data));
+        assert_ne!(data[..32], data[32..]);
+
+        let mut second = [0u8; 32];
+        assert_ok!(xts.encrypt_sector(1, &mut second));
+        assert_eq!(data[32..], second);
+    }
+
+    #[test]
+    fn short_sector_fails() {
+        let xts = assert_ok!(AesXts::new(KEY1, KEY2));
+        let err = assert_err!(xts.encrypt_sector(0, &mut [0u8; 15]));
+        assert_matches!(err, CipherError::InvalidBlockSize { .. });
+    }
+
+    #[test]
+    fn short_final_sector_leaves_data_unchanged() {
+        let xts = assert_ok!(AesXts::new(KEY1, KEY2));
+        let mut data = [0u8; 32 + 8];
+        assert_err!(xts.encrypt_sectors(0, 32, &mut data));
+        assert_eq!(data, [0u8; 40]);
+    }
+
+    #[test]
+    fn sector_number_overflow_fails() {
+        let xts = assert_ok!(AesXts::new(KEY1, KEY2));
+        let mut data = [0u8; 64];
+        assert_ok!(xts.encrypt_sectors(u128::MAX, 64, &mut data));
+        let err = assert_err!(xts.encrypt_sectors(u128::MAX, 32, &mut data));
+        assert_matches!(err, CipherError::CounterOverflow { bits: 128 });
+    }
+
+    #[test]
+    fn mismatched_keys_fail() {
+        assert_err!(AesXts::new(KEY1, [0u8; 32]));
+        assert_err!(AesXts::new([0u8; 24], [0u8; 24]));
+        assert_err!(AesXts::from_slice(&[0u8; 48]));
+        assert_ok!(AesXts::from_slice(&[0u8; 64]));
+    }
+}
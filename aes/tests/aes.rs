@@ -1,6 +1,8 @@
-use aes::Aes;
-use cipher_core::BlockCipher;
+use aes::{Aes, Backend, Key, KeySize};
+use cipher_core::{BlockCipher, CipherError};
+use rand::{Rng, SeedableRng, rngs::StdRng};
 use rstest::rstest;
+use std::str::FromStr;
 
 const TEST_KEY: u128 = 0x0F15_71C9_47D9_E859_1CB7_ADD6_AF7F_6798;
 const TEST_PLAINTEXT: u128 = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210;
@@ -512,8 +514,15 @@ fn encrypt_decrypt_roundtrip(
     #[case] plaintext: u128,
     #[case] expected_ciphertext: u128,
     #[case] key: u128,
+    #[values(
+        Backend::Reference,
+        Backend::TTable,
+        Backend::Bitsliced,
+        Backend::AesNi
+    )]
+    backend: Backend,
 ) {
-    let aes = Aes::new(key);
+    let aes = Aes::with_backend(key, backend);
 
     // Encrypt
     let ciphertext = aes
@@ -541,3 +550,277 @@ fn encrypt_decrypt_roundtrip(
         "Decryption failed. Expected 0x{plaintext:032X}, got 0x{decrypted_u128:032X}"
     );
 }
+
+// FIPS-197 Appendix C.2/C.3 and NIST SP 800-38A F.1.3/F.1.5 test vectors
+#[rstest]
+#[case(
+    "000102030405060708090a0b0c0d0e0f1011121314151617",
+    0x0011_2233_4455_6677_8899_aabb_ccdd_eeff,
+    0xdda9_7ca4_864c_dfe0_6eaf_70a0_ec0d_7191
+)]
+#[case(
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    0x6bc1_bee2_2e40_9f96_e93d_7e11_7393_172a,
+    0xbd33_4f1d_6e45_f25f_f712_a214_571f_a5cc
+)]
+#[case(
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    0xae2d_8a57_1e03_ac9c_9eb7_6fac_45af_8e51,
+    0x9741_0484_6d0a_d3ad_7734_ecb3_ecee_4eef
+)]
+#[case(
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    0x0011_2233_4455_6677_8899_aabb_ccdd_eeff,
+    0x8ea2_b7ca_5167_45bf_eafc_4990_4b49_6089
+)]
+#[case(
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    0x6bc1_bee2_2e40_9f96_e93d_7e11_7393_172a,
+    0xf3ee_d1bd_b5d2_a03c_064b_5a7e_3db1_81f8
+)]
+#[case(
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    0xae2d_8a57_1e03_ac9c_9eb7_6fac_45af_8e51,
+    0x591c_cb10_d410_ed26_dc5b_a74a_3136_2870
+)]
+fn aes_192_256_encrypt_decrypt(
+    #[case] key: &str,
+    #[case] plaintext: u128,
+    #[case] expected_ciphertext: u128,
+    #[values(
+        Backend::Reference,
+        Backend::TTable,
+        Backend::Bitsliced,
+        Backend::AesNi
+    )]
+    backend: Backend,
+) {
+    let key = Key::from_str(&format!("0x{key}")).expect("valid key");
+    let aes = Aes::with_backend(key, backend);
+
+    let ciphertext = aes
+        .encrypt(&plaintext.to_be_bytes())
+        .expect("Encryption failed");
+    let ciphertext_u128 =
+        u128::from_be_bytes(ciphertext.as_slice().try_into().expect("ciphertext"));
+
+    assert_eq!(
+        ciphertext_u128, expected_ciphertext,
+        "Encryption failed. Expected 0x{expected_ciphertext:032X}, got 0x{ciphertext_u128:032X}"
+    );
+
+    let decrypted = aes.decrypt(&ciphertext).expect("Decryption failed");
+    let decrypted_u128 = u128::from_be_bytes(decrypted.as_slice().try_into().expect("plaintext"));
+
+    assert_eq!(
+        decrypted_u128, plaintext,
+        "Decryption failed. Expected 0x{plaintext:032X}, got 0x{decrypted_u128:032X}"
+    );
+}
+
+#[rstest]
+#[case("0x2b7e151628aed2a6abf7158809cf4f3c", KeySize::Aes128, 10)]
+#[case(
+    "0x8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    KeySize::Aes192,
+    12
+)]
+#[case(
+    "0x603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    KeySize::Aes256,
+    14
+)]
+fn rounds_per_key_size(
+    #[case] key: &str,
+    #[case] size: KeySize,
+    #[case] rounds: usize,
+    #[values(
+        Backend::Reference,
+        Backend::TTable,
+        Backend::Bitsliced,
+        Backend::AesNi
+    )]
+    backend: Backend,
+) {
+    let aes = Aes::with_backend(Key::from_str(key).expect("valid key"), backend);
+    if backend.is_available() {
+        assert_eq!(aes.backend(), backend);
+    } else {
+        assert_eq!(aes.backend(), Backend::TTable);
+    }
+    assert_eq!(aes.key_size(), size);
+    assert_eq!(aes.rounds(), rounds);
+}
+
+#[rstest]
+fn backends_agree_on_batches(#[values(1, 3, 4, 9)] count: usize) {
+    let key = "0x603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4";
+    let blocks: Vec<[u8; 16]> = (0..count)
+        .map(|n| (TEST_PLAINTEXT ^ (n as u128).rotate_left(40)).to_be_bytes())
+        .collect();
+
+    let mut expected = blocks.clone();
+    Aes::with_backend(Key::from_str(key).expect("valid key"), Backend::Reference)
+        .encrypt_blocks(&mut expected);
+
+    for backend in [Backend::TTable, Backend::Bitsliced, Backend::AesNi] {
+        let aes = Aes::with_backend(Key::from_str(key).expect("valid key"), backend);
+        let mut actual = blocks.clone();
+        aes.encrypt_blocks(&mut actual);
+        assert_eq!(actual, expected, "{backend}");
+
+        aes.decrypt_blocks(&mut actual);
+        assert_eq!(actual, blocks, "{backend}");
+    }
+}
+
+#[rstest]
+#[cfg_attr(not(target_arch = "x86_64"), ignore = "requires AES-NI")]
+fn aesni_matches_portable_backends(
+    #[values(
+        "0x2b7e151628aed2a6abf7158809cf4f3c",
+        "0x8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+        "0x603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4"
+    )]
+    key: &str,
+    #[values(Backend::Reference, Backend::TTable)] portable: Backend,
+) {
+    #[cfg(target_arch = "x86_64")]
+    if !std::arch::is_x86_feature_detected!("aes") {
+        eprintln!("skipping aesni_matches_portable_backends: the CPU has no AES-NI");
+        return;
+    }
+
+    let aesni = Aes::with_backend(Key::from_str(key).expect("valid key"), Backend::AesNi);
+    let expected = Aes::with_backend(Key::from_str(key).expect("valid key"), portable);
+    assert_eq!(aesni.backend(), Backend::AesNi);
+
+    let mut rng = StdRng::seed_from_u64(0x0017);
+    for _ in 0..256 {
+        let block: [u8; 16] = rng.random();
+        let ciphertext = aesni.encrypt(&block).expect("Encryption failed");
+        assert_eq!(
+            ciphertext,
+            expected.encrypt(&block).expect("Encryption failed"),
+            "encrypt {block:02x?}"
+        );
+        assert_eq!(
+            aesni.decrypt(&block).expect("Decryption failed"),
+            expected.decrypt(&block).expect("Decryption failed"),
+            "decrypt {block:02x?}"
+        );
+        let decrypted = aesni.decrypt(&ciphertext).expect("Decryption failed");
+        assert_eq!(decrypted.as_slice(), block);
+    }
+}
+
+// Reduced-round ciphertexts for the FIPS-197 Appendix B example. With the
+// final MixColumns kept, r rounds end at the state that starts round r + 1
+// of the full cipher; without it, they end one MixColumns short of it.
+#[rstest]
+#[case(1, true, 0xa49c_7ff2_689f_352b_6b5b_ea43_026a_5049)]
+#[case(2, true, 0xaa8f_5f03_61dd_e3ef_82d2_4ad2_6832_469a)]
+#[case(3, true, 0x486c_4eee_671d_9d0d_4de3_b138_d65f_58e7)]
+#[case(4, true, 0xe092_7fe8_c863_63c0_d9b1_3550_85b8_be01)]
+#[case(10, false, 0x3925_841d_02dc_09fb_dc11_8597_196a_0b32)]
+fn reduced_rounds_match_appendix_b(
+    #[case] rounds: usize,
+    #[case] final_mix_columns: bool,
+    #[case] expected: u128,
+) {
+    let input = 0x3243_f6a8_885a_308d_3131_98a2_e037_0734_u128;
+    let aes = Aes::with_rounds(
+        0x2b7e_1516_28ae_d2a6_abf7_1588_09cf_4f3c_u128,
+        rounds,
+        final_mix_columns,
+    )
+    .expect("valid rounds");
+    assert_eq!(aes.rounds(), rounds);
+
+    let ciphertext = aes
+        .encrypt(&input.to_be_bytes())
+        .expect("Encryption failed");
+    assert_eq!(ciphertext.as_slice(), expected.to_be_bytes());
+    let decrypted = aes.decrypt(&ciphertext).expect("Decryption failed");
+    assert_eq!(decrypted.as_slice(), input.to_be_bytes());
+}
+
+#[rstest]
+fn reduced_rounds_skip_final_mix_columns(#[values(1, 2, 3, 4, 9)] rounds: usize) {
+    let key = 0x2b7e_1516_28ae_d2a6_abf7_1588_09cf_4f3c_u128;
+    let input = 0x3243_f6a8_885a_308d_3131_98a2_e037_0734_u128.to_be_bytes();
+    let full = Aes::new(key).trace_encrypt(input);
+    let round = &full.rounds[rounds];
+
+    // Without the final MixColumns, r rounds add round key r straight after
+    // ShiftRows of round r
+    let shift_rows = round.shift_rows.expect("ShiftRows state").to_bytes();
+    let round_key = round.round_key.to_bytes();
+    let expected: Vec<u8> = shift_rows
+        .iter()
+        .zip(round_key)
+        .map(|(s, k)| s ^ k)
+        .collect();
+
+    let aes = Aes::with_rounds(key, rounds, false).expect("valid rounds");
+    assert_eq!(
+        aes.encrypt(&input).expect("Encryption failed").as_slice(),
+        expected
+    );
+}
+
+#[rstest]
+fn extended_rounds_roundtrip(
+    #[values(
+        "0x2b7e151628aed2a6abf7158809cf4f3c",
+        "0x8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+        "0x603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4"
+    )]
+    key: &str,
+    #[values(1, 7, 14)] rounds: usize,
+    #[values(false, true)] final_mix_columns: bool,
+) {
+    let aes = Aes::with_rounds(
+        Key::from_str(key).expect("valid key"),
+        rounds,
+        final_mix_columns,
+    )
+    .expect("valid rounds");
+    assert_eq!(aes.rounds(), rounds);
+    assert_eq!(aes.final_mix_columns(), final_mix_columns);
+
+    let ciphertext = aes
+        .encrypt(&TEST_PLAINTEXT.to_be_bytes())
+        .expect("Encryption failed");
+    let decrypted = aes.decrypt(&ciphertext).expect("Decryption failed");
+    assert_eq!(decrypted.as_slice(), TEST_PLAINTEXT.to_be_bytes());
+
+    // The trace follows the same rounds
+    let trace = aes.trace_encrypt(TEST_PLAINTEXT.to_be_bytes());
+    assert_eq!(trace.output.to_bytes().as_slice(), ciphertext.as_slice());
+}
+
+#[test]
+fn standard_rounds_match_default_cipher() {
+    for size in KeySize::ALL {
+        let key = Key::from_slice(&[0x5A; 32][..size.bytes()]).expect("valid key");
+        let aes = Aes::with_rounds(key, size.rounds(), false).expect("valid rounds");
+        let expected = Aes::new(Key::from_slice(&[0x5A; 32][..size.bytes()]).expect("valid key"));
+        assert_eq!(
+            aes.encrypt(&TEST_PLAINTEXT.to_be_bytes())
+                .expect("Encryption failed"),
+            expected
+                .encrypt(&TEST_PLAINTEXT.to_be_bytes())
+                .expect("Encryption failed"),
+            "{size}"
+        );
+    }
+}
+
+#[rstest]
+fn invalid_rounds_fail(#[values(0, 15, 100)] rounds: usize) {
+    let err = Aes::with_rounds(0_u128, rounds, false)
+        .err()
+        .expect("invalid rounds");
+    assert_eq!(err, CipherError::invalid_rounds(1, Aes::MAX_ROUNDS, rounds));
+}
@@ -1,5 +1,7 @@
-use aes::{AesCbc, Iv};
+use aes::{AesCbc, Iv, Key};
 use claims::assert_ok;
+use rstest::rstest;
+use std::str::FromStr;
 
 // NIST SP 800-38A test vectors for AES-128-CBC
 const NIST_KEY: u128 = 0x2b7e_1516_28ae_d2a6_abf7_1588_09cf_4f3c;
@@ -112,3 +114,43 @@ fn arbitrary_length_plaintext() {
 
     assert_eq!(decrypted, plaintext);
 }
+
+// NIST SP 800-38A F.2.3 (CBC-AES192) and F.2.5 (CBC-AES256) test vectors
+#[rstest]
+#[case(
+    "0x8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    [
+        0x4f02_1db2_43bc_633d_7178_183a_9fa0_71e8,
+        0xb4d9_ada9_ad7d_edf4_e5e7_3876_3f69_145a,
+        0x571b_2420_12fb_7ae0_7fa9_baac_3df1_02e0,
+        0x08b0_e279_8859_8881_d920_a9e6_4f56_15cd,
+    ]
+)]
+#[case(
+    "0x603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    [
+        0xf58c_4c04_d6e5_f1ba_779e_abfb_5f7b_fbd6,
+        0x9cfc_4e96_7edb_808d_679f_777b_c670_2c7d,
+        0x39f2_3369_a9d9_bacf_a530_e263_0423_1461,
+        0xb2eb_05e2_c39b_e9fc_da6c_1907_8c6a_9d1b,
+    ]
+)]
+fn nist_multi_block_aes_192_256(#[case] key: &str, #[case] expected: [u128; 4]) {
+    let key = assert_ok!(Key::from_str(key));
+    let cipher = AesCbc::new(key, Iv::new(NIST_IV));
+
+    let plaintext = NIST_BLOCKS
+        .iter()
+        .flat_map(|(pt, _)| pt.to_be_bytes())
+        .collect::<Vec<_>>();
+    let expected = expected
+        .iter()
+        .flat_map(|ct| ct.to_be_bytes())
+        .collect::<Vec<_>>();
+
+    let ciphertext = assert_ok!(cipher.encrypt(&plaintext));
+    assert_eq!(&ciphertext[16..80], &expected);
+
+    let decrypted = assert_ok!(cipher.decrypt(&ciphertext));
+    assert_eq!(decrypted, plaintext);
+}
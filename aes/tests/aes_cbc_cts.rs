@@ -0,0 +1,72 @@
+mod common;
+
+use aes::Aes;
+use cipher_core::{CbcCts, CipherMode, CtsVariant};
+use claims::assert_ok;
+use common::hex;
+use rstest::rstest;
+
+/// "chicken teriyaki", the RFC 3962 test key
+const KEY: u128 = 0x6368_6963_6b65_6e20_7465_7269_7961_6b69;
+const IV: [u8; 16] = [0; 16];
+const MESSAGE: &[u8] = b"I would like the General Gau's Chicken, please, and wonton soup.";
+
+fn cts(variant: CtsVariant) -> CbcCts<Aes> {
+    assert_ok!(CbcCts::new(Aes::new(KEY), &IV, variant))
+}
+
+// RFC 3962 Appendix B (Kerberos AES-CTS is CBC-CS3)
+#[rstest]
+#[case(17, "C6353568F2BF8CB4D8A580362DA7FF7F97")]
+#[case(31, "FC00783E0EFDB2C1D445D4C8EFF7ED2297687268D6ECCCC0C07B25E25ECFE5")]
+#[case(32, "39312523A78662D5BE7FCBCC98EBF5A897687268D6ECCCC0C07B25E25ECFE584")]
+#[case(
+    47,
+    "97687268D6ECCCC0C07B25E25ECFE584B3FFFD940C16A18C1B5549D2F838029E39312523A78662D5BE7FCBCC98EBF5"
+)]
+#[case(
+    48,
+    "97687268D6ECCCC0C07B25E25ECFE5849DAD8BBB96C4CDC03BC103E1A194BBD839312523A78662D5BE7FCBCC98EBF5A8"
+)]
+#[case(
+    64,
+    "97687268D6ECCCC0C07B25E25ECFE58439312523A78662D5BE7FCBCC98EBF5A84807EFE836EE89A526730DBC2F7BC8409DAD8BBB96C4CDC03BC103E1A194BBD8"
+)]
+fn rfc3962(#[case] len: usize, #[case] expected: &str) {
+    let cipher = cts(CtsVariant::Cs3);
+    let ciphertext = assert_ok!(cipher.encrypt(&MESSAGE[..len]));
+    assert_eq!(ciphertext[..16], IV);
+    assert_eq!(ciphertext[16..], hex(expected));
+    assert_eq!(assert_ok!(cipher.decrypt(&ciphertext)), &MESSAGE[..len]);
+}
+
+// The same messages in the CS1 and CS2 block orders
+#[rstest]
+#[case(CtsVariant::Cs1, 17, "97C6353568F2BF8CB4D8A580362DA7FF7F")]
+#[case(CtsVariant::Cs2, 17, "C6353568F2BF8CB4D8A580362DA7FF7F97")]
+#[case(
+    CtsVariant::Cs1,
+    32,
+    "97687268D6ECCCC0C07B25E25ECFE58439312523A78662D5BE7FCBCC98EBF5A8"
+)]
+#[case(
+    CtsVariant::Cs2,
+    32,
+    "97687268D6ECCCC0C07B25E25ECFE58439312523A78662D5BE7FCBCC98EBF5A8"
+)]
+#[case(
+    CtsVariant::Cs1,
+    47,
+    "97687268D6ECCCC0C07B25E25ECFE58439312523A78662D5BE7FCBCC98EBF5B3FFFD940C16A18C1B5549D2F838029E"
+)]
+#[case(
+    CtsVariant::Cs2,
+    47,
+    "97687268D6ECCCC0C07B25E25ECFE584B3FFFD940C16A18C1B5549D2F838029E39312523A78662D5BE7FCBCC98EBF5"
+)]
+fn cs1_cs2(#[case] variant: CtsVariant, #[case] len: usize, #[case] expected: &str) {
+    let cipher = cts(variant);
+    let ciphertext = assert_ok!(cipher.encrypt(&MESSAGE[..len]));
+    assert_eq!(ciphertext[16..], hex(expected));
+    assert_eq!(assert_ok!(cipher.decrypt(&ciphertext)), &MESSAGE[..len]);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use aes::AesCcm;
+use cipher_core::{Aead, CipherError};
+use claims::{assert_err, assert_matches, assert_ok};
+use common::hex;
+use rstest::rstest;
+
+const SP800_38C_KEY: u128 = 0x4041_4243_4445_4647_4849_4a4b_4c4d_4e4f;
+const RFC3610_KEY: u128 = 0xc0c1_c2c3_c4c5_c6c7_c8c9_cacb_cccd_cecf;
+
+// NIST SP 800-38C Appendix C examples 1-3 and RFC 3610 packet vectors #1 and #2.
+// The expected output is the ciphertext followed by the tag.
+#[rstest]
+#[case(
+    SP800_38C_KEY,
+    4,
+    "10111213141516",
+    "0001020304050607",
+    "20212223",
+    "7162015b4dac255d"
+)]
+#[case(
+    SP800_38C_KEY,
+    6,
+    "1011121314151617",
+    "000102030405060708090a0b0c0d0e0f",
+    "202122232425262728292a2b2c2d2e2f",
+    "d2a1f0e051ea5f62081a7792073d593d1fc64fbfaccd"
+)]
+#[case(
+    SP800_38C_KEY,
+    8,
+    "101112131415161718191a1b",
+    "000102030405060708090a0b0c0d0e0f10111213",
+    "202122232425262728292a2b2c2d2e2f3031323334353637",
+    "e3b201a9f5b71a7a9b1ceaeccd97e70b6176aad9a4428aa5484392fbc1b09951"
+)]
+#[case(
+    RFC3610_KEY,
+    8,
+    "00000003020100a0a1a2a3a4a5",
+    "0001020304050607",
+    "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+    "588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0"
+)]
+#[case(
+    RFC3610_KEY,
+    8,
+    "00000004030201a0a1a2a3a4a5",
+    "0001020304050607",
+    "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "72c91a36e135f8cf291ca894085c87e3cc15c439c9e43a3ba091d56e10400916"
+)]
+fn known_answer(
+    #[case] key: u128,
+    #[case] tag_size: usize,
+    #[case] nonce: &str,
+    #[case] aad: &str,
+    #[case] plaintext: &str,
+    #[case] expected: &str,
+) {
+    let (nonce, aad, plaintext) = (hex(nonce), hex(aad), hex(plaintext));
+    let cipher = assert_ok!(AesCcm::new(key, nonce.len(), tag_size));
+
+    let sealed = assert_ok!(cipher.seal(&nonce, &aad, &plaintext));
+    assert_eq!(sealed, hex(expected));
+
+    let (ciphertext, tag) = assert_ok!(cipher.encrypt_detached(&nonce, &aad, &plaintext));
+    assert_eq!(tag.len(), tag_size);
+    assert_eq!([ciphertext, tag].concat(), sealed);
+
+    let opened = assert_ok!(cipher.open(&nonce, &aad, &sealed));
+    assert_eq!(opened, plaintext);
+}
+
+#[test]
+fn tampered_vector_is_rejected() {
+    let cipher = assert_ok!(AesCcm::new(RFC3610_KEY, 13, 8));
+    let nonce = hex("00000003020100a0a1a2a3a4a5");
+    let mut sealed = hex("588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0");
+
+    let last = sealed.len() - 1;
+    sealed[last] ^= 0x01;
+    let err = assert_err!(cipher.open(&nonce, &hex("0001020304050607"), &sealed));
+    assert_matches!(err, CipherError::AuthenticationFailed);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use aes::{Aes, Key};
+use cipher_core::{Cfb, CfbSegment, CipherMode};
+use claims::assert_ok;
+use common::hex;
+use rstest::rstest;
+use std::str::FromStr;
+
+// NIST SP 800-38A F.3 IV and plaintext
+const NIST_IV: u128 = 0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f;
+const NIST_PLAINTEXT: &str = concat!(
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710"
+);
+
+const KEY_128: &str = "0x2b7e151628aed2a6abf7158809cf4f3c";
+const KEY_192: &str = "0x8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b";
+const KEY_256: &str = "0x603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4";
+
+// F.3.1/3/5 (CFB1, 16 bits), F.3.7/9/11 (CFB8, 18 bytes) and F.3.13/15/17 (CFB128)
+#[rstest]
+#[case(KEY_128, CfbSegment::Bit, "68b3")]
+#[case(KEY_192, CfbSegment::Bit, "9359")]
+#[case(KEY_256, CfbSegment::Bit, "9029")]
+#[case(KEY_128, CfbSegment::Byte, "3b79424c9c0dd436bace9e0ed4586a4f32b9")]
+#[case(KEY_192, CfbSegment::Byte, "cda2521ef0a905ca44cd057cbf0d47a0678a")]
+#[case(KEY_256, CfbSegment::Byte, "dc1f1a8520a64db55fcc8ac554844e889700")]
+#[case(
+    KEY_128,
+    CfbSegment::Block,
+    concat!(
+        "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b",
+        "26751f67a3cbb140b1808cf187a4f4dfc04b05357c5d1c0eeac4c66f9ff7f2e6"
+    )
+)]
+#[case(
+    KEY_192,
+    CfbSegment::Block,
+    concat!(
+        "cdc80d6fddf18cab34c25909c99a417467ce7f7f81173621961a2b70171d3d7a",
+        "2e1e8a1dd59b88b1c8e60fed1efac4c9c05f9f9ca9834fa042ae8fba584b09ff"
+    )
+)]
+#[case(
+    KEY_256,
+    CfbSegment::Block,
+    concat!(
+        "dc7e84bfda79164b7ecd8486985d386039ffed143b28b1c832113c6331e5407b",
+        "df10132415e54b92a13ed0a8267ae2f975a385741ab9cef82031623d55b1e471"
+    )
+)]
+fn nist_encrypt_decrypt(#[case] key: &str, #[case] segment: CfbSegment, #[case] expected: &str) {
+    let key = assert_ok!(Key::from_str(key));
+    let cfb = assert_ok!(Cfb::new(
+        Aes::from_key(key),
+        &NIST_IV.to_be_bytes(),
+        segment
+    ));
+    let expected = hex(expected);
+    let plaintext = &hex(NIST_PLAINTEXT)[..expected.len()];
+
+    let ciphertext = assert_ok!(cfb.encrypt(plaintext));
+    assert_eq!(ciphertext[..16], NIST_IV.to_be_bytes());
+    assert_eq!(ciphertext[16..], expected);
+
+    let decrypted = assert_ok!(cfb.decrypt(&ciphertext));
+    assert_eq!(decrypted, plaintext);
+}
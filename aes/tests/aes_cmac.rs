@@ -0,0 +1,57 @@
+mod common;
+
+use aes::{Aes, Key};
+use cipher_core::{CipherError, Cmac, Mac};
+use claims::{assert_err, assert_matches, assert_ok};
+use common::hex;
+use rstest::rstest;
+
+const MESSAGE: &str = concat!(
+    "6bc1bee22e409f96e93d7e117393172a",
+    "ae2d8a571e03ac9c9eb76fac45af8e51",
+    "30c81c46a35ce411e5fbc1191a0a52ef",
+    "f69f2445df4f9b17ad2b417be66c3710",
+);
+
+const AES128_KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+const AES192_KEY: &str = "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b";
+const AES256_KEY: &str = "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4";
+
+fn aes(key: &str) -> Aes {
+    Aes::new(assert_ok!(Key::from_slice(&hex(key))))
+}
+
+// NIST SP 800-38B Appendix D.1-D.3 (also RFC 4493 section 4 for AES-128):
+// messages of 0, 16, 40 and 64 bytes
+#[rstest]
+#[case(AES128_KEY, 0, "bb1d6929e95937287fa37d129b756746")]
+#[case(AES128_KEY, 16, "070a16b46b4d4144f79bdd9dd04a287c")]
+#[case(AES128_KEY, 40, "dfa66747de9ae63030ca32611497c827")]
+#[case(AES128_KEY, 64, "51f0bebf7e3b9d92fc49741779363cfe")]
+#[case(AES192_KEY, 0, "d17ddf46adaacde531cac483de7a9367")]
+#[case(AES192_KEY, 16, "9e99a7bf31e710900662f65e617c5184")]
+#[case(AES192_KEY, 40, "8a1de5be2eb31aad089a82e6ee908b0e")]
+#[case(AES192_KEY, 64, "a1d5df0eed790f794d77589659f39a11")]
+#[case(AES256_KEY, 0, "028962f61b7bf89efc6b551f4667d983")]
+#[case(AES256_KEY, 16, "28a7023f452e8f82bd4bf28d8c37c35c")]
+#[case(AES256_KEY, 40, "aaf3d8f1de5640c232f5b169b9c911e6")]
+#[case(AES256_KEY, 64, "e1992190549f6ed5696a2c056c315410")]
+fn sp800_38b(#[case] key: &str, #[case] len: usize, #[case] expected: &str) {
+    let message = hex(MESSAGE);
+    let expected = hex(expected);
+
+    let mut cmac = assert_ok!(Cmac::new(aes(key)));
+    assert_ok!(cmac.update(&message[..len]));
+    assert_eq!(assert_ok!(cmac.finalize()), expected);
+    assert_ok!(cmac.verify(&expected));
+}
+
+#[test]
+fn wrong_tag_fails() {
+    let message = hex(MESSAGE);
+    let mut cmac = assert_ok!(Cmac::new(aes(AES128_KEY)));
+    assert_ok!(cmac.update(&message));
+
+    let err = assert_err!(cmac.verify(&hex("51f0bebf7e3b9d92fc49741779363cff")));
+    assert_matches!(err, CipherError::AuthenticationFailed);
+}
@@ -0,0 +1,82 @@
+use aes::{AesCtr, CounterLayout, Iv, Key};
+use claims::assert_ok;
+use rstest::rstest;
+use std::str::FromStr;
+
+// NIST SP 800-38A F.5 initial counter block and plaintext
+const NIST_COUNTER: u128 = 0xf0f1_f2f3_f4f5_f6f7_f8f9_fafb_fcfd_feff;
+const NIST_PLAINTEXT: [u128; 4] = [
+    0x6bc1_bee2_2e40_9f96_e93d_7e11_7393_172a,
+    0xae2d_8a57_1e03_ac9c_9eb7_6fac_45af_8e51,
+    0x30c8_1c46_a35c_e411_e5fb_c119_1a0a_52ef,
+    0xf69f_2445_df4f_9b17_ad2b_417b_e66c_3710,
+];
+
+// NIST SP 800-38A F.5.1 (CTR-AES128), F.5.3 (CTR-AES192) and F.5.5 (CTR-AES256)
+#[rstest]
+#[case(
+    "0x2b7e151628aed2a6abf7158809cf4f3c",
+    [
+        0x874d_6191_b620_e326_1bef_6864_990d_b6ce,
+        0x9806_f66b_7970_fdff_8617_187b_b9ff_fdff,
+        0x5ae4_df3e_dbd5_d35e_5b4f_0902_0db0_3eab,
+        0x1e03_1dda_2fbe_03d1_7921_70a0_f300_9cee,
+    ]
+)]
+#[case(
+    "0x8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    [
+        0x1abc_9324_1752_1ca2_4f2b_0459_fe7e_6e0b,
+        0x0903_39ec_0aa6_faef_d5cc_c2c6_f4ce_8e94,
+        0x1e36_b26b_d1eb_c670_d1bd_1d66_5620_abf7,
+        0x4f78_a7f6_d298_0958_5a97_daec_58c6_b050,
+    ]
+)]
+#[case(
+    "0x603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    [
+        0x601e_c313_7757_89a5_b7a7_f504_bbf3_d228,
+        0xf443_e3ca_4d62_b59a_ca84_e990_caca_f5c5,
+        0x2b09_30da_a23d_e94c_e870_17ba_2d84_988d,
+        0xdfc9_c58d_b67a_ada6_13c2_dd08_4579_41a6,
+    ]
+)]
+fn nist_encrypt_decrypt(
+    #[case] key: &str,
+    #[case] expected: [u128; 4],
+    #[values(CounterLayout::Nonce96Counter32, CounterLayout::Counter128)] layout: CounterLayout,
+) {
+    let key = assert_ok!(Key::from_str(key));
+    let cipher = AesCtr::with_layout(key, Iv::new(NIST_COUNTER), layout);
+    let plaintext = NIST_PLAINTEXT.map(u128::to_be_bytes).concat();
+    let expected = expected.map(u128::to_be_bytes).concat();
+
+    let ciphertext = assert_ok!(cipher.encrypt(&plaintext));
+    assert_eq!(&ciphertext[..16], &NIST_COUNTER.to_be_bytes());
+    assert_eq!(&ciphertext[16..], &expected);
+
+    let decrypted = assert_ok!(cipher.decrypt(&ciphertext));
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn nist_random_access() {
+    let key = assert_ok!(Key::from_str("0x2b7e151628aed2a6abf7158809cf4f3c"));
+    let cipher = AesCtr::new(key, Iv::new(NIST_COUNTER));
+
+    // Decrypt only the third block of F.5.1
+    let mut block = 0x5ae4_df3e_dbd5_d35e_5b4f_0902_0db0_3eab_u128.to_be_bytes();
+    assert_ok!(cipher.apply_keystream_at(2, &mut block));
+    assert_eq!(block, NIST_PLAINTEXT[2].to_be_bytes());
+}
+
+#[test]
+fn partial_final_block() {
+    let cipher = AesCtr::new(0x2b7e_1516_28ae_d2a6_abf7_1588_09cf_4f3c_u128, NIST_COUNTER);
+    let plaintext = NIST_PLAINTEXT.map(u128::to_be_bytes).concat();
+
+    // CTR needs no padding: a truncated message encrypts to a truncated ciphertext
+    let full = assert_ok!(cipher.encrypt(&plaintext));
+    let partial = assert_ok!(cipher.encrypt(&plaintext[..37]));
+    assert_eq!(partial, full[..16 + 37]);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use aes::{AesKw, Key};
+use cipher_core::CipherError;
+use claims::{assert_err, assert_matches, assert_ok};
+use common::hex;
+use rstest::rstest;
+
+fn kw(kek: &str) -> AesKw {
+    AesKw::new(assert_ok!(Key::from_slice(&hex(kek))))
+}
+
+// RFC 3394 section 4, test vectors 4.1-4.6
+#[rstest]
+#[case(
+    "000102030405060708090A0B0C0D0E0F",
+    "00112233445566778899AABBCCDDEEFF",
+    "1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5"
+)]
+#[case(
+    "000102030405060708090A0B0C0D0E0F1011121314151617",
+    "00112233445566778899AABBCCDDEEFF",
+    "96778B25AE6CA435F92B5B97C050AED2468AB8A17AD84E5D"
+)]
+#[case(
+    "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F",
+    "00112233445566778899AABBCCDDEEFF",
+    "64E8C3F9CE0F5BA263E9777905818A2A93C8191E7D6E8AE7"
+)]
+#[case(
+    "000102030405060708090A0B0C0D0E0F1011121314151617",
+    "00112233445566778899AABBCCDDEEFF0001020304050607",
+    "031D33264E15D33268F24EC260743EDCE1C6C7DDEE725A936BA814915C6762D2"
+)]
+#[case(
+    "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F",
+    "00112233445566778899AABBCCDDEEFF0001020304050607",
+    "A8F9BC1612C68B3FF6E6F4FBE30E71E4769C8B80A32CB8958CD5D17D6B254DA1"
+)]
+#[case(
+    "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F",
+    "00112233445566778899AABBCCDDEEFF000102030405060708090A0B0C0D0E0F",
+    "28C9F404C4B810F4CBCCB35CFB87F8263F5786E2D80ED326CBC7F0E71A99F43BFB988B9B7A02DD21"
+)]
+fn rfc3394(#[case] kek: &str, #[case] key_data: &str, #[case] expected: &str) {
+    let kw = kw(kek);
+    let (key_data, expected) = (hex(key_data), hex(expected));
+
+    let wrapped = assert_ok!(kw.wrap(&key_data));
+    assert_eq!(wrapped, expected);
+    assert_eq!(assert_ok!(kw.unwrap(&wrapped)), key_data);
+}
+
+// RFC 5649 section 6: a 20-byte key and a 7-byte key, which takes the
+// single-block path
+#[rstest]
+#[case(
+    "c37b7e6492584340bed12207808941155068f738",
+    "138bdeaa9b8fa7fc61f97742e72248ee5ae6ae5360d1ae6a5f54f373fa543b6a"
+)]
+#[case("466f7250617369", "afbeb0f07dfbf5419200f2ccb50bb24f")]
+fn rfc5649(#[case] key_data: &str, #[case] expected: &str) {
+    let kw = kw("5840df6e29b02af1ab493b705bf16ea1ae8338f4dcc176a8");
+    let (key_data, expected) = (hex(key_data), hex(expected));
+
+    let wrapped = assert_ok!(kw.wrap_padded(&key_data));
+    assert_eq!(wrapped, expected);
+    assert_eq!(assert_ok!(kw.unwrap_padded(&wrapped)), key_data);
+}
+
+#[test]
+fn wrong_kek_fails() {
+    let wrapped = hex("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let err = assert_err!(kw("0F0E0D0C0B0A09080706050403020100").unwrap(&wrapped));
+    assert_matches!(err, CipherError::AuthenticationFailed);
+}
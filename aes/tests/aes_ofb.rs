@@ -0,0 +1,76 @@
+use aes::{Aes, Key};
+use cipher_core::{CipherMode, Ofb};
+use claims::assert_ok;
+use rstest::rstest;
+use std::str::FromStr;
+
+// NIST SP 800-38A F.4 IV and plaintext
+const NIST_IV: u128 = 0x0001_0203_0405_0607_0809_0a0b_0c0d_0e0f;
+const NIST_PLAINTEXT: [u128; 4] = [
+    0x6bc1_bee2_2e40_9f96_e93d_7e11_7393_172a,
+    0xae2d_8a57_1e03_ac9c_9eb7_6fac_45af_8e51,
+    0x30c8_1c46_a35c_e411_e5fb_c119_1a0a_52ef,
+    0xf69f_2445_df4f_9b17_ad2b_417b_e66c_3710,
+];
+
+// NIST SP 800-38A F.4.1 (OFB-AES128), F.4.3 (OFB-AES192) and F.4.5 (OFB-AES256)
+#[rstest]
+#[case(
+    "0x2b7e151628aed2a6abf7158809cf4f3c",
+    [
+        0x3b3f_d92e_b72d_ad20_3334_49f8_e83c_fb4a,
+        0x7789_508d_1691_8f03_f53c_52da_c54e_d825,
+        0x9740_051e_9c5f_ecf6_4344_f7a8_2260_edcc,
+        0x304c_6528_f659_c778_66a5_10d9_c1d6_ae5e,
+    ]
+)]
+#[case(
+    "0x8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    [
+        0xcdc8_0d6f_ddf1_8cab_34c2_5909_c99a_4174,
+        0xfcc2_8b8d_4c63_837c_09e8_1700_c110_0401,
+        0x8d9a_9aea_c0f6_596f_559c_6d4d_af59_a5f2,
+        0x6d9f_2008_57ca_6c3e_9cac_524b_d9ac_c92a,
+    ]
+)]
+#[case(
+    "0x603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    [
+        0xdc7e_84bf_da79_164b_7ecd_8486_985d_3860,
+        0x4feb_dc67_40d2_0b3a_c88f_6ad8_2a4f_b08d,
+        0x71ab_47a0_86e8_6eed_f39d_1c5b_ba97_c408,
+        0x0126_141d_67f3_7be8_538f_5a8b_e740_e484,
+    ]
+)]
+fn nist_encrypt_decrypt(#[case] key: &str, #[case] expected: [u128; 4]) {
+    let key = assert_ok!(Key::from_str(key));
+    let ofb = assert_ok!(Ofb::new(Aes::from_key(key), &NIST_IV.to_be_bytes()));
+    let plaintext = NIST_PLAINTEXT.map(u128::to_be_bytes).concat();
+    let expected = expected.map(u128::to_be_bytes).concat();
+
+    let ciphertext = assert_ok!(ofb.encrypt(&plaintext));
+    assert_eq!(&ciphertext[..16], &NIST_IV.to_be_bytes());
+    assert_eq!(&ciphertext[16..], &expected);
+
+    let decrypted = assert_ok!(ofb.decrypt(&ciphertext));
+    assert_eq!(decrypted, plaintext);
+}
+
+#[rstest]
+#[case(0)]
+#[case(1)]
+#[case(37)]
+#[case(64)]
+fn partial_final_block(#[case] len: usize) {
+    let ofb = assert_ok!(Ofb::new(
+        Aes::new(0x2b7e_1516_28ae_d2a6_abf7_1588_09cf_4f3c_u128),
+        &NIST_IV.to_be_bytes()
+    ));
+    let plaintext = NIST_PLAINTEXT.map(u128::to_be_bytes).concat();
+
+    // OFB needs no padding: a truncated message encrypts to a truncated ciphertext
+    let full = assert_ok!(ofb.encrypt(&plaintext));
+    let partial = assert_ok!(ofb.encrypt(&plaintext[..len]));
+    assert_eq!(partial, full[..16 + len]);
+    assert_eq!(assert_ok!(ofb.decrypt(&partial)), plaintext[..len]);
+}
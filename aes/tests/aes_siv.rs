@@ -0,0 +1,67 @@
+mod common;
+
+use aes::{AesSiv, SIV_TAG_SIZE};
+use cipher_core::CipherError;
+use claims::{assert_err, assert_matches, assert_ok};
+use common::hex;
+
+// RFC 5297 Appendix A.1: deterministic authenticated encryption
+#[test]
+fn rfc5297_deterministic() {
+    let siv = assert_ok!(AesSiv::from_slice(&hex(
+        "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff"
+    )));
+    let ad = hex("101112131415161718191a1b1c1d1e1f2021222324252627");
+    let plaintext = hex("112233445566778899aabbccddee");
+    let expected = hex("85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c");
+
+    let output = assert_ok!(siv.encrypt(&[&ad], &plaintext));
+    assert_eq!(output, expected);
+    assert_eq!(assert_ok!(siv.decrypt(&[&ad], &output)), plaintext);
+}
+
+// RFC 5297 Appendix A.2: nonce-based authenticated encryption with two
+// associated data components followed by the nonce
+#[test]
+fn rfc5297_nonce_based() {
+    let siv = assert_ok!(AesSiv::from_slice(&hex(
+        "7f7e7d7c7b7a79787776757473727170404142434445464748494a4b4c4d4e4f"
+    )));
+    let ad1 =
+        hex("00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100");
+    let ad2 = hex("102030405060708090a0");
+    let nonce = hex("09f911029d74e35bd84156c5635688c0");
+    let plaintext = hex(
+        "7468697320697320736f6d6520706c61696e7465787420746f20656e6372797074207573696e67205349562d414553",
+    );
+    let expected = hex(concat!(
+        "7bdb6e3b432667eb06f4d14bff2fbd0f",
+        "cb900f2fddbe404326601965c889bf17dba77ceb094fa663b7a3f748ba8af829ea64ad544a272e9c485b62a3fd5c0d",
+    ));
+    let components: [&[u8]; 3] = [&ad1, &ad2, &nonce];
+
+    let output = assert_ok!(siv.encrypt(&components, &plaintext));
+    assert_eq!(output, expected);
+    assert_eq!(assert_ok!(siv.decrypt(&components, &output)), plaintext);
+
+    let mut tampered = output;
+    tampered[SIV_TAG_SIZE - 1] ^= 1;
+    let err = assert_err!(siv.decrypt(&components, &tampered));
+    assert_matches!(err, CipherError::AuthenticationFailed);
+}
+
+// AES-SIV-CMAC-384 and -512, cross-checked against OpenSSL
+#[test]
+fn longer_keys() {
+    let plaintext = b"Hello, World!";
+
+    let key: Vec<u8> = (0..48).collect();
+    let siv = assert_ok!(AesSiv::from_slice(&key));
+    let expected = hex("039467c13486db7b34284a9d99b0fb03535eec8b87969634f8511ea862");
+    assert_eq!(assert_ok!(siv.encrypt(&[b"header"], plaintext)), expected);
+
+    let key: Vec<u8> = (0..64).collect();
+    let siv = assert_ok!(AesSiv::from_slice(&key));
+    let expected = hex("408e23149dc7cbcb592f67896fef1335bd0635cc119e23327a7cdf36ee");
+    assert_eq!(assert_ok!(siv.encrypt(&[b"header"], plaintext)), expected);
+}
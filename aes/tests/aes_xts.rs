@@ -0,0 +1,155 @@
+mod common;
+
+use aes::AesXts;
+use claims::assert_ok;
+use common::hex;
+use rstest::rstest;
+
+/// The 512-byte plaintext of vectors 4-14: bytes 00..ff, twice.
+fn sequence(len: usize) -> Vec<u8> {
+    (0..=u8::MAX).cycle().take(len).collect()
+}
+
+const VECTOR_4_KEY: &str = concat!(
+    "27182818284590452353602874713526",
+    "31415926535897932384626433832795"
+);
+const VECTOR_4_CIPHERTEXT: &str = concat!(
+    "27a7479befa1d476489f308cd4cfa6e2a96e4bbe3208ff25287dd3819616e89c",
+    "c78cf7f5e543445f8333d8fa7f56000005279fa5d8b5e4ad40e736ddb4d35412",
+    "328063fd2aab53e5ea1e0a9f332500a5df9487d07a5c92cc512c8866c7e860ce",
+    "93fdf166a24912b422976146ae20ce846bb7dc9ba94a767aaef20c0d61ad0265",
+    "5ea92dc4c4e41a8952c651d33174be51a10c421110e6d81588ede82103a252d8",
+    "a750e8768defffed9122810aaeb99f9172af82b604dc4b8e51bcb08235a6f434",
+    "1332e4ca60482a4ba1a03b3e65008fc5da76b70bf1690db4eae29c5f1badd03c",
+    "5ccf2a55d705ddcd86d449511ceb7ec30bf12b1fa35b913f9f747a8afd1b130e",
+    "94bff94effd01a91735ca1726acd0b197c4e5b03393697e126826fb6bbde8ecc",
+    "1e08298516e2c9ed03ff3c1b7860f6de76d4cecd94c8119855ef5297ca67e9f3",
+    "e7ff72b1e99785ca0a7e7720c5b36dc6d72cac9574c8cbbc2f801e23e56fd344",
+    "b07f22154beba0f08ce8891e643ed995c94d9a69c9f1b5f499027a78572aeebd",
+    "74d20cc39881c213ee770b1010e4bea718846977ae119f7a023ab58cca0ad752",
+    "afe656bb3c17256a9f6e9bf19fdd5a38fc82bbe872c5539edb609ef4f79c203e",
+    "bb140f2e583cb2ad15b4aa5b655016a8449277dbd477ef2c8d6c017db738b18d",
+    "eb4a427d1923ce3ff262735779a418f20a282df920147beabe421ee5319d0568"
+);
+
+// IEEE 1619-2007 Annex B, vectors 1-3: 32-byte data units
+#[rstest]
+#[case(
+    concat!("00000000000000000000000000000000", "00000000000000000000000000000000"),
+    0,
+    "0000000000000000000000000000000000000000000000000000000000000000",
+    "917cf69ebd68b2ec9b9fe9a3eadda692cd43d2f59598ed858c02c2652fbf922e"
+)]
+#[case(
+    concat!("11111111111111111111111111111111", "22222222222222222222222222222222"),
+    0x33_3333_3333,
+    "4444444444444444444444444444444444444444444444444444444444444444",
+    "c454185e6a16936e39334038acef838bfb186fff7480adc4289382ecd6d394f0"
+)]
+#[case(
+    concat!("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0", "22222222222222222222222222222222"),
+    0x33_3333_3333,
+    "4444444444444444444444444444444444444444444444444444444444444444",
+    "af85336b597afc1a900b2eb21ec949d292df4c047e0b21532186a5971a227a89"
+)]
+// Vectors 15-18: partial final block (ciphertext stealing)
+#[case(
+    concat!("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0", "bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0"),
+    0x12_3456_789a,
+    "000102030405060708090a0b0c0d0e0f10",
+    "6c1625db4671522d3d7599601de7ca09ed"
+)]
+#[case(
+    concat!("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0", "bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0"),
+    0x12_3456_789a,
+    "000102030405060708090a0b0c0d0e0f1011",
+    "d069444b7a7e0cab09e24447d24deb1fedbf"
+)]
+#[case(
+    concat!("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0", "bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0"),
+    0x12_3456_789a,
+    "000102030405060708090a0b0c0d0e0f101112",
+    "e5df1351c0544ba1350b3363cd8ef4beedbf9d"
+)]
+#[case(
+    concat!("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0", "bfbebdbcbbbab9b8b7b6b5b4b3b2b1b0"),
+    0x12_3456_789a,
+    "000102030405060708090a0b0c0d0e0f10111213",
+    "9d84c813f719aa2c7be3f66171c7c5c2edbf9dac"
+)]
+fn ieee_1619_vectors(
+    #[case] key: &str,
+    #[case] sector: u128,
+    #[case] plaintext: &str,
+    #[case] ciphertext: &str,
+) {
+    let xts = assert_ok!(AesXts::from_slice(&hex(key)));
+    let plaintext = hex(plaintext);
+
+    let mut data = plaintext.clone();
+    assert_ok!(xts.encrypt_sector(sector, &mut data));
+    assert_eq!(data, hex(ciphertext));
+
+    assert_ok!(xts.decrypt_sector(sector, &mut data));
+    assert_eq!(data, plaintext);
+}
+
+// Vector 4: a full 512-byte sector
+#[test]
+fn ieee_1619_vector_4() {
+    let xts = assert_ok!(AesXts::from_slice(&hex(VECTOR_4_KEY)));
+    let mut data = sequence(512);
+    assert_ok!(xts.encrypt_sector(0, &mut data));
+    assert_eq!(data, hex(VECTOR_4_CIPHERTEXT));
+}
+
+// Vector 5 reuses the key of vector 4 with data-unit 1, and its plaintext is
+// the ciphertext of vector 4; vector 10 uses XTS-AES-256. Only the first two
+// blocks are checked: without stealing, every block depends only on its tweak.
+#[rstest]
+#[case(
+    VECTOR_4_KEY,
+    1,
+    &VECTOR_4_CIPHERTEXT[..64],
+    "264d3ca8512194fec312c8c9891f279fefdd608d0c027b60483a3fa811d65ee5"
+)]
+#[case(
+    concat!(
+        "2718281828459045235360287471352662497757247093699959574966967627",
+        "3141592653589793238462643383279502884197169399375105820974944592"
+    ),
+    0xff,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "1c3b3a102f770386e4836c99e370cf9bea00803f5e482357a4ae12d414a3e63b"
+)]
+fn ieee_1619_sector_prefix(
+    #[case] key: &str,
+    #[case] sector: u128,
+    #[case] plaintext: &str,
+    #[case] ciphertext: &str,
+) {
+    let xts = assert_ok!(AesXts::from_slice(&hex(key)));
+    let mut data = hex(plaintext);
+    assert_ok!(xts.encrypt_sector(sector, &mut data));
+    assert_eq!(data, hex(ciphertext));
+}
+
+#[test]
+fn image_sectors_match_individual_sectors() {
+    let xts = assert_ok!(AesXts::from_slice(&hex(VECTOR_4_KEY)));
+
+    // Two 512-byte sectors followed by a short 100-byte final sector
+    let image = sequence(512 * 2 + 100);
+    let mut encrypted = image.clone();
+    assert_ok!(xts.encrypt_sectors(10, 512, &mut encrypted));
+
+    for (idx, (chunk, expected)) in (10..).zip(image.chunks(512).zip(encrypted.chunks(512))) {
+        let mut sector = chunk.to_vec();
+        assert_ok!(xts.encrypt_sector(idx, &mut sector));
+        assert_eq!(sector, expected);
+    }
+
+    assert_ok!(xts.decrypt_sectors(10, 512, &mut encrypted));
+    assert_eq!(encrypted, image);
+}
@@ -0,0 +1,432 @@
+//! NIST CAVP AESAVS response file runner.
+//!
+//! Runs every `.rsp` file in `tests/cavp/` through [`Aes`] (ECB) and
+//! [`AesCbc`] (CBC): the `GFSbox`, `KeySbox`, `VarKey` and `VarTxt` known-answer
+//! suites, the multi-block message tests (MMT) and the Monte Carlo tests
+//! (MCT). The suite and mode come from the file name, as in
+//! `ECBGFSbox128.rsp` or `CBCMCT256.rsp`; see `tests/cavp/README.md` for where
+//! to get the files.
+
+mod common;
+
+use aes::{Aes, AesCbc, Iv, Key};
+use cipher_core::{BlockCipher, Cbc, CipherMode, NoPadding};
+use common::hex;
+use std::{collections::HashMap, fs, path::Path};
+
+/// Inner loop length of an AESAVS Monte Carlo test.
+const MCT_ITERATIONS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Ecb,
+    Cbc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Suite {
+    /// `GFSbox`, `KeySbox`, `VarKey`, `VarTxt` and MMT: one cipher call per record.
+    KnownAnswer,
+    /// MCT: each record runs the 1000-iteration inner loop.
+    MonteCarlo,
+}
+
+/// One `COUNT = ...` record of a response file.
+#[derive(Debug)]
+struct Record {
+    direction: Direction,
+    count: usize,
+    fields: HashMap<String, Vec<u8>>,
+}
+
+impl Record {
+    fn field(&self, name: &str) -> &[u8] {
+        self.fields
+            .get(name)
+            .unwrap_or_else(|| panic!("COUNT = {} has no {name}", self.count))
+    }
+}
+
+/// Parses the records of a `.rsp` file, skipping comments and blank lines.
+///
+/// `[ENCRYPT]` and `[DECRYPT]` headers set the direction of the records that
+/// follow; every `COUNT` line starts a new record.
+fn parse_rsp(text: &str) -> Vec<Record> {
+    let mut records = Vec::new();
+    let mut direction = Direction::Encrypt;
+
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line {
+            "[ENCRYPT]" => direction = Direction::Encrypt,
+            "[DECRYPT]" => direction = Direction::Decrypt,
+            _ => {
+                let (name, value) = line
+                    .split_once('=')
+                    .unwrap_or_else(|| panic!("malformed line: {line}"));
+                let (name, value) = (name.trim(), value.trim());
+                if name == "COUNT" {
+                    records.push(Record {
+                        direction,
+                        count: value.parse().expect("numeric COUNT"),
+                        fields: HashMap::new(),
+                    });
+                } else {
+                    let record = records
+                        .last_mut()
+                        .unwrap_or_else(|| panic!("{name} before the first COUNT"));
+                    record.fields.insert(name.to_owned(), hex(value));
+                }
+            }
+        }
+    }
+    records
+}
+
+/// Returns the mode and suite of a response file from its name.
+fn classify(file_name: &str) -> Option<(Mode, Suite)> {
+    let mode = if file_name.starts_with("ECB") {
+        Mode::Ecb
+    } else if file_name.starts_with("CBC") {
+        Mode::Cbc
+    } else {
+        return None;
+    };
+    let suite = if file_name.contains("MCT") {
+        Suite::MonteCarlo
+    } else {
+        Suite::KnownAnswer
+    };
+    Some((mode, suite))
+}
+
+fn cipher(key: &[u8]) -> Aes {
+    Aes::new(Key::from_slice(key).expect("AES key length"))
+}
+
+fn block(bytes: &[u8]) -> [u8; 16] {
+    bytes.try_into().expect("16-byte block")
+}
+
+fn ecb(key: &[u8], input: &[u8], direction: Direction) -> Vec<u8> {
+    let cipher = cipher(key);
+    input
+        .chunks_exact(16)
+        .flat_map(|chunk| {
+            let output = match direction {
+                Direction::Encrypt => cipher.encrypt(chunk),
+                Direction::Decrypt => cipher.decrypt(chunk),
+            };
+            output.expect("one block").to_vec()
+        })
+        .collect()
+}
+
+fn cbc(key: &[u8], iv: &[u8], input: &[u8], direction: Direction) -> Vec<u8> {
+    match direction {
+        Direction::Encrypt => {
+            // AesCbc always pads: the vector is the ciphertext between the
+            // IV prefix and the padding block
+            let cipher = AesCbc::new(
+                Key::from_slice(key).expect("AES key length"),
+                Iv::from(block(iv)),
+            );
+            let output = cipher.encrypt(input).expect("CBC encryption");
+            output[16..16 + input.len()].to_vec()
+        }
+        Direction::Decrypt => {
+            let cipher = Cbc::<Aes, NoPadding>::new(cipher(key), iv).expect("16-byte IV");
+            let data = [iv, input].concat();
+            cipher.decrypt(&data).expect("CBC decryption")
+        }
+    }
+}
+
+/// Runs the AESAVS Monte Carlo inner loop, returning the last output block.
+///
+/// In ECB mode each output block is the next input. In CBC mode the next
+/// input is the previous output block, or the IV after the first step, so the
+/// loop feeds back outputs from two steps earlier as AESAVS specifies.
+fn monte_carlo(
+    mode: Mode,
+    key: &[u8],
+    iv: &[u8],
+    input: &[u8],
+    direction: Direction,
+    iterations: usize,
+) -> [u8; 16] {
+    let cipher = cipher(key);
+    let transform = |data: &[u8; 16]| {
+        let output = match direction {
+            Direction::Encrypt => cipher.encrypt(data),
+            Direction::Decrypt => cipher.decrypt(data),
+        };
+        block(&output.expect("one block"))
+    };
+    let xor = |a: [u8; 16], b: [u8; 16]| std::array::from_fn(|i| a[i] ^ b[i]);
+
+    let mut input = block(input);
+    let mut output = [0; 16];
+    match mode {
+        Mode::Ecb => {
+            for _ in 0..iterations {
+                output = transform(&input);
+                input = output;
+            }
+        }
+        Mode::Cbc => {
+            // `chain` is the previous ciphertext block and `previous` the
+            // previous output block; both start as the IV
+            let mut chain = block(iv);
+            let mut previous = chain;
+            for _ in 0..iterations {
+                output = match direction {
+                    Direction::Encrypt => {
+                        chain = transform(&xor(input, chain));
+                        chain
+                    }
+                    Direction::Decrypt => {
+                        let plaintext = xor(transform(&input), chain);
+                        chain = input;
+                        plaintext
+                    }
+                };
+                input = previous;
+                previous = output;
+            }
+        }
+    }
+    output
+}
+
+/// Checks one record, returning a description of the mismatch on failure.
+fn run_record(mode: Mode, suite: Suite, record: &Record) -> Result<(), String> {
+    let key = record.field("KEY");
+    let (input, expected) = match record.direction {
+        Direction::Encrypt => (record.field("PLAINTEXT"), record.field("CIPHERTEXT")),
+        Direction::Decrypt => (record.field("CIPHERTEXT"), record.field("PLAINTEXT")),
+    };
+    let iv = match mode {
+        Mode::Ecb => &[][..],
+        Mode::Cbc => record.field("IV"),
+    };
+
+    let output = match (suite, mode) {
+        (Suite::KnownAnswer, Mode::Ecb) => ecb(key, input, record.direction),
+        (Suite::KnownAnswer, Mode::Cbc) => cbc(key, iv, input, record.direction),
+        (Suite::MonteCarlo, _) => {
+            monte_carlo(mode, key, iv, input, record.direction, MCT_ITERATIONS).to_vec()
+        }
+    };
+    if output == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "{:?} COUNT = {}: expected {expected:02x?}, got {output:02x?}",
+            record.direction, record.count
+        ))
+    }
+}
+
+#[test]
+#[ignore = "needs the NIST AESAVS .rsp files in tests/cavp, see its README.md"]
+fn aesavs_response_files() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cavp");
+    let mut files = fs::read_dir(&dir)
+        .expect("tests/cavp exists")
+        .map(|entry| entry.expect("readable entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rsp"))
+        .collect::<Vec<_>>();
+    files.sort();
+
+    assert!(
+        !files.is_empty(),
+        "no AESAVS .rsp files in {}, see its README.md",
+        dir.display()
+    );
+
+    let mut failures = Vec::new();
+    for path in &files {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("UTF-8 name");
+        let Some((mode, suite)) = classify(name) else {
+            continue;
+        };
+        let text = fs::read_to_string(path).expect("readable response file");
+        let records = parse_rsp(&text);
+        assert!(!records.is_empty(), "{name} has no records");
+        failures.extend(
+            records
+                .iter()
+                .filter_map(|record| run_record(mode, suite, record).err())
+                .map(|failure| format!("{name}: {failure}")),
+        );
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+// FIPS-197 Appendix C.1 and C.3 and SP 800-38A F.1.1, F.2.1 and F.2.2 in
+// response file layout
+const SAMPLE_ECB: &str = "
+# CAVS 11.1
+# AESAVS sample
+
+[ENCRYPT]
+
+COUNT = 0
+KEY = 000102030405060708090a0b0c0d0e0f
+PLAINTEXT = 00112233445566778899aabbccddeeff
+CIPHERTEXT = 69c4e0d86a7b0430d8cdb78070b4c55a
+
+COUNT = 1
+KEY = 000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f
+PLAINTEXT = 00112233445566778899aabbccddeeff
+CIPHERTEXT = 8ea2b7ca516745bfeafc49904b496089
+
+COUNT = 2
+KEY = 2b7e151628aed2a6abf7158809cf4f3c
+PLAINTEXT = 6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51
+CIPHERTEXT = 3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf
+
+[DECRYPT]
+
+COUNT = 0
+KEY = 000102030405060708090a0b0c0d0e0f
+CIPHERTEXT = 69c4e0d86a7b0430d8cdb78070b4c55a
+PLAINTEXT = 00112233445566778899aabbccddeeff
+";
+
+const SAMPLE_CBC: &str = "
+[ENCRYPT]
+
+COUNT = 0
+KEY = 2b7e151628aed2a6abf7158809cf4f3c
+IV = 000102030405060708090a0b0c0d0e0f
+PLAINTEXT = 6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51
+CIPHERTEXT = 7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2
+
+[DECRYPT]
+
+COUNT = 0
+KEY = 2b7e151628aed2a6abf7158809cf4f3c
+IV = 000102030405060708090a0b0c0d0e0f
+CIPHERTEXT = 7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2
+PLAINTEXT = 6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51
+";
+
+#[test]
+fn parses_records_and_directions() {
+    let records = parse_rsp(SAMPLE_ECB);
+    assert_eq!(records.len(), 4);
+    assert_eq!(
+        records
+            .iter()
+            .map(|record| (record.direction, record.count))
+            .collect::<Vec<_>>(),
+        [
+            (Direction::Encrypt, 0),
+            (Direction::Encrypt, 1),
+            (Direction::Encrypt, 2),
+            (Direction::Decrypt, 0),
+        ]
+    );
+    assert_eq!(records[1].field("KEY").len(), 32);
+    assert_eq!(records[2].field("PLAINTEXT").len(), 32);
+}
+
+#[test]
+fn classifies_file_names() {
+    assert_eq!(
+        classify("ECBGFSbox128.rsp"),
+        Some((Mode::Ecb, Suite::KnownAnswer))
+    );
+    assert_eq!(
+        classify("ECBVarTxt256.rsp"),
+        Some((Mode::Ecb, Suite::KnownAnswer))
+    );
+    assert_eq!(
+        classify("CBCMMT192.rsp"),
+        Some((Mode::Cbc, Suite::KnownAnswer))
+    );
+    assert_eq!(
+        classify("CBCMCT128.rsp"),
+        Some((Mode::Cbc, Suite::MonteCarlo))
+    );
+    assert_eq!(classify("OFBVarKey128.rsp"), None);
+}
+
+#[test]
+fn runs_sample_known_answer_records() {
+    for (records, mode) in [(SAMPLE_ECB, Mode::Ecb), (SAMPLE_CBC, Mode::Cbc)] {
+        for record in parse_rsp(records) {
+            assert_eq!(run_record(mode, Suite::KnownAnswer, &record), Ok(()));
+        }
+    }
+}
+
+#[test]
+fn reports_mismatches() {
+    let mut records = parse_rsp(SAMPLE_ECB);
+    records[0]
+        .fields
+        .insert("CIPHERTEXT".to_owned(), vec![0; 16]);
+    assert!(run_record(Mode::Ecb, Suite::KnownAnswer, &records[0]).is_err());
+}
+
+#[test]
+fn cbc_monte_carlo_chains_like_cbc() {
+    // The first two steps encrypt PT, IV as one CBC message, and decrypting
+    // that message starts the decryption loop the same way
+    let key = hex("2b7e151628aed2a6abf7158809cf4f3c");
+    let iv = hex("000102030405060708090a0b0c0d0e0f");
+    let plaintext = hex("6bc1bee22e409f96e93d7e117393172a");
+    let message = [plaintext.as_slice(), &iv].concat();
+    let ciphertext = cbc(&key, &iv, &message, Direction::Encrypt);
+
+    for (iterations, expected) in [(1, &ciphertext[..16]), (2, &ciphertext[16..])] {
+        let output = monte_carlo(
+            Mode::Cbc,
+            &key,
+            &iv,
+            &plaintext,
+            Direction::Encrypt,
+            iterations,
+        );
+        assert_eq!(output, expected);
+    }
+
+    let decrypted = monte_carlo(
+        Mode::Cbc,
+        &key,
+        &iv,
+        &ciphertext[..16],
+        Direction::Decrypt,
+        1,
+    );
+    assert_eq!(decrypted.as_slice(), plaintext);
+}
+
+#[test]
+fn monte_carlo_inverts() {
+    let key = hex("000102030405060708090a0b0c0d0e0f1011121314151617");
+    let iv = hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");
+    let plaintext = hex("00112233445566778899aabbccddeeff");
+    for mode in [Mode::Ecb, Mode::Cbc] {
+        let ciphertext = monte_carlo(mode, &key, &iv, &plaintext, Direction::Encrypt, 1);
+        let decrypted = monte_carlo(mode, &key, &iv, &ciphertext, Direction::Decrypt, 1);
+        assert_eq!(decrypted.as_slice(), plaintext, "{mode:?}");
+    }
+    let twice = monte_carlo(Mode::Ecb, &key, &iv, &plaintext, Direction::Encrypt, 2);
+    let back = monte_carlo(Mode::Ecb, &key, &iv, &twice, Direction::Decrypt, 2);
+    assert_eq!(back.as_slice(), plaintext);
+}
@@ -0,0 +1,9 @@
+//! Helpers shared by the integration tests.
+
+/// Decodes a hex string, as written in the published test vectors.
+pub fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).expect("valid hex"))
+        .collect()
+}
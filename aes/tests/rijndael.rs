@@ -0,0 +1,69 @@
+mod common;
+
+use aes::Rijndael;
+use cipher_core::BlockCipher;
+use claims::assert_ok;
+use common::hex;
+use rstest::rstest;
+
+// Rijndael reference values for every block/key length combination.
+// Key and plaintext are truncated to the key and block length respectively.
+const KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfe";
+const PLAINTEXT: &str = "3243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c8";
+
+#[rstest]
+#[case(16, 20, "231d844639b31b412211cfe93712b880")]
+#[case(16, 24, "f9fb29aefc384a250340d833b87ebc00")]
+#[case(16, 28, "8faa8fe4dee9eb17caa4797502fc9d3f")]
+#[case(16, 32, "1a6e6c2c662e7da6501ffb62bc9e93f3")]
+#[case(20, 16, "16e73aec921314c29df905432bc8968ab64b1f51")]
+#[case(20, 20, "0553eb691670dd8a5a5b5addf1aa7450f7a0e587")]
+#[case(20, 24, "73cd6f3423036790463aa9e19cfcde894ea16623")]
+#[case(20, 28, "601b5dcd1cf4ece954c740445340bf0afdc048df")]
+#[case(20, 32, "579e930b36c1529aa3e86628bacfe146942882cf")]
+#[case(24, 16, "b24d275489e82bb8f7375e0d5fcdb1f481757c538b65148a")]
+#[case(24, 20, "738dae25620d3d3beff4a037a04290d73eb33521a63ea568")]
+#[case(24, 24, "725ae43b5f3161de806a7c93e0bca93c967ec1ae1b71e1cf")]
+#[case(24, 28, "bbfc14180afbf6a36382a061843f0b63e769acdc98769130")]
+#[case(24, 32, "0ebacf199e3315c2e34b24fcc7c46ef4388aa475d66c194c")]
+#[case(28, 16, "b0a8f78f6b3c66213f792ffd2a61631f79331407a5e5c8d3793aceb1")]
+#[case(28, 20, "08b99944edfce33a2acb131183ab0168446b2d15e958480010f545e3")]
+#[case(28, 24, "be4c597d8f7efe22a2f7e5b1938e2564d452a5bfe72399c7af1101e2")]
+#[case(28, 28, "ef529598ecbce297811b49bbed2c33bbe1241d6e1a833dbe119569e8")]
+#[case(28, 32, "02fafc200176ed05deb8edb82a3555b0b10d47a388dfd59cab2f6c11")]
+#[case(
+    32,
+    16,
+    "7d15479076b69a46ffb3b3beae97ad8313f622f67fedb487de9f06b9ed9c8f19"
+)]
+#[case(
+    32,
+    20,
+    "514f93fb296b5ad16aa7df8b577abcbd484decacccc7fb1f18dc567309ceeffd"
+)]
+#[case(
+    32,
+    24,
+    "5d7101727bb25781bf6715b0e6955282b9610e23a43c2eb062699f0ebf5887b2"
+)]
+#[case(
+    32,
+    28,
+    "d56c5a63627432579e1dd308b2c8f157b40a4bfb56fea1377b25d3ed3d6dbf80"
+)]
+#[case(
+    32,
+    32,
+    "a49406115dfb30a40418aafa4869b7c6a886ff31602a7dd19c889dc64f7e4e7a"
+)]
+fn known_answer(#[case] block_size: usize, #[case] key_len: usize, #[case] expected: &str) {
+    let key = hex(KEY);
+    let plaintext = hex(PLAINTEXT);
+    let cipher = assert_ok!(Rijndael::new(&key[..key_len], block_size));
+
+    let ciphertext = assert_ok!(cipher.encrypt(&plaintext[..block_size]));
+    assert_eq!(ciphertext.as_slice(), hex(expected));
+
+    let decrypted = assert_ok!(cipher.decrypt(&ciphertext));
+    assert_eq!(decrypted.as_slice(), &plaintext[..block_size]);
+}
@@ -0,0 +1,40 @@
+//! Constant-time helpers for comparing secret values.
+
+use std::hint::black_box;
+
+/// Compares two byte slices without short-circuiting on the first difference.
+///
+/// The running time depends only on the lengths, which are not secret, so
+/// authentication tags can be checked without leaking how many leading bytes
+/// matched.
+#[must_use]
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    black_box(diff) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_slices() {
+        assert!(ct_eq(b"tag value", b"tag value"));
+        assert!(ct_eq(&[], &[]));
+    }
+
+    #[test]
+    fn different_slices() {
+        assert!(!ct_eq(b"tag value", b"tag valuf"));
+        assert!(!ct_eq(b"\x00tag", b"\x01tag"));
+    }
+
+    #[test]
+    fn different_lengths() {
+        assert!(!ct_eq(b"tag", b"tag value"));
+    }
+}
@@ -11,6 +11,38 @@ pub enum CipherError {
     #[error("Invalid block size: expected {expected} bytes, got {actual}.")]
     InvalidBlockSize { expected: usize, actual: usize },
 
+    /// IV doesn't match the size required by the mode of operation
+    #[error("Invalid IV size: expected {expected} bytes, got {actual}.")]
+    InvalidIvSize { expected: usize, actual: usize },
+
+    /// Counter mode ran out of counter values; continuing would reuse keystream
+    #[error("Counter overflow: the {bits}-bit counter would wrap around.")]
+    CounterOverflow { bits: u32 },
+
+    /// Tag length is not one the AEAD mode supports
+    #[error("Invalid tag size: {actual} bytes is not supported by this mode.")]
+    InvalidTagSize { actual: usize },
+
+    /// More associated data components than the mode can authenticate
+    #[error("Too many associated data components: at most {max} are allowed, got {actual}.")]
+    TooManyComponents { max: usize, actual: usize },
+
+    /// Round count outside the range the cipher supports
+    #[error("Invalid number of rounds: expected {min} to {max}, got {actual}.")]
+    InvalidRounds {
+        min: usize,
+        max: usize,
+        actual: usize,
+    },
+
+    /// Authentication tag did not match; the ciphertext or associated data was altered
+    #[error("Authentication failed: the message or its associated data was tampered with.")]
+    AuthenticationFailed,
+
+    /// The algorithm does not support the requested operation
+    #[error("Unsupported operation: {0}")]
+    UnsupportedOperation(String),
+
     /// Invalid PKCS#7 padding
     #[error("Invalid padding: {0}")]
     InvalidPadding(String),
@@ -32,6 +64,36 @@ impl CipherError {
     pub const fn invalid_block_size(expected: usize, actual: usize) -> Self {
         Self::InvalidBlockSize { expected, actual }
     }
+
+    #[inline]
+    #[must_use]
+    pub const fn invalid_iv_size(expected: usize, actual: usize) -> Self {
+        Self::InvalidIvSize { expected, actual }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn invalid_tag_size(actual: usize) -> Self {
+        Self::InvalidTagSize { actual }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn too_many_components(max: usize, actual: usize) -> Self {
+        Self::TooManyComponents { max, actual }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn invalid_rounds(min: usize, max: usize, actual: usize) -> Self {
+        Self::InvalidRounds { min, max, actual }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn counter_overflow(bits: u32) -> Self {
+        Self::CounterOverflow { bits }
+    }
 }
 
 /// Type alias for clean Result types
@@ -1,18 +1,22 @@
+mod constant_time;
 mod error;
 mod macros;
+mod modes;
 mod padding;
 mod parsing;
 mod traits;
 mod types;
 
 pub use {
+    constant_time::ct_eq,
     error::{BlockError, CipherError, CipherResult},
-    padding::{pkcs7_pad, pkcs7_unpad},
-    parsing::{BlockInt, parse_block_int},
-    traits::{BlockCipher, BlockParser, InputBlock},
+    modes::{Cbc, CbcCts, Cfb, CfbSegment, CipherMode, Cmac, CtsVariant, Ecb, Ofb, dbl},
+    padding::{NoPadding, Padding, Pkcs7, pkcs7_pad, pkcs7_unpad},
+    parsing::{BlockInt, parse_block_bytes, parse_block_int},
+    traits::{Aead, BlockCipher, BlockParser, InputBlock, Mac},
     types::{CipherAction, Output},
 };
 
 pub mod prelude {
-    pub use super::{CipherAction, CipherResult, InputBlock, Output};
+    pub use super::{CipherAction, CipherMode, CipherResult, InputBlock, Output};
 }
@@ -0,0 +1,174 @@
+//! CBC (Cipher Block Chaining) mode.
+//!
+//! CBC mode combines each plaintext block with the previous ciphertext block
+//! (using XOR) before encryption. The first block uses an Initialization Vector (IV).
+
+use crate::{
+    BlockCipher, CipherError, CipherMode, CipherResult, Padding, Pkcs7, modes::xor_in_place,
+};
+use std::{fmt::Debug, marker::PhantomData};
+use zeroize::Zeroizing;
+
+/// A block cipher in CBC (Cipher Block Chaining) mode.
+///
+/// The IV is prepended to the ciphertext: `[IV][ciphertext...]`. On
+/// decryption the IV is read back from the input, so the IV stored in the
+/// mode is only used for encryption.
+///
+/// # Example
+///
+/// ```ignore
+/// use cipher_core::{Cbc, CipherMode};
+///
+/// let cbc = Cbc::<_>::new(Des::new(key), &iv)?;
+/// let ciphertext = cbc.encrypt(b"Hello, World!")?;
+/// let plaintext = cbc.decrypt(&ciphertext)?;
+/// ```
+pub struct Cbc<C, P = Pkcs7> {
+    cipher: C,
+    iv: Zeroizing<Vec<u8>>,
+    padding: PhantomData<P>,
+}
+
+impl<C: BlockCipher, P: Padding> Cbc<C, P> {
+    /// Wraps `cipher` in CBC mode with the given IV.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the IV is not exactly one block long.
+    pub fn new(cipher: C, iv: &[u8]) -> CipherResult<Self> {
+        let block_size = cipher.block_size();
+        if iv.len() != block_size {
+            return Err(CipherError::invalid_iv_size(block_size, iv.len()));
+        }
+
+        Ok(Self {
+            cipher,
+            iv: Zeroizing::new(iv.to_vec()),
+            padding: PhantomData,
+        })
+    }
+
+    /// Returns the underlying block cipher.
+    #[inline]
+    #[must_use]
+    pub const fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    /// Returns the IV used for encryption.
+    #[inline]
+    #[must_use]
+    pub fn iv(&self) -> &[u8] {
+        &self.iv
+    }
+}
+
+/// Omits the cipher, which holds key material.
+impl<C, P> Debug for Cbc<C, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cbc").finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher, P: Padding> CipherMode for Cbc<C, P> {
+    /// Encrypts plaintext, returning `[IV][ciphertext...]`.
+    fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        let padded = P::pad(plaintext, block_size)?;
+        let mut output = Vec::with_capacity(block_size + padded.len());
+
+        // Prepend IV to output
+        output.extend_from_slice(&self.iv);
+
+        for (idx, chunk) in padded.chunks_exact(block_size).enumerate() {
+            // The previous ciphertext block (or the IV) sits right before this block
+            let prev = &output[idx * block_size..(idx + 1) * block_size];
+            let mut block = chunk.to_vec();
+            xor_in_place(&mut block, prev);
+            let encrypted = self.cipher.encrypt(&block)?;
+            output.extend_from_slice(&encrypted);
+        }
+
+        Ok(output)
+    }
+
+    /// Decrypts `[IV][ciphertext...]` and removes the padding.
+    fn decrypt(&self, data: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        if data.len() < block_size || !data.len().is_multiple_of(block_size) {
+            return Err(CipherError::invalid_block_size(block_size, data.len()));
+        }
+
+        let mut plaintext = Vec::with_capacity(data.len() - block_size);
+        for (prev, chunk) in data
+            .chunks_exact(block_size)
+            .zip(data.chunks_exact(block_size).skip(1))
+        {
+            let mut block = self.cipher.decrypt(chunk)?.to_vec();
+            xor_in_place(&mut block, prev);
+            plaintext.extend_from_slice(&block);
+        }
+
+        Ok(P::unpad(&plaintext, block_size)?.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoPadding, modes::tests::ToyCipher};
+    use claims::{assert_err, assert_ok};
+
+    const KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+    const IV: [u8; 8] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xAB, 0xCD, 0xEF];
+
+    #[test]
+    fn roundtrip() {
+        let cbc = assert_ok!(Cbc::<_>::new(ToyCipher(KEY), &IV));
+        let plaintext = b"The quick brown fox jumps over the lazy dog";
+
+        let ciphertext = assert_ok!(cbc.encrypt(plaintext));
+        assert_eq!(&ciphertext[..8], IV);
+        assert_eq!(ciphertext.len(), 8 + 48);
+
+        let decrypted = assert_ok!(cbc.decrypt(&ciphertext));
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn identical_blocks_are_chained() {
+        let cbc = assert_ok!(Cbc::<_, NoPadding>::new(ToyCipher(KEY), &IV));
+        let ciphertext = assert_ok!(cbc.encrypt(&[0xAB; 16]));
+        assert_ne!(ciphertext[8..16], ciphertext[16..]);
+    }
+
+    #[test]
+    fn decrypt_uses_embedded_iv() {
+        let encryptor = assert_ok!(Cbc::<_>::new(ToyCipher(KEY), &IV));
+        let decryptor = assert_ok!(Cbc::<_>::new(ToyCipher(KEY), &[0; 8]));
+
+        let ciphertext = assert_ok!(encryptor.encrypt(b"embedded IV"));
+        assert_eq!(assert_ok!(decryptor.decrypt(&ciphertext)), b"embedded IV");
+    }
+
+    #[test]
+    fn no_padding_empty_roundtrip() {
+        let cbc = assert_ok!(Cbc::<_, NoPadding>::new(ToyCipher(KEY), &IV));
+        let ciphertext = assert_ok!(cbc.encrypt(&[]));
+        assert_eq!(ciphertext, IV);
+        assert_eq!(assert_ok!(cbc.decrypt(&ciphertext)), []);
+    }
+
+    #[test]
+    fn invalid_iv_size_fails() {
+        assert_err!(Cbc::<_>::new(ToyCipher(KEY), &[0; 16]));
+    }
+
+    #[test]
+    fn decrypt_invalid_length_fails() {
+        let cbc = assert_ok!(Cbc::<_>::new(ToyCipher(KEY), &IV));
+        assert_err!(cbc.decrypt(&[0u8; 4]));
+        assert_err!(cbc.decrypt(&[0u8; 20]));
+    }
+}
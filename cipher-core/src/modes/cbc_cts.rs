@@ -0,0 +1,301 @@
+//! CBC with ciphertext stealing (NIST SP 800-38A Addendum).
+//!
+//! Ciphertext stealing replaces padding: a partial final block is zero-padded
+//! before encryption, and the bytes of the previous ciphertext block that the
+//! padding would have added are dropped again. The ciphertext is then exactly
+//! as long as the plaintext. The three variants differ only in the order of
+//! the last two ciphertext blocks.
+
+use crate::{BlockCipher, CipherError, CipherMode, CipherResult, modes::xor_in_place};
+use std::fmt::{Debug, Display};
+use zeroize::Zeroizing;
+
+/// Order of the last two ciphertext blocks in CBC with ciphertext stealing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CtsVariant {
+    /// CBC-CS1: the partial block comes before the last full block.
+    Cs1,
+    /// CBC-CS2: the last two blocks are swapped only if the final block is
+    /// partial, so block-aligned messages match plain CBC.
+    Cs2,
+    /// CBC-CS3: the last two blocks are always swapped, as in Kerberos
+    /// (RFC 3962).
+    #[default]
+    Cs3,
+}
+
+impl CtsVariant {
+    /// Returns whether the last two ciphertext blocks are swapped for a
+    /// message whose final block holds `partial` bytes (0 when complete).
+    const fn swaps(self, partial: usize) -> bool {
+        match self {
+            Self::Cs1 => false,
+            Self::Cs2 => partial != 0,
+            Self::Cs3 => true,
+        }
+    }
+}
+
+impl Display for CtsVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Cs1 => "CBC-CS1",
+            Self::Cs2 => "CBC-CS2",
+            Self::Cs3 => "CBC-CS3",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A block cipher in CBC mode with ciphertext stealing.
+///
+/// Messages must be at least one block long; any longer length is accepted
+/// and no padding is added. The IV is prepended to the ciphertext:
+/// `[IV][ciphertext...]`, where the ciphertext is as long as the plaintext.
+/// On decryption the IV is read back from the input, so the IV stored in the
+/// mode is only used for encryption.
+///
+/// # Example
+///
+/// ```ignore
+/// use cipher_core::{CbcCts, CipherMode, CtsVariant};
+///
+/// let cbc = CbcCts::new(Aes::new(key), &iv, CtsVariant::Cs3)?;
+/// let ciphertext = cbc.encrypt(b"Hello, World! Hello!")?;
+/// let plaintext = cbc.decrypt(&ciphertext)?;
+/// ```
+pub struct CbcCts<C> {
+    cipher: C,
+    iv: Zeroizing<Vec<u8>>,
+    variant: CtsVariant,
+}
+
+impl<C: BlockCipher> CbcCts<C> {
+    /// Wraps `cipher` in CBC mode with ciphertext stealing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the IV is not exactly one block long.
+    pub fn new(cipher: C, iv: &[u8], variant: CtsVariant) -> CipherResult<Self> {
+        let block_size = cipher.block_size();
+        if iv.len() != block_size {
+            return Err(CipherError::invalid_iv_size(block_size, iv.len()));
+        }
+
+        Ok(Self {
+            cipher,
+            iv: Zeroizing::new(iv.to_vec()),
+            variant,
+        })
+    }
+
+    /// Returns the underlying block cipher.
+    #[inline]
+    #[must_use]
+    pub const fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    /// Returns the IV used for encryption.
+    #[inline]
+    #[must_use]
+    pub fn iv(&self) -> &[u8] {
+        &self.iv
+    }
+
+    /// Returns the ciphertext stealing variant.
+    #[inline]
+    #[must_use]
+    pub const fn variant(&self) -> CtsVariant {
+        self.variant
+    }
+
+    /// Decrypts complete blocks in plain CBC mode, chaining from `iv`.
+    fn decrypt_blocks(&self, iv: &[u8], ciphertext: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        let mut plaintext = Vec::with_capacity(ciphertext.len());
+        let mut prev = iv;
+        for chunk in ciphertext.chunks_exact(block_size) {
+            let mut block = self.cipher.decrypt(chunk)?.to_vec();
+            xor_in_place(&mut block, prev);
+            plaintext.extend_from_slice(&block);
+            prev = chunk;
+        }
+        Ok(plaintext)
+    }
+}
+
+/// Omits the cipher, which holds key material.
+impl<C> Debug for CbcCts<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CbcCts")
+            .field("variant", &self.variant)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher> CipherMode for CbcCts<C> {
+    /// Encrypts plaintext, returning `[IV][ciphertext...]`.
+    fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        if plaintext.len() < block_size {
+            return Err(CipherError::invalid_block_size(block_size, plaintext.len()));
+        }
+
+        let blocks = plaintext.len().div_ceil(block_size);
+        let mut output = Vec::with_capacity(block_size * (blocks + 1));
+        output.extend_from_slice(&self.iv);
+
+        for (idx, chunk) in plaintext.chunks(block_size).enumerate() {
+            // A partial final block is zero-padded, as in the addendum
+            let mut block = Zeroizing::new(vec![0; block_size]);
+            block[..chunk.len()].copy_from_slice(chunk);
+            xor_in_place(
+                &mut block,
+                &output[idx * block_size..(idx + 1) * block_size],
+            );
+            let encrypted = self.cipher.encrypt(&block)?;
+            output.extend_from_slice(&encrypted);
+        }
+
+        let partial = plaintext.len() % block_size;
+        if blocks > 1 {
+            // Keep only the first `partial` bytes of C_{n-1}, then order the
+            // last two blocks for the variant
+            let kept = if partial == 0 { block_size } else { partial };
+            let tail = output.split_off(block_size * (blocks - 1));
+            let (prev, last) = tail.split_at(block_size);
+            if self.variant.swaps(partial) {
+                output.extend_from_slice(last);
+                output.extend_from_slice(&prev[..kept]);
+            } else {
+                output.extend_from_slice(&prev[..kept]);
+                output.extend_from_slice(last);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Decrypts `[IV][ciphertext...]`.
+    fn decrypt(&self, data: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        if data.len() < 2 * block_size {
+            return Err(CipherError::invalid_block_size(2 * block_size, data.len()));
+        }
+
+        let (iv, ciphertext) = data.split_at(block_size);
+        let blocks = ciphertext.len().div_ceil(block_size);
+        if blocks == 1 {
+            return self.decrypt_blocks(iv, ciphertext);
+        }
+
+        let partial = ciphertext.len() % block_size;
+        let kept = if partial == 0 { block_size } else { partial };
+        let (head, tail) = ciphertext.split_at(block_size * (blocks - 2));
+        let (stolen, last) = if self.variant.swaps(partial) {
+            let (last, stolen) = tail.split_at(block_size);
+            (stolen, last)
+        } else {
+            tail.split_at(kept)
+        };
+
+        // D(C_n) is P_n (zero-padded) XOR C_{n-1}, so its trailing bytes are
+        // the part of C_{n-1} that was dropped
+        let decrypted = self.cipher.decrypt(last)?;
+        let mut prev = Zeroizing::new(stolen.to_vec());
+        prev.extend_from_slice(&decrypted[kept..]);
+        let mut final_block = decrypted[..kept].to_vec();
+        xor_in_place(&mut final_block, stolen);
+
+        let mut chained = head.to_vec();
+        chained.extend_from_slice(&prev);
+        let mut plaintext = self.decrypt_blocks(iv, &chained)?;
+        plaintext.extend_from_slice(&final_block);
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cbc, NoPadding, modes::tests::ToyCipher};
+    use claims::{assert_err, assert_ok};
+
+    const KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+    const IV: [u8; 8] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xAB, 0xCD, 0xEF];
+    const MESSAGE: &[u8] = b"The quick brown fox jumps over the lazy dog";
+    const VARIANTS: [CtsVariant; 3] = [CtsVariant::Cs1, CtsVariant::Cs2, CtsVariant::Cs3];
+
+    #[test]
+    fn roundtrip_preserves_length() {
+        for variant in VARIANTS {
+            let cts = assert_ok!(CbcCts::new(ToyCipher(KEY), &IV, variant));
+            for len in 8..=MESSAGE.len() {
+                let ciphertext = assert_ok!(cts.encrypt(&MESSAGE[..len]));
+                assert_eq!(ciphertext.len(), 8 + len, "{variant} length {len}");
+                assert_eq!(&ciphertext[..8], IV);
+                assert_eq!(
+                    assert_ok!(cts.decrypt(&ciphertext)),
+                    &MESSAGE[..len],
+                    "{variant} length {len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn aligned_messages_match_cbc_except_cs3() {
+        let cbc = assert_ok!(Cbc::<_, NoPadding>::new(ToyCipher(KEY), &IV));
+        let expected = assert_ok!(cbc.encrypt(&MESSAGE[..24]));
+
+        let cs1 = assert_ok!(CbcCts::new(ToyCipher(KEY), &IV, CtsVariant::Cs1));
+        let cs2 = assert_ok!(CbcCts::new(ToyCipher(KEY), &IV, CtsVariant::Cs2));
+        let cs3 = assert_ok!(CbcCts::new(ToyCipher(KEY), &IV, CtsVariant::Cs3));
+        assert_eq!(assert_ok!(cs1.encrypt(&MESSAGE[..24])), expected);
+        assert_eq!(assert_ok!(cs2.encrypt(&MESSAGE[..24])), expected);
+
+        // CS3 always swaps the last two blocks
+        let swapped = assert_ok!(cs3.encrypt(&MESSAGE[..24]));
+        assert_eq!(swapped[..16], expected[..16]);
+        assert_eq!(swapped[16..24], expected[24..]);
+        assert_eq!(swapped[24..], expected[16..24]);
+    }
+
+    #[test]
+    fn variants_differ_in_block_order() {
+        let cs1 = assert_ok!(CbcCts::new(ToyCipher(KEY), &IV, CtsVariant::Cs1));
+        let cs2 = assert_ok!(CbcCts::new(ToyCipher(KEY), &IV, CtsVariant::Cs2));
+        let cs3 = assert_ok!(CbcCts::new(ToyCipher(KEY), &IV, CtsVariant::Cs3));
+
+        // 20 bytes: one full block, then a 4-byte stolen block and a full block
+        let one = assert_ok!(cs1.encrypt(&MESSAGE[..20]));
+        let two = assert_ok!(cs2.encrypt(&MESSAGE[..20]));
+        assert_eq!(two, assert_ok!(cs3.encrypt(&MESSAGE[..20])));
+        assert_eq!(one[..16], two[..16]);
+        assert_eq!(one[16..20], two[24..]);
+        assert_eq!(one[20..], two[16..24]);
+    }
+
+    #[test]
+    fn single_block_is_plain_cbc() {
+        let cbc = assert_ok!(Cbc::<_, NoPadding>::new(ToyCipher(KEY), &IV));
+        let expected = assert_ok!(cbc.encrypt(&MESSAGE[..8]));
+        for variant in VARIANTS {
+            let cts = assert_ok!(CbcCts::new(ToyCipher(KEY), &IV, variant));
+            assert_eq!(assert_ok!(cts.encrypt(&MESSAGE[..8])), expected);
+        }
+    }
+
+    #[test]
+    fn short_input_fails() {
+        let cts = assert_ok!(CbcCts::new(ToyCipher(KEY), &IV, CtsVariant::Cs3));
+        assert_err!(cts.encrypt(&MESSAGE[..7]));
+        assert_err!(cts.decrypt(&[0; 15]));
+    }
+
+    #[test]
+    fn invalid_iv_size_fails() {
+        assert_err!(CbcCts::new(ToyCipher(KEY), &[0; 16], CtsVariant::Cs1));
+    }
+}
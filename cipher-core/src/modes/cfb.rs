@@ -0,0 +1,268 @@
+//! CFB (Cipher Feedback) mode.
+//!
+//! CFB mode turns a block cipher into a self-synchronising stream cipher. The
+//! previous ciphertext segments are fed back through the cipher to produce
+//! the keystream for the next segment. Only the cipher's encryption direction
+//! is used, for both encryption and decryption.
+
+use crate::{BlockCipher, CipherAction, CipherError, CipherMode, CipherResult};
+use std::fmt::{Debug, Display};
+use zeroize::Zeroizing;
+
+/// Number of bits processed per cipher invocation in CFB mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CfbSegment {
+    /// CFB-1: one bit per block encryption.
+    Bit,
+    /// CFB-8: one byte per block encryption.
+    Byte,
+    /// Full-block CFB (CFB-64 for DES, CFB-128 for AES).
+    #[default]
+    Block,
+}
+
+impl CfbSegment {
+    /// Returns the segment size in bits for a cipher with the given block size.
+    #[inline]
+    #[must_use]
+    pub const fn bits(self, block_size: usize) -> usize {
+        match self {
+            Self::Bit => 1,
+            Self::Byte => 8,
+            Self::Block => block_size * 8,
+        }
+    }
+}
+
+impl Display for CfbSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Bit => "CFB-1",
+            Self::Byte => "CFB-8",
+            Self::Block => "CFB",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A block cipher in CFB (Cipher Feedback) mode.
+///
+/// Messages of any length are accepted and no padding is added. The IV is
+/// prepended to the ciphertext: `[IV][ciphertext...]`. On decryption the IV
+/// is read back from the input, so the IV stored in the mode is only used
+/// for encryption.
+///
+/// # Example
+///
+/// ```ignore
+/// use cipher_core::{Cfb, CfbSegment, CipherMode};
+///
+/// let cfb = Cfb::new(Des::new(key), &iv, CfbSegment::Byte)?;
+/// let ciphertext = cfb.encrypt(b"Hello, World!")?;
+/// let plaintext = cfb.decrypt(&ciphertext)?;
+/// ```
+pub struct Cfb<C> {
+    cipher: C,
+    iv: Zeroizing<Vec<u8>>,
+    segment: CfbSegment,
+}
+
+impl<C: BlockCipher> Cfb<C> {
+    /// Wraps `cipher` in CFB mode with the given IV and segment size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the IV is not exactly one block long.
+    pub fn new(cipher: C, iv: &[u8], segment: CfbSegment) -> CipherResult<Self> {
+        let block_size = cipher.block_size();
+        if iv.len() != block_size {
+            return Err(CipherError::invalid_iv_size(block_size, iv.len()));
+        }
+
+        Ok(Self {
+            cipher,
+            iv: Zeroizing::new(iv.to_vec()),
+            segment,
+        })
+    }
+
+    /// Returns the underlying block cipher.
+    #[inline]
+    #[must_use]
+    pub const fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    /// Returns the IV used for encryption.
+    #[inline]
+    #[must_use]
+    pub fn iv(&self) -> &[u8] {
+        &self.iv
+    }
+
+    /// Returns the segment size.
+    #[inline]
+    #[must_use]
+    pub const fn segment(&self) -> CfbSegment {
+        self.segment
+    }
+
+    /// Encrypts or decrypts `data` in place, starting from the shift register `iv`.
+    fn apply(&self, iv: &[u8], data: &mut [u8], action: CipherAction) -> CipherResult<()> {
+        let mut register = Zeroizing::new(iv.to_vec());
+        match self.segment {
+            CfbSegment::Bit => {
+                for byte in data {
+                    let mut output = 0;
+                    for bit in (0..8).rev() {
+                        let keystream = self.cipher.encrypt(&register)?[0] >> 7;
+                        let input = (*byte >> bit) & 1;
+                        let result = input ^ keystream;
+                        output |= result << bit;
+
+                        let feedback = match action {
+                            CipherAction::Encrypt => result,
+                            CipherAction::Decrypt => input,
+                        };
+                        shift_in_bit(&mut register, feedback);
+                    }
+                    *byte = output;
+                }
+            }
+            CfbSegment::Byte => {
+                for byte in data {
+                    let keystream = self.cipher.encrypt(&register)?[0];
+                    let input = *byte;
+                    *byte ^= keystream;
+
+                    let feedback = match action {
+                        CipherAction::Encrypt => *byte,
+                        CipherAction::Decrypt => input,
+                    };
+                    register.rotate_left(1);
+                    if let Some(last) = register.last_mut() {
+                        *last = feedback;
+                    }
+                }
+            }
+            CfbSegment::Block => {
+                for chunk in data.chunks_mut(register.len()) {
+                    let keystream = self.cipher.encrypt(&register)?;
+                    // A short final segment is the last one, so the register is not needed again
+                    if action == CipherAction::Decrypt {
+                        register[..chunk.len()].copy_from_slice(chunk);
+                    }
+                    for (byte, key_byte) in chunk.iter_mut().zip(keystream.iter()) {
+                        *byte ^= key_byte;
+                    }
+                    if action == CipherAction::Encrypt {
+                        register[..chunk.len()].copy_from_slice(chunk);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Shifts the register left by one bit and appends `bit` at the end.
+fn shift_in_bit(register: &mut [u8], bit: u8) {
+    let mut carry = bit;
+    for byte in register.iter_mut().rev() {
+        let next = *byte >> 7;
+        *byte = (*byte << 1) | carry;
+        carry = next;
+    }
+}
+
+/// Omits the cipher, which holds key material.
+impl<C> Debug for Cfb<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cfb")
+            .field("segment", &self.segment)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher> CipherMode for Cfb<C> {
+    /// Encrypts plaintext, returning `[IV][ciphertext...]`.
+    fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        let mut output = Vec::with_capacity(block_size + plaintext.len());
+        output.extend_from_slice(&self.iv);
+        output.extend_from_slice(plaintext);
+
+        self.apply(&self.iv, &mut output[block_size..], CipherAction::Encrypt)?;
+        Ok(output)
+    }
+
+    /// Decrypts `[IV][ciphertext...]`.
+    fn decrypt(&self, data: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        if data.len() < block_size {
+            return Err(CipherError::invalid_block_size(block_size, data.len()));
+        }
+
+        let (iv, ciphertext) = data.split_at(block_size);
+        let mut plaintext = ciphertext.to_vec();
+        self.apply(iv, &mut plaintext, CipherAction::Decrypt)?;
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modes::tests::ToyCipher;
+    use claims::{assert_err, assert_ok};
+
+    const KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+    const IV: [u8; 8] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xAB, 0xCD, 0xEF];
+
+    const SEGMENTS: [CfbSegment; 3] = [CfbSegment::Bit, CfbSegment::Byte, CfbSegment::Block];
+
+    #[test]
+    fn roundtrip() {
+        let plaintext = b"The quick brown fox jumps over the lazy dog";
+        for segment in SEGMENTS {
+            let cfb = assert_ok!(Cfb::new(ToyCipher(KEY), &IV, segment));
+
+            let ciphertext = assert_ok!(cfb.encrypt(plaintext));
+            assert_eq!(&ciphertext[..8], IV);
+            assert_eq!(ciphertext.len(), 8 + plaintext.len());
+
+            let decrypted = assert_ok!(cfb.decrypt(&ciphertext));
+            assert_eq!(decrypted, plaintext, "{segment}");
+        }
+    }
+
+    #[test]
+    fn prefix_is_stable() {
+        // Without padding, a truncated message encrypts to a truncated ciphertext
+        for segment in SEGMENTS {
+            let cfb = assert_ok!(Cfb::new(ToyCipher(KEY), &IV, segment));
+            let full = assert_ok!(cfb.encrypt(&[0xA5; 20]));
+            let partial = assert_ok!(cfb.encrypt(&[0xA5; 13]));
+            assert_eq!(partial, full[..8 + 13], "{segment}");
+        }
+    }
+
+    #[test]
+    fn shift_in_bit_carries_across_bytes() {
+        let mut register = [0x80, 0x01];
+        shift_in_bit(&mut register, 1);
+        assert_eq!(register, [0x00, 0x03]);
+    }
+
+    #[test]
+    fn invalid_iv_size_fails() {
+        assert_err!(Cfb::new(ToyCipher(KEY), &[0; 16], CfbSegment::Byte));
+    }
+
+    #[test]
+    fn decrypt_too_short_fails() {
+        let cfb = assert_ok!(Cfb::new(ToyCipher(KEY), &IV, CfbSegment::Block));
+        assert_err!(cfb.decrypt(&[0u8; 7]));
+    }
+}
@@ -0,0 +1,284 @@
+//! CMAC (Cipher-based MAC) authentication mode (NIST SP 800-38B, RFC 4493).
+//!
+//! CMAC is CBC-MAC with the last block masked by one of two subkeys derived
+//! from the cipher key, which makes it secure for messages of any length.
+//! It works with 64-bit block ciphers such as DES and 128-bit block ciphers
+//! such as AES; the subkeys are derived in GF(2^64) or GF(2^128) accordingly.
+
+use crate::{BlockCipher, CipherError, CipherResult, Mac, modes::xor_in_place};
+use std::fmt::Debug;
+use zeroize::Zeroizing;
+
+/// Reduction constant `R_64` for doubling in GF(2^64): x^4 + x^3 + x + 1.
+const RB_64: u8 = 0x1B;
+
+/// Reduction constant `R_128` for doubling in GF(2^128): x^7 + x^2 + x + 1.
+const RB_128: u8 = 0x87;
+
+/// CMAC over a block cipher with an 8 or 16-byte block.
+///
+/// The tag is one full block long.
+///
+/// # Example
+///
+/// ```ignore
+/// use cipher_core::{Cmac, Mac};
+///
+/// let mut cmac = Cmac::new(Aes::new(key))?;
+/// cmac.update(b"Hello, World!")?;
+/// let tag = cmac.finalize()?;
+/// ```
+pub struct Cmac<C> {
+    cipher: C,
+    /// Subkey for a complete final block.
+    k1: Zeroizing<Vec<u8>>,
+    /// Subkey for a padded final block.
+    k2: Zeroizing<Vec<u8>>,
+    /// CBC-MAC chaining value over every block before `buffer`.
+    state: Zeroizing<Vec<u8>>,
+    /// Up to one block of data not yet absorbed; the final block is held
+    /// back until [`finalize`](Mac::finalize) knows it is the last one.
+    buffer: Zeroizing<Vec<u8>>,
+}
+
+impl<C: BlockCipher> Cmac<C> {
+    /// Creates a CMAC instance and derives its subkeys.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidBlockSize` if the cipher's block is not
+    /// 8 or 16 bytes, the sizes CMAC defines subkeys for.
+    pub fn new(cipher: C) -> CipherResult<Self> {
+        let block_size = cipher.block_size();
+        let l = Zeroizing::new(cipher.encrypt(&vec![0; block_size])?.to_vec());
+        let k1 = dbl(&l)?;
+        let k2 = dbl(&k1)?;
+
+        Ok(Self {
+            cipher,
+            k1,
+            k2,
+            state: Zeroizing::new(vec![0; block_size]),
+            buffer: Zeroizing::new(Vec::with_capacity(block_size)),
+        })
+    }
+
+    /// Returns the underlying block cipher.
+    #[inline]
+    #[must_use]
+    pub const fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    /// Computes the tag over `data` in one call.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the cipher's block size is not supported or
+    /// the cipher fails.
+    pub fn compute(cipher: C, data: &[u8]) -> CipherResult<Vec<u8>> {
+        let mut cmac = Self::new(cipher)?;
+        cmac.update(data)?;
+        cmac.finalize()
+    }
+}
+
+impl<C: BlockCipher> Mac for Cmac<C> {
+    fn output_size(&self) -> usize {
+        self.cipher.block_size()
+    }
+
+    fn update(&mut self, mut data: &[u8]) -> CipherResult<()> {
+        let block_size = self.cipher.block_size();
+        while !data.is_empty() {
+            if self.buffer.len() == block_size {
+                absorb(&self.cipher, &mut self.state, &self.buffer)?;
+                self.buffer.clear();
+            }
+
+            let take = (block_size - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+        }
+        Ok(())
+    }
+
+    fn finalize(&self) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        let mut last = Zeroizing::new(self.buffer.to_vec());
+        if last.len() == block_size {
+            xor_in_place(&mut last, &self.k1);
+        } else {
+            last.push(0x80);
+            last.resize(block_size, 0);
+            xor_in_place(&mut last, &self.k2);
+        }
+
+        let mut state = self.state.to_vec();
+        absorb(&self.cipher, &mut state, &last)?;
+        Ok(state)
+    }
+
+    fn reset(&mut self) {
+        self.state.fill(0);
+        self.buffer.clear();
+    }
+}
+
+/// Omits the cipher and subkeys, which are key material.
+impl<C> Debug for Cmac<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cmac").finish_non_exhaustive()
+    }
+}
+
+/// Encrypts `state ^ block` into the chaining value.
+fn absorb(cipher: &impl BlockCipher, state: &mut [u8], block: &[u8]) -> CipherResult<()> {
+    xor_in_place(state, block);
+    let output = cipher.encrypt(state)?;
+    state.copy_from_slice(&output);
+    Ok(())
+}
+
+/// Multiplies a big-endian block by `x` in GF(2^64) or GF(2^128).
+///
+/// This is the doubling CMAC uses to derive its subkeys, and S2V (RFC 5297)
+/// to chain its inputs. The reduction is applied through a mask, so the
+/// running time does not depend on the top bit.
+///
+/// # Errors
+///
+/// Returns `CipherError::InvalidBlockSize` if `block` is not 8 or 16 bytes.
+pub fn dbl(block: &[u8]) -> CipherResult<Zeroizing<Vec<u8>>> {
+    let rb = match block.len() {
+        8 => RB_64,
+        16 => RB_128,
+        len => return Err(CipherError::invalid_block_size(16, len)),
+    };
+
+    let mut doubled = Zeroizing::new(vec![0; block.len()]);
+    let mut carry = 0;
+    for (out, byte) in doubled.iter_mut().zip(block).rev() {
+        *out = (byte << 1) | carry;
+        carry = byte >> 7;
+    }
+    if let Some(last) = doubled.last_mut() {
+        *last ^= rb & 0u8.wrapping_sub(carry);
+    }
+    Ok(doubled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CipherAction, Output, modes::tests::ToyCipher};
+    use claims::{assert_err, assert_matches, assert_ok};
+
+    const KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+    const MESSAGE: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn dbl_shifts_and_reduces() {
+        let dbl_64 = |value: u64| assert_ok!(dbl(&value.to_be_bytes())).to_vec();
+        assert_eq!(dbl_64(1), 2_u64.to_be_bytes());
+        assert_eq!(
+            dbl_64(0x4000_0000_0000_0080),
+            0x8000_0000_0000_0100_u64.to_be_bytes()
+        );
+        assert_eq!(dbl_64(1 << 63), u64::from(RB_64).to_be_bytes());
+
+        let dbl_128 = |value: u128| assert_ok!(dbl(&value.to_be_bytes())).to_vec();
+        assert_eq!(dbl_128(1), 2_u128.to_be_bytes());
+        assert_eq!(dbl_128(1 << 127), u128::from(RB_128).to_be_bytes());
+    }
+
+    #[test]
+    fn dbl_rejects_other_sizes() {
+        let err = assert_err!(dbl(&[0; 4]));
+        assert_eq!(err, CipherError::invalid_block_size(16, 4));
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        let expected = assert_ok!(Cmac::compute(ToyCipher(KEY), MESSAGE));
+        assert_eq!(expected.len(), 8);
+
+        // Every split point, including ones on block boundaries
+        for split in 0..=MESSAGE.len() {
+            let mut cmac = assert_ok!(Cmac::new(ToyCipher(KEY)));
+            assert_ok!(cmac.update(&MESSAGE[..split]));
+            assert_ok!(cmac.update(&MESSAGE[split..]));
+            assert_eq!(assert_ok!(cmac.finalize()), expected, "split at {split}");
+        }
+    }
+
+    #[test]
+    fn complete_and_padded_final_blocks_differ() {
+        // "abcdefgh" fills a block; "abcdefg" padded with 0x80 must not collide
+        let full = assert_ok!(Cmac::compute(ToyCipher(KEY), b"abcdefgh"));
+        let padded = assert_ok!(Cmac::compute(ToyCipher(KEY), b"abcdefg\x80"));
+        let short = assert_ok!(Cmac::compute(ToyCipher(KEY), b"abcdefg"));
+        assert_ne!(full, short);
+        assert_ne!(padded, short);
+    }
+
+    #[test]
+    fn finalize_does_not_consume_state() {
+        let mut cmac = assert_ok!(Cmac::new(ToyCipher(KEY)));
+        assert_ok!(cmac.update(b"Hello, "));
+        let partial = assert_ok!(cmac.finalize());
+        assert_eq!(
+            partial,
+            assert_ok!(Cmac::compute(ToyCipher(KEY), b"Hello, "))
+        );
+
+        assert_ok!(cmac.update(b"World!"));
+        let tag = assert_ok!(cmac.finalize());
+        assert_eq!(
+            tag,
+            assert_ok!(Cmac::compute(ToyCipher(KEY), b"Hello, World!"))
+        );
+    }
+
+    #[test]
+    fn reset_clears_data() {
+        let mut cmac = assert_ok!(Cmac::new(ToyCipher(KEY)));
+        assert_ok!(cmac.update(MESSAGE));
+        cmac.reset();
+        assert_eq!(
+            assert_ok!(cmac.finalize()),
+            assert_ok!(Cmac::compute(ToyCipher(KEY), &[]))
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let mut cmac = assert_ok!(Cmac::new(ToyCipher(KEY)));
+        assert_ok!(cmac.update(MESSAGE));
+        let mut tag = assert_ok!(cmac.finalize());
+        assert_ok!(cmac.verify(&tag));
+
+        tag[0] ^= 1;
+        let err = assert_err!(cmac.verify(&tag));
+        assert_matches!(err, CipherError::AuthenticationFailed);
+        assert_err!(cmac.verify(&tag[..4]));
+    }
+
+    #[test]
+    fn unsupported_block_size_fails() {
+        struct Identity32;
+
+        impl BlockCipher for Identity32 {
+            fn block_size(&self) -> usize {
+                32
+            }
+
+            fn transform_impl(&self, block: &[u8], _: CipherAction) -> CipherResult<Output> {
+                Ok(block.to_vec().into())
+            }
+        }
+
+        let err = assert_err!(Cmac::new(Identity32));
+        assert_matches!(err, CipherError::InvalidBlockSize { actual: 32, .. });
+    }
+}
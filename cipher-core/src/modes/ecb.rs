@@ -0,0 +1,126 @@
+//! ECB (Electronic Codebook) mode.
+//!
+//! Every block is encrypted independently with the same key, so identical
+//! plaintext blocks produce identical ciphertext blocks.
+
+use crate::{BlockCipher, CipherError, CipherMode, CipherResult, Padding, Pkcs7};
+use std::{fmt::Debug, marker::PhantomData};
+
+/// A block cipher in ECB (Electronic Codebook) mode.
+///
+/// # Security
+///
+/// ECB leaks patterns in the plaintext and should only be used for
+/// demonstration or single-block messages.
+///
+/// # Example
+///
+/// ```ignore
+/// use cipher_core::{CipherMode, Ecb};
+///
+/// let ecb = Ecb::<_>::new(Des::new(key));
+/// let ciphertext = ecb.encrypt(b"Hello, World!")?;
+/// ```
+pub struct Ecb<C, P = Pkcs7> {
+    cipher: C,
+    padding: PhantomData<P>,
+}
+
+impl<C: BlockCipher, P: Padding> Ecb<C, P> {
+    /// Wraps `cipher` in ECB mode.
+    #[inline]
+    #[must_use]
+    pub const fn new(cipher: C) -> Self {
+        Self {
+            cipher,
+            padding: PhantomData,
+        }
+    }
+
+    /// Returns the underlying block cipher.
+    #[inline]
+    #[must_use]
+    pub const fn cipher(&self) -> &C {
+        &self.cipher
+    }
+}
+
+/// Omits the cipher, which holds key material.
+impl<C, P> Debug for Ecb<C, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ecb").finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher, P: Padding> CipherMode for Ecb<C, P> {
+    fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        let padded = P::pad(plaintext, block_size)?;
+        let mut output = Vec::with_capacity(padded.len());
+
+        for chunk in padded.chunks_exact(block_size) {
+            output.extend_from_slice(&self.cipher.encrypt(chunk)?);
+        }
+
+        Ok(output)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        if !ciphertext.len().is_multiple_of(block_size) {
+            return Err(CipherError::invalid_block_size(
+                block_size,
+                ciphertext.len(),
+            ));
+        }
+
+        let mut plaintext = Vec::with_capacity(ciphertext.len());
+        for chunk in ciphertext.chunks_exact(block_size) {
+            plaintext.extend_from_slice(&self.cipher.decrypt(chunk)?);
+        }
+
+        Ok(P::unpad(&plaintext, block_size)?.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoPadding, modes::tests::ToyCipher};
+    use claims::{assert_err, assert_ok};
+
+    const KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+
+    #[test]
+    fn roundtrip() {
+        let ecb = Ecb::<_>::new(ToyCipher(KEY));
+        let plaintext = b"The quick brown fox jumps over the lazy dog";
+
+        let ciphertext = assert_ok!(ecb.encrypt(plaintext));
+        assert_eq!(ciphertext.len(), 48);
+
+        let decrypted = assert_ok!(ecb.decrypt(&ciphertext));
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn identical_blocks_encrypt_identically() {
+        let ecb = Ecb::<_, NoPadding>::new(ToyCipher(KEY));
+        let ciphertext = assert_ok!(ecb.encrypt(&[0xAB; 16]));
+        assert_eq!(ciphertext[..8], ciphertext[8..]);
+    }
+
+    #[test]
+    fn boxed_cipher() {
+        let cipher: Box<dyn BlockCipher> = Box::new(ToyCipher(KEY));
+        let ecb = Ecb::<_>::new(cipher);
+        let ciphertext = assert_ok!(ecb.encrypt(b"boxed"));
+        assert_eq!(assert_ok!(ecb.decrypt(&ciphertext)), b"boxed");
+    }
+
+    #[test]
+    fn decrypt_invalid_length_fails() {
+        let ecb = Ecb::<_>::new(ToyCipher(KEY));
+        assert_err!(ecb.decrypt(&[0u8; 12]));
+    }
+}
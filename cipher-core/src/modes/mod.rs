@@ -0,0 +1,89 @@
+//! Block cipher modes of operation.
+//!
+//! Modes turn a [`BlockCipher`](crate::BlockCipher), which only transforms a
+//! single block, into a cipher for messages of arbitrary length. They are
+//! generic over the underlying cipher, so any cipher implementing the trait,
+//! including `Box<dyn BlockCipher>`, can be chained. [`Cmac`] is the
+//! authentication mode built the same way.
+
+mod cbc;
+mod cbc_cts;
+mod cfb;
+mod cmac;
+mod ecb;
+mod ofb;
+
+use crate::CipherResult;
+
+pub use {
+    cbc::Cbc,
+    cbc_cts::{CbcCts, CtsVariant},
+    cfb::{Cfb, CfbSegment},
+    cmac::{Cmac, dbl},
+    ecb::Ecb,
+    ofb::Ofb,
+};
+
+/// Multi-block encryption and decryption of arbitrary length messages.
+pub trait CipherMode {
+    /// Encrypts a message of any length.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the message cannot be padded or encrypted.
+    fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>>;
+
+    /// Decrypts a message produced by [`encrypt`](Self::encrypt).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the ciphertext length or padding is invalid.
+    fn decrypt(&self, ciphertext: &[u8]) -> CipherResult<Vec<u8>>;
+}
+
+impl<T: CipherMode + ?Sized> CipherMode for Box<T> {
+    fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        (**self).encrypt(plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> CipherResult<Vec<u8>> {
+        (**self).decrypt(ciphertext)
+    }
+}
+
+/// XORs `src` into `dst` byte by byte.
+fn xor_in_place(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BlockCipher, CipherAction, CipherResult, Output};
+
+    /// Toy 8-byte cipher (rotate then XOR with the key) used to test the modes
+    /// without depending on a real cipher crate.
+    pub struct ToyCipher(pub [u8; 8]);
+
+    impl BlockCipher for ToyCipher {
+        fn block_size(&self) -> usize {
+            8
+        }
+
+        fn transform_impl(&self, block: &[u8], action: CipherAction) -> CipherResult<Output> {
+            let mut out = block.to_vec();
+            match action {
+                CipherAction::Encrypt => {
+                    out.rotate_left(1);
+                    super::xor_in_place(&mut out, &self.0);
+                }
+                CipherAction::Decrypt => {
+                    super::xor_in_place(&mut out, &self.0);
+                    out.rotate_right(1);
+                }
+            }
+            Ok(out.into())
+        }
+    }
+}
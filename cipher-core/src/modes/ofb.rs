@@ -0,0 +1,200 @@
+//! OFB (Output Feedback) mode.
+//!
+//! OFB mode turns a block cipher into a synchronous stream cipher. The IV is
+//! encrypted repeatedly and each output block is used as keystream. Because
+//! the keystream never depends on the data, encryption and decryption are
+//! the same operation.
+
+use crate::{BlockCipher, CipherError, CipherMode, CipherResult, modes::xor_in_place};
+use std::fmt::Debug;
+use zeroize::Zeroizing;
+
+/// A block cipher in OFB (Output Feedback) mode.
+///
+/// Messages of any length are accepted and no padding is added. The IV is
+/// prepended to the ciphertext: `[IV][ciphertext...]`. On decryption the IV
+/// is read back from the input, so the IV stored in the mode is only used
+/// for encryption.
+///
+/// # Security
+///
+/// Reusing an IV with the same key reuses the keystream.
+///
+/// # Example
+///
+/// ```ignore
+/// use cipher_core::{CipherMode, Ofb};
+///
+/// let ofb = Ofb::new(Aes::new(key), &iv)?;
+/// let ciphertext = ofb.encrypt(b"Hello, World!")?;
+/// let plaintext = ofb.decrypt(&ciphertext)?;
+/// ```
+pub struct Ofb<C> {
+    cipher: C,
+    iv: Zeroizing<Vec<u8>>,
+}
+
+impl<C: BlockCipher> Ofb<C> {
+    /// Wraps `cipher` in OFB mode with the given IV.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if the IV is not exactly one block long.
+    pub fn new(cipher: C, iv: &[u8]) -> CipherResult<Self> {
+        let block_size = cipher.block_size();
+        if iv.len() != block_size {
+            return Err(CipherError::invalid_iv_size(block_size, iv.len()));
+        }
+
+        Ok(Self {
+            cipher,
+            iv: Zeroizing::new(iv.to_vec()),
+        })
+    }
+
+    /// Returns the underlying block cipher.
+    #[inline]
+    #[must_use]
+    pub const fn cipher(&self) -> &C {
+        &self.cipher
+    }
+
+    /// Returns the IV used for encryption.
+    #[inline]
+    #[must_use]
+    pub fn iv(&self) -> &[u8] {
+        &self.iv
+    }
+
+    /// Generates `len` bytes of keystream starting from `iv`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidIvSize` if `iv` is not exactly one block
+    /// long, or `CipherError` if the underlying cipher fails.
+    pub fn keystream(&self, iv: &[u8], len: usize) -> CipherResult<Zeroizing<Vec<u8>>> {
+        let block_size = self.cipher.block_size();
+        if iv.len() != block_size {
+            return Err(CipherError::invalid_iv_size(block_size, iv.len()));
+        }
+
+        let mut keystream = Zeroizing::new(Vec::with_capacity(len.next_multiple_of(block_size)));
+        let mut register = Zeroizing::new(iv.to_vec());
+
+        while keystream.len() < len {
+            register = Zeroizing::new(self.cipher.encrypt(&register)?.to_vec());
+            keystream.extend_from_slice(&register);
+        }
+
+        keystream.truncate(len);
+        Ok(keystream)
+    }
+
+    /// XORs `data` with the keystream starting from `iv`.
+    fn apply(&self, iv: &[u8], data: &mut [u8]) -> CipherResult<()> {
+        let keystream = self.keystream(iv, data.len())?;
+        xor_in_place(data, &keystream);
+        Ok(())
+    }
+}
+
+/// Omits the cipher, which holds key material.
+impl<C> Debug for Ofb<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ofb").finish_non_exhaustive()
+    }
+}
+
+impl<C: BlockCipher> CipherMode for Ofb<C> {
+    /// Encrypts plaintext, returning `[IV][ciphertext...]`.
+    fn encrypt(&self, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        let mut output = Vec::with_capacity(block_size + plaintext.len());
+        output.extend_from_slice(&self.iv);
+        output.extend_from_slice(plaintext);
+
+        self.apply(&self.iv, &mut output[block_size..])?;
+        Ok(output)
+    }
+
+    /// Decrypts `[IV][ciphertext...]`.
+    fn decrypt(&self, data: &[u8]) -> CipherResult<Vec<u8>> {
+        let block_size = self.cipher.block_size();
+        if data.len() < block_size {
+            return Err(CipherError::invalid_block_size(block_size, data.len()));
+        }
+
+        let (iv, ciphertext) = data.split_at(block_size);
+        let mut plaintext = ciphertext.to_vec();
+        self.apply(iv, &mut plaintext)?;
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modes::tests::ToyCipher;
+    use claims::{assert_err, assert_ok};
+    use rstest::rstest;
+
+    const KEY: [u8; 8] = [0x13, 0x34, 0x57, 0x79, 0x9B, 0xBC, 0xDF, 0xF1];
+    const IV: [u8; 8] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xAB, 0xCD, 0xEF];
+
+    #[test]
+    fn roundtrip() {
+        let ofb = assert_ok!(Ofb::new(ToyCipher(KEY), &IV));
+        let plaintext = b"The quick brown fox jumps over the lazy dog";
+
+        let ciphertext = assert_ok!(ofb.encrypt(plaintext));
+        assert_eq!(&ciphertext[..8], IV);
+        assert_eq!(ciphertext.len(), 8 + plaintext.len());
+
+        let decrypted = assert_ok!(ofb.decrypt(&ciphertext));
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn keystream_is_independent_of_data() {
+        let ofb = assert_ok!(Ofb::new(ToyCipher(KEY), &IV));
+        let keystream = assert_ok!(ofb.keystream(&IV, 20));
+
+        // Encrypting zeros exposes the keystream, whatever the other inputs were
+        let zeros = assert_ok!(ofb.encrypt(&[0; 20]));
+        assert_eq!(zeros[8..], keystream[..]);
+
+        let data = [0x5A; 20];
+        let ciphertext = assert_ok!(ofb.encrypt(&data));
+        for ((c, p), k) in ciphertext[8..].iter().zip(data).zip(keystream.iter()) {
+            assert_eq!(c ^ p, *k);
+        }
+    }
+
+    #[test]
+    fn empty_roundtrip() {
+        let ofb = assert_ok!(Ofb::new(ToyCipher(KEY), &IV));
+        let ciphertext = assert_ok!(ofb.encrypt(&[]));
+        assert_eq!(ciphertext, IV);
+        assert_eq!(assert_ok!(ofb.decrypt(&ciphertext)), []);
+    }
+
+    #[test]
+    fn invalid_iv_size_fails() {
+        assert_err!(Ofb::new(ToyCipher(KEY), &[0; 4]));
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(4)]
+    fn keystream_rejects_wrong_iv_size(#[case] len: usize) {
+        let ofb = assert_ok!(Ofb::new(ToyCipher(KEY), &IV));
+        let err = assert_err!(ofb.keystream(&IV[..len], 16));
+        assert_eq!(err, CipherError::invalid_iv_size(8, len));
+    }
+
+    #[test]
+    fn decrypt_too_short_fails() {
+        let ofb = assert_ok!(Ofb::new(ToyCipher(KEY), &IV));
+        assert_err!(ofb.decrypt(&[0u8; 7]));
+    }
+}
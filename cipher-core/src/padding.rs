@@ -1,4 +1,4 @@
-//! PKCS#7 padding for block ciphers.
+//! Padding schemes for block ciphers.
 //!
 //! PKCS#7 pads data to a multiple of the block size by appending N bytes
 //! of value N, where N is the number of padding bytes needed.
@@ -10,7 +10,61 @@
 //! - 14 bytes of data → add 2 bytes of value `0x02`
 //! - 16 bytes of data → add 16 bytes of value `0x10` (full padding block)
 
-use crate::CipherError;
+use crate::{CipherError, CipherResult};
+
+/// Padding scheme used by block cipher modes that need whole blocks.
+///
+/// Implementations are zero-sized markers selected through a type parameter,
+/// e.g. `Cbc<Aes, Pkcs7>` or `Ecb<Des, NoPadding>`.
+pub trait Padding {
+    /// Pads `data` to a multiple of `block_size`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the data cannot be padded by this scheme.
+    fn pad(data: &[u8], block_size: usize) -> CipherResult<Vec<u8>>;
+
+    /// Removes the padding added by [`pad`](Self::pad).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidPadding` if the padding is malformed.
+    fn unpad(data: &[u8], block_size: usize) -> CipherResult<&[u8]>;
+}
+
+/// PKCS#7 padding, see [`pkcs7_pad`] and [`pkcs7_unpad`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pkcs7;
+
+impl Padding for Pkcs7 {
+    fn pad(data: &[u8], block_size: usize) -> CipherResult<Vec<u8>> {
+        Ok(pkcs7_pad(data, block_size))
+    }
+
+    fn unpad(data: &[u8], block_size: usize) -> CipherResult<&[u8]> {
+        pkcs7_unpad(data, block_size)
+    }
+}
+
+/// No padding: the input must already be a multiple of the block size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NoPadding;
+
+impl Padding for NoPadding {
+    fn pad(data: &[u8], block_size: usize) -> CipherResult<Vec<u8>> {
+        if !data.len().is_multiple_of(block_size) {
+            return Err(CipherError::InvalidPadding(format!(
+                "data length {} is not a multiple of the block size {block_size}",
+                data.len()
+            )));
+        }
+        Ok(data.to_vec())
+    }
+
+    fn unpad(data: &[u8], _block_size: usize) -> CipherResult<&[u8]> {
+        Ok(data)
+    }
+}
 
 /// Applies PKCS#7 padding to input data.
 ///
@@ -160,6 +214,19 @@ mod tests {
         assert_err!(pkcs7_unpad(&data, 16));
     }
 
+    #[test]
+    fn no_padding_keeps_aligned_data() {
+        let data = [7u8; 32];
+        let padded = assert_ok!(NoPadding::pad(&data, 16));
+        assert_eq!(padded, data);
+        assert_eq!(assert_ok!(NoPadding::unpad(&padded, 16)), data);
+    }
+
+    #[test]
+    fn no_padding_unaligned_fails() {
+        assert_err!(NoPadding::pad(&[0u8; 15], 16));
+    }
+
     #[test]
     fn roundtrip() {
         let original = b"The quick brown fox";
@@ -84,6 +84,64 @@ pub fn parse_block_int<T: BlockInt>(s: &str) -> Result<T, BlockError> {
     parse_ascii::<T>(trimmed)
 }
 
+/// Parse a string into big-endian bytes, supporting hex (0x), binary (0b), and ASCII formats.
+///
+/// Unlike [`parse_block_int`], the result is not limited to a fixed integer width:
+/// its length follows the input (two hex digits, eight binary digits or one
+/// ASCII character per byte). Used for keys wider than 128 bits.
+///
+/// # Errors
+/// Returns `BlockError` if the string is empty or contains invalid characters.
+pub fn parse_block_bytes(s: &str) -> Result<Vec<u8>, BlockError> {
+    let trimmed = s.trim();
+
+    if trimmed.is_empty() {
+        return Err(BlockError::EmptyBlock);
+    }
+
+    if !trimmed.is_ascii() {
+        return Err(BlockError::conversion_error(
+            "bytes",
+            "String contains non-ASCII characters",
+        ));
+    }
+
+    // Hexadecimal with 0x/0X prefix
+    if let Some(hex_str) = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+    {
+        return parse_radix_bytes(hex_str, 16, 2);
+    }
+
+    // Binary with 0b/0B prefix
+    if let Some(bin_str) = trimmed
+        .strip_prefix("0b")
+        .or_else(|| trimmed.strip_prefix("0B"))
+    {
+        return parse_radix_bytes(bin_str, 2, 8);
+    }
+
+    Ok(trimmed.as_bytes().to_vec())
+}
+
+/// Parses `digits_per_byte` digits at a time, left-padding the input with zeros
+/// to a whole number of bytes.
+fn parse_radix_bytes(s: &str, radix: u32, digits_per_byte: usize) -> Result<Vec<u8>, BlockError> {
+    let padding = (digits_per_byte - s.len() % digits_per_byte) % digits_per_byte;
+    let padded = format!("{}{s}", "0".repeat(padding));
+
+    padded
+        .as_bytes()
+        .chunks(digits_per_byte)
+        .map(|chunk| {
+            // Input is ASCII, so every chunk is valid UTF-8
+            let digits = std::str::from_utf8(chunk).unwrap_or_default();
+            u8::from_str_radix(digits, radix).map_err(BlockError::from)
+        })
+        .collect()
+}
+
 fn parse_radix<T: BlockInt>(s: &str, radix: u32) -> Result<T, BlockError> {
     let trimmed = s.trim_start_matches('0');
     if trimmed.is_empty() {
@@ -110,3 +168,52 @@ fn parse_ascii<T: BlockInt>(s: &str) -> Result<T, BlockError> {
 
     Ok(T::from_be_bytes_padded(s.as_bytes()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_ok};
+
+    #[test]
+    fn bytes_from_hex() {
+        let bytes = assert_ok!(parse_block_bytes("0x000102"));
+        assert_eq!(bytes, [0x00, 0x01, 0x02]);
+    }
+
+    #[test]
+    fn bytes_from_odd_length_hex() {
+        let bytes = assert_ok!(parse_block_bytes("0xABC"));
+        assert_eq!(bytes, [0x0A, 0xBC]);
+    }
+
+    #[test]
+    fn bytes_from_binary() {
+        let bytes = assert_ok!(parse_block_bytes("0b100000001"));
+        assert_eq!(bytes, [0x01, 0x01]);
+    }
+
+    #[test]
+    fn bytes_from_ascii() {
+        let bytes = assert_ok!(parse_block_bytes("key"));
+        assert_eq!(bytes, b"key");
+    }
+
+    #[test]
+    fn bytes_wider_than_u128() {
+        let bytes = assert_ok!(parse_block_bytes(
+            "0x000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F"
+        ));
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(bytes[31], 0x1F);
+    }
+
+    #[test]
+    fn bytes_invalid_hex_fails() {
+        assert_err!(parse_block_bytes("0xZZ"));
+    }
+
+    #[test]
+    fn bytes_empty_fails() {
+        assert_err!(parse_block_bytes("   "));
+    }
+}
@@ -0,0 +1,39 @@
+use crate::CipherResult;
+
+/// Authenticated encryption with associated data (AEAD).
+///
+/// [`seal`](Self::seal) encrypts the plaintext and authenticates it together
+/// with the associated data, which is not encrypted. [`open`](Self::open)
+/// verifies the tag before releasing any plaintext, so tampering with the
+/// ciphertext, tag or associated data is detected.
+///
+/// # Example
+/// ```ignore
+/// use cipher_core::Aead;
+///
+/// let cipher = AesGcm::new(key);
+/// let sealed = cipher.seal(&nonce, b"header", b"secret")?;
+/// let opened = cipher.open(&nonce, b"header", &sealed)?;
+/// ```
+pub trait Aead {
+    /// Returns the length in bytes of the authentication tag.
+    fn tag_size(&self) -> usize;
+
+    /// Encrypts and authenticates `plaintext`, returning `[ciphertext][tag]`.
+    ///
+    /// Modes whose specification fixes another layout keep it; AES-SIV
+    /// returns `[SIV][ciphertext]` as in RFC 5297.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the nonce is invalid or the message is too long.
+    fn seal(&self, nonce: &[u8], associated_data: &[u8], plaintext: &[u8])
+    -> CipherResult<Vec<u8>>;
+
+    /// Verifies and decrypts the output of [`seal`](Self::seal).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::AuthenticationFailed` if the tag does not match.
+    fn open(&self, nonce: &[u8], associated_data: &[u8], sealed: &[u8]) -> CipherResult<Vec<u8>>;
+}
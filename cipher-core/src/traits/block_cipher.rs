@@ -61,3 +61,31 @@ pub trait BlockCipher {
         self.transform(ciphertext, CipherAction::Decrypt)
     }
 }
+
+impl<T: BlockCipher + ?Sized> BlockCipher for Box<T> {
+    fn block_size(&self) -> usize {
+        (**self).block_size()
+    }
+
+    fn transform_impl(&self, block: &[u8], action: CipherAction) -> CipherResult<Output> {
+        (**self).transform_impl(block, action)
+    }
+
+    fn transform(&self, block: &[u8], action: CipherAction) -> CipherResult<Output> {
+        (**self).transform(block, action)
+    }
+}
+
+impl<T: BlockCipher + ?Sized> BlockCipher for &T {
+    fn block_size(&self) -> usize {
+        (**self).block_size()
+    }
+
+    fn transform_impl(&self, block: &[u8], action: CipherAction) -> CipherResult<Output> {
+        (**self).transform_impl(block, action)
+    }
+
+    fn transform(&self, block: &[u8], action: CipherAction) -> CipherResult<Output> {
+        (**self).transform(block, action)
+    }
+}
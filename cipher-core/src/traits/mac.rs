@@ -0,0 +1,75 @@
+use crate::{CipherError, CipherResult, ct_eq};
+
+/// Message authentication code (MAC).
+///
+/// Data is fed in with [`update`](Self::update) in pieces of any size, and
+/// [`finalize`](Self::finalize) returns the tag for everything absorbed so
+/// far without consuming the state. [`verify`](Self::verify) compares tags
+/// in constant time.
+///
+/// # Example
+/// ```ignore
+/// use cipher_core::{Cmac, Mac};
+///
+/// let mut mac = Cmac::new(Aes::new(key))?;
+/// mac.update(b"Hello, ")?;
+/// mac.update(b"World!")?;
+/// let tag = mac.finalize()?;
+/// mac.verify(&tag)?;
+/// ```
+pub trait Mac {
+    /// Returns the length in bytes of the tag.
+    fn output_size(&self) -> usize;
+
+    /// Absorbs `data` into the MAC state.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the underlying cipher fails.
+    fn update(&mut self, data: &[u8]) -> CipherResult<()>;
+
+    /// Returns the tag over all data absorbed since creation or the last reset.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the underlying cipher fails.
+    fn finalize(&self) -> CipherResult<Vec<u8>>;
+
+    /// Clears the absorbed data, keeping the key.
+    fn reset(&mut self);
+
+    /// Checks `tag` against the tag over the absorbed data in constant time.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::AuthenticationFailed` if the tags differ.
+    fn verify(&self, tag: &[u8]) -> CipherResult<()> {
+        if ct_eq(&self.finalize()?, tag) {
+            Ok(())
+        } else {
+            Err(CipherError::AuthenticationFailed)
+        }
+    }
+}
+
+impl<T: Mac + ?Sized> Mac for Box<T> {
+    fn output_size(&self) -> usize {
+        (**self).output_size()
+    }
+
+    fn update(&mut self, data: &[u8]) -> CipherResult<()> {
+        (**self).update(data)
+    }
+
+    fn finalize(&self) -> CipherResult<Vec<u8>> {
+        (**self).finalize()
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+
+    fn verify(&self, tag: &[u8]) -> CipherResult<()> {
+        (**self).verify(tag)
+    }
+}
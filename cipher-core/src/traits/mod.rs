@@ -1,5 +1,9 @@
+mod aead;
 mod block_cipher;
 mod input_block;
+mod mac;
 
+pub use aead::Aead;
 pub use block_cipher::BlockCipher;
 pub use input_block::{BlockParser, InputBlock};
+pub use mac::Mac;
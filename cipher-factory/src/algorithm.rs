@@ -1,24 +1,139 @@
 use std::fmt::Display;
 
-use aes::{Aes, AesCbc, Block128, Iv};
-use cipher_core::{BlockCipher, BlockError, CipherError, CipherResult};
-use des::{Block64, Des};
+use aes::{Aes, AesCtr, AesKw, AesXts, Block128, CounterLayout, Iv, Key, Trace};
+use cipher_core::{
+    BlockCipher, BlockError, Cbc, CbcCts, Cfb, CfbSegment, CipherError, CipherMode, CipherResult,
+    Cmac, CtsVariant, Mac, Ofb, parse_block_bytes,
+};
+use des::{Block64, Des, TripleDes};
 use std::str::FromStr;
 
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Algorithm {
     Des,
+    #[cfg_attr(feature = "clap", clap(name = "des-cbc"))]
+    DesCbc,
+    #[cfg_attr(feature = "clap", clap(name = "des-cbc-cs1"))]
+    DesCbcCs1,
+    #[cfg_attr(feature = "clap", clap(name = "des-cbc-cs2"))]
+    DesCbcCs2,
+    #[cfg_attr(feature = "clap", clap(name = "des-cbc-cs3"))]
+    DesCbcCs3,
+    #[cfg_attr(feature = "clap", clap(name = "des-cfb1"))]
+    DesCfb1,
+    #[cfg_attr(feature = "clap", clap(name = "des-cfb8"))]
+    DesCfb8,
+    #[cfg_attr(feature = "clap", clap(name = "des-cfb64"))]
+    DesCfb64,
+    #[cfg_attr(feature = "clap", clap(name = "des-ofb"))]
+    DesOfb,
+    #[cfg_attr(feature = "clap", clap(name = "des-cmac"))]
+    DesCmac,
+    #[cfg_attr(feature = "clap", clap(name = "3des", alias = "des-ede3"))]
+    TripleDes,
     Aes,
     #[cfg_attr(feature = "clap", clap(name = "aes-cbc"))]
     AesCbc,
+    #[cfg_attr(feature = "clap", clap(name = "aes-cbc-cs1"))]
+    AesCbcCs1,
+    #[cfg_attr(feature = "clap", clap(name = "aes-cbc-cs2"))]
+    AesCbcCs2,
+    #[cfg_attr(feature = "clap", clap(name = "aes-cbc-cs3"))]
+    AesCbcCs3,
+    #[cfg_attr(feature = "clap", clap(name = "aes-ctr"))]
+    AesCtr,
+    #[cfg_attr(feature = "clap", clap(name = "aes-ctr128"))]
+    AesCtr128,
+    #[cfg_attr(feature = "clap", clap(name = "aes-cfb1"))]
+    AesCfb1,
+    #[cfg_attr(feature = "clap", clap(name = "aes-cfb8"))]
+    AesCfb8,
+    #[cfg_attr(feature = "clap", clap(name = "aes-cfb128"))]
+    AesCfb128,
+    #[cfg_attr(feature = "clap", clap(name = "aes-ofb"))]
+    AesOfb,
+    #[cfg_attr(feature = "clap", clap(name = "aes-xts"))]
+    AesXts,
+    #[cfg_attr(feature = "clap", clap(name = "aes-kw"))]
+    AesKw,
+    #[cfg_attr(feature = "clap", clap(name = "aes-kwp"))]
+    AesKwp,
+    #[cfg_attr(feature = "clap", clap(name = "aes-cmac"))]
+    AesCmac,
 }
 
 impl Algorithm {
     /// Returns whether this algorithm requires an IV (Initialization Vector).
     #[must_use]
     pub const fn requires_iv(&self) -> bool {
-        matches!(self, Self::AesCbc)
+        !matches!(
+            self,
+            Self::Des
+                | Self::DesCmac
+                | Self::TripleDes
+                | Self::Aes
+                | Self::AesXts
+                | Self::AesKw
+                | Self::AesKwp
+                | Self::AesCmac
+        )
+    }
+
+    /// Returns whether this algorithm encrypts fixed-size sectors addressed
+    /// by a data-unit number rather than whole messages.
+    #[must_use]
+    pub const fn is_sector_mode(&self) -> bool {
+        matches!(self, Self::AesXts)
+    }
+
+    /// Returns whether this algorithm wraps keys (AES-KW or AES-KWP) rather
+    /// than encrypting messages.
+    #[must_use]
+    pub const fn is_key_wrap(&self) -> bool {
+        matches!(self, Self::AesKw | Self::AesKwp)
+    }
+
+    /// Returns whether this algorithm computes message authentication codes
+    /// (AES-CMAC or DES-CMAC) rather than encrypting messages.
+    #[must_use]
+    pub const fn is_mac(&self) -> bool {
+        matches!(self, Self::DesCmac | Self::AesCmac)
+    }
+
+    /// Returns the block size in bytes of the underlying block cipher.
+    ///
+    /// This is also the IV size for modes of operation.
+    #[must_use]
+    pub const fn block_size(&self) -> usize {
+        match self {
+            Self::Des
+            | Self::DesCbc
+            | Self::DesCbcCs1
+            | Self::DesCbcCs2
+            | Self::DesCbcCs3
+            | Self::DesCfb1
+            | Self::DesCfb8
+            | Self::DesCfb64
+            | Self::DesOfb
+            | Self::DesCmac
+            | Self::TripleDes => 8,
+            Self::Aes
+            | Self::AesCbc
+            | Self::AesCbcCs1
+            | Self::AesCbcCs2
+            | Self::AesCbcCs3
+            | Self::AesCtr
+            | Self::AesCtr128
+            | Self::AesCfb1
+            | Self::AesCfb8
+            | Self::AesCfb128
+            | Self::AesOfb
+            | Self::AesXts
+            | Self::AesKw
+            | Self::AesKwp
+            | Self::AesCmac => 16,
+        }
     }
 
     /// Creates a new block cipher instance for the specified algorithm.
@@ -26,71 +141,302 @@ impl Algorithm {
     /// Parses the key string and instantiates either DES or AES based on the algorithm choice.
     /// The key format depends on the algorithm:
     /// - DES: 64-bit key (hex string, e.g., "0x1334577999bcdff1")
-    /// - AES: 128-bit key (hex string, e.g., "0x2b7e151628aed2a6abf7158809cf4f3c")
+    /// - 3DES: 128-bit (two-key) or 192-bit (three-key) key, K1 first
+    /// - AES: 128, 192 or 256-bit key (hex string, e.g., "0x2b7e151628aed2a6abf7158809cf4f3c").
+    ///   The key size, and with it AES-128, AES-192 or AES-256, follows the key length.
+    ///
+    /// For modes of operation this is the underlying block cipher, which only
+    /// transforms a single block; use [`new_mode_cipher`](Self::new_mode_cipher)
+    /// to encrypt whole messages.
     ///
     /// # Errors
     ///
-    /// Returns `CipherError` if the key cannot be parsed or if called with a CBC algorithm.
+    /// Returns `CipherError` if the key cannot be parsed.
     pub fn new_cipher(&self, key: &str) -> Result<Box<dyn BlockCipher>, CipherError> {
         match self {
-            Self::Des => {
+            Self::Des
+            | Self::DesCbc
+            | Self::DesCbcCs1
+            | Self::DesCbcCs2
+            | Self::DesCbcCs3
+            | Self::DesCfb1
+            | Self::DesCfb8
+            | Self::DesCfb64
+            | Self::DesOfb
+            | Self::DesCmac => {
                 let key = Block64::from_str(key)?;
                 let cipher = Des::from_key(key);
                 Ok(Box::new(cipher))
             }
-            Self::Aes => {
-                let key = Block128::from_str(key)?;
+            Self::TripleDes => Ok(Box::new(TripleDes::from_slice(&parse_block_bytes(key)?)?)),
+            Self::Aes
+            | Self::AesCbc
+            | Self::AesCbcCs1
+            | Self::AesCbcCs2
+            | Self::AesCbcCs3
+            | Self::AesCtr
+            | Self::AesCtr128
+            | Self::AesCfb1
+            | Self::AesCfb8
+            | Self::AesCfb128
+            | Self::AesOfb
+            | Self::AesCmac => {
+                let key = Key::from_str(key)?;
                 let cipher = Aes::from_key(key);
                 Ok(Box::new(cipher))
             }
-            Self::AesCbc => Err(CipherError::InvalidPadding(
-                "AES-CBC requires an IV; use new_cbc_cipher instead".into(),
-            )),
+            Self::AesXts => Err(CipherError::UnsupportedOperation(format!(
+                "{self} uses a double-length key; create it with new_xts_cipher"
+            ))),
+            Self::AesKw | Self::AesKwp => Err(CipherError::UnsupportedOperation(format!(
+                "{self} wraps keys; use wrap_key and unwrap_key"
+            ))),
         }
     }
 
-    /// Creates a new AES-CBC cipher instance with the given key and IV.
+    /// Creates an XTS-AES cipher from a combined 256 or 512-bit key `Key1 || Key2`.
     ///
     /// # Errors
     ///
-    /// Returns `CipherError` if the key or IV cannot be parsed.
-    pub fn new_cbc_cipher(&self, key: &str, iv: &str) -> Result<AesCbc, CipherError> {
+    /// Returns `CipherError` if the key cannot be parsed or has the wrong
+    /// length, or if the algorithm is not AES-XTS.
+    pub fn new_xts_cipher(&self, key: &str) -> CipherResult<AesXts> {
+        if !self.is_sector_mode() {
+            return Err(CipherError::UnsupportedOperation(format!(
+                "{self} is not a sector encryption mode"
+            )));
+        }
+        AesXts::from_slice(&parse_block_bytes(key)?)
+    }
+
+    /// Wraps `key_data` under the key-encryption key `kek`.
+    ///
+    /// AES-KW needs key data that is a multiple of 8 bytes and at least 16
+    /// bytes long; AES-KWP accepts any non-zero length.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the KEK cannot be parsed, the key data has
+    /// an invalid length, or the algorithm is not a key wrap.
+    pub fn wrap_key(&self, kek: &str, key_data: &[u8]) -> CipherResult<Vec<u8>> {
+        let kw = self.key_wrap(kek)?;
         match self {
-            Self::AesCbc => {
-                let key = Block128::from_str(key)?;
-                let iv = Iv::from_str(iv)?;
-                Ok(AesCbc::new(key, iv))
-            }
-            _ => Err(CipherError::InvalidPadding(format!(
-                "{self} does not support CBC mode"
-            ))),
+            Self::AesKwp => kw.wrap_padded(key_data),
+            _ => kw.wrap(key_data),
+        }
+    }
+
+    /// Unwraps `wrapped` under the key-encryption key `kek`, checking its
+    /// integrity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::AuthenticationFailed` if the integrity check
+    /// fails, or another `CipherError` if the KEK cannot be parsed, the input
+    /// has an invalid length, or the algorithm is not a key wrap.
+    pub fn unwrap_key(&self, kek: &str, wrapped: &[u8]) -> CipherResult<Vec<u8>> {
+        let kw = self.key_wrap(kek)?;
+        match self {
+            Self::AesKwp => kw.unwrap_padded(wrapped),
+            _ => kw.unwrap(wrapped),
+        }
+    }
+
+    fn key_wrap(self, kek: &str) -> CipherResult<AesKw> {
+        if !self.is_key_wrap() {
+            return Err(CipherError::UnsupportedOperation(format!(
+                "{self} is not a key wrap algorithm"
+            )));
+        }
+        Ok(AesKw::new(Key::from_str(kek)?))
+    }
+
+    /// Creates a CMAC instance keyed by `key` over DES or AES.
+    ///
+    /// Tags are one block long: 8 bytes for DES-CMAC and 16 bytes for
+    /// AES-CMAC.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the key cannot be parsed or the algorithm is
+    /// not a MAC.
+    pub fn new_mac(&self, key: &str) -> CipherResult<Box<dyn Mac>> {
+        if !self.is_mac() {
+            return Err(CipherError::UnsupportedOperation(format!(
+                "{self} is not a MAC algorithm"
+            )));
+        }
+        Ok(Box::new(Cmac::new(self.new_cipher(key)?)?))
+    }
+
+    /// Computes the tag over `message`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the key cannot be parsed or the algorithm is
+    /// not a MAC.
+    pub fn compute_mac(&self, key: &str, message: &[u8]) -> CipherResult<Vec<u8>> {
+        let mut mac = self.new_mac(key)?;
+        mac.update(message)?;
+        mac.finalize()
+    }
+
+    /// Checks `tag` against the tag over `message` in constant time.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::AuthenticationFailed` if the tag does not match,
+    /// or another `CipherError` if the key cannot be parsed or the algorithm
+    /// is not a MAC.
+    pub fn verify_mac(&self, key: &str, message: &[u8], tag: &[u8]) -> CipherResult<()> {
+        let mut mac = self.new_mac(key)?;
+        mac.update(message)?;
+        mac.verify(tag)
+    }
+
+    /// Encrypts one AES block and records every round step.
+    ///
+    /// `text` is parsed like the input of a single-block encryption.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the key or block cannot be parsed, or if the
+    /// algorithm is not plain AES.
+    pub fn trace_encrypt(&self, key: &str, text: &str) -> CipherResult<Trace> {
+        if *self != Self::Aes {
+            return Err(CipherError::UnsupportedOperation(format!(
+                "{self} cannot be traced; only single-block AES can"
+            )));
         }
+        let block = Block128::from_str(text)?;
+        Ok(Aes::from_key(Key::from_str(key)?).trace_encrypt(block.to_be_bytes()))
     }
 
-    /// Encrypts data using CBC mode with PKCS#7 padding.
+    /// Creates a new mode-of-operation cipher with the given key and IV.
+    ///
+    /// CBC modes use PKCS#7 padding; CBC with ciphertext stealing (CS1, CS2
+    /// and CS3), CTR, CFB and OFB need no padding. The IV must be one
+    /// block long: 8 bytes for DES and 16 bytes for AES. Ciphertexts start
+    /// with the IV.
+    ///
+    /// AES-CTR reads the IV as a 96-bit nonce and a 32-bit block counter;
+    /// AES-CTR128 increments the whole IV as one 128-bit counter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError` if the key or IV cannot be parsed, or if the
+    /// algorithm is a single-block cipher.
+    pub fn new_mode_cipher(&self, key: &str, iv: &str) -> CipherResult<Box<dyn CipherMode>> {
+        self.mode_cipher(key, &self.parse_iv(iv)?)
+    }
+
+    /// Encrypts data using the algorithm's mode of operation.
     ///
     /// # Errors
     ///
     /// Returns `CipherError` if encryption fails.
-    pub fn encrypt_cbc(&self, key: &str, iv: &str, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
-        let cipher = self.new_cbc_cipher(key, iv)?;
+    pub fn encrypt_mode(&self, key: &str, iv: &str, plaintext: &[u8]) -> CipherResult<Vec<u8>> {
+        let cipher = self.new_mode_cipher(key, iv)?;
         cipher.encrypt(plaintext)
     }
 
-    /// Decrypts data using CBC mode and removes PKCS#7 padding.
+    /// Decrypts data using the algorithm's mode of operation.
     ///
-    /// The IV is extracted from the first 16 bytes of the ciphertext.
+    /// The IV is extracted from the first block of the ciphertext.
     ///
     /// # Errors
     ///
     /// Returns `CipherError` if decryption fails or padding is invalid.
-    pub fn decrypt_cbc(&self, key: &str, ciphertext: &[u8]) -> CipherResult<Vec<u8>> {
-        // IV is embedded in ciphertext, use dummy IV for cipher construction
-        let dummy_iv = "0x00000000000000000000000000000000";
-        let cipher = self.new_cbc_cipher(key, dummy_iv)?;
+    pub fn decrypt_mode(&self, key: &str, ciphertext: &[u8]) -> CipherResult<Vec<u8>> {
+        // IV is embedded in ciphertext, use a zero IV for cipher construction
+        let cipher = self.mode_cipher(key, &vec![0; self.block_size()])?;
         cipher.decrypt(ciphertext)
     }
 
+    fn mode_cipher(self, key: &str, iv: &[u8]) -> CipherResult<Box<dyn CipherMode>> {
+        match self {
+            Self::DesCbc | Self::AesCbc => Ok(Box::new(Cbc::<_>::new(self.new_cipher(key)?, iv)?)),
+            Self::AesCtr => Self::ctr(key, iv, CounterLayout::Nonce96Counter32),
+            Self::AesCtr128 => Self::ctr(key, iv, CounterLayout::Counter128),
+            Self::DesCbcCs1 | Self::AesCbcCs1 => self.cbc_cts(key, iv, CtsVariant::Cs1),
+            Self::DesCbcCs2 | Self::AesCbcCs2 => self.cbc_cts(key, iv, CtsVariant::Cs2),
+            Self::DesCbcCs3 | Self::AesCbcCs3 => self.cbc_cts(key, iv, CtsVariant::Cs3),
+            Self::DesCfb1 | Self::AesCfb1 => self.cfb(key, iv, CfbSegment::Bit),
+            Self::DesCfb8 | Self::AesCfb8 => self.cfb(key, iv, CfbSegment::Byte),
+            Self::DesCfb64 | Self::AesCfb128 => self.cfb(key, iv, CfbSegment::Block),
+            Self::DesOfb | Self::AesOfb => Ok(Box::new(Ofb::new(self.new_cipher(key)?, iv)?)),
+            Self::Des | Self::TripleDes | Self::Aes => Err(CipherError::UnsupportedOperation(
+                format!("{self} is a single-block cipher; it has no mode of operation"),
+            )),
+            Self::AesXts => Err(CipherError::UnsupportedOperation(format!(
+                "{self} encrypts sectors; create it with new_xts_cipher"
+            ))),
+            Self::AesKw | Self::AesKwp => Err(CipherError::UnsupportedOperation(format!(
+                "{self} wraps keys; use wrap_key and unwrap_key"
+            ))),
+            Self::DesCmac | Self::AesCmac => Err(CipherError::UnsupportedOperation(format!(
+                "{self} computes MACs; create it with new_mac"
+            ))),
+        }
+    }
+
+    fn ctr(key: &str, iv: &[u8], layout: CounterLayout) -> CipherResult<Box<dyn CipherMode>> {
+        let iv: [u8; 16] = iv
+            .try_into()
+            .map_err(|_| CipherError::invalid_iv_size(16, iv.len()))?;
+        let key = Key::from_str(key)?;
+        Ok(Box::new(AesCtr::with_layout(key, iv, layout)))
+    }
+
+    fn cbc_cts(
+        self,
+        key: &str,
+        iv: &[u8],
+        variant: CtsVariant,
+    ) -> CipherResult<Box<dyn CipherMode>> {
+        Ok(Box::new(CbcCts::new(self.new_cipher(key)?, iv, variant)?))
+    }
+
+    fn cfb(self, key: &str, iv: &[u8], segment: CfbSegment) -> CipherResult<Box<dyn CipherMode>> {
+        Ok(Box::new(Cfb::new(self.new_cipher(key)?, iv, segment)?))
+    }
+
+    /// Parses an IV string for the algorithm's block size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BlockError` if the IV cannot be parsed.
+    pub fn parse_iv(&self, iv: &str) -> Result<Vec<u8>, BlockError> {
+        match self {
+            Self::Des
+            | Self::DesCbc
+            | Self::DesCbcCs1
+            | Self::DesCbcCs2
+            | Self::DesCbcCs3
+            | Self::DesCfb1
+            | Self::DesCfb8
+            | Self::DesCfb64
+            | Self::DesOfb
+            | Self::DesCmac
+            | Self::TripleDes => Ok(Block64::from_str(iv)?.to_be_bytes().to_vec()),
+            Self::Aes
+            | Self::AesCbc
+            | Self::AesCbcCs1
+            | Self::AesCbcCs2
+            | Self::AesCbcCs3
+            | Self::AesCtr
+            | Self::AesCtr128
+            | Self::AesCfb1
+            | Self::AesCfb8
+            | Self::AesCfb128
+            | Self::AesOfb
+            | Self::AesXts
+            | Self::AesKw
+            | Self::AesKwp
+            | Self::AesCmac => Ok(Iv::from_str(iv)?.to_be_bytes().to_vec()),
+        }
+    }
+
     /// Parses plaintext or ciphertext according to the specified algorithm's block size.
     ///
     /// Converts a text string into a byte vector using the appropriate block size:
@@ -116,8 +462,32 @@ impl Algorithm {
     /// For CBC mode, use raw bytes directly instead of this method.
     pub fn parse_text(&self, text: &str) -> Result<Vec<u8>, BlockError> {
         match self {
-            Self::Des => Ok(Block64::from_str(text)?.to_be_bytes().to_vec()),
-            Self::Aes | Self::AesCbc => Ok(Block128::from_str(text)?.to_be_bytes().to_vec()),
+            Self::Des
+            | Self::DesCbc
+            | Self::DesCbcCs1
+            | Self::DesCbcCs2
+            | Self::DesCbcCs3
+            | Self::DesCfb1
+            | Self::DesCfb8
+            | Self::DesCfb64
+            | Self::DesOfb
+            | Self::DesCmac
+            | Self::TripleDes => Ok(Block64::from_str(text)?.to_be_bytes().to_vec()),
+            Self::Aes
+            | Self::AesCbc
+            | Self::AesCbcCs1
+            | Self::AesCbcCs2
+            | Self::AesCbcCs3
+            | Self::AesCtr
+            | Self::AesCtr128
+            | Self::AesCfb1
+            | Self::AesCfb8
+            | Self::AesCfb128
+            | Self::AesOfb
+            | Self::AesXts
+            | Self::AesKw
+            | Self::AesKwp
+            | Self::AesCmac => Ok(Block128::from_str(text)?.to_be_bytes().to_vec()),
         }
     }
 }
@@ -126,9 +496,68 @@ impl Display for Algorithm {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
             Self::Des => "DES",
+            Self::DesCbc => "DES-CBC",
+            Self::DesCbcCs1 => "DES-CBC-CS1",
+            Self::DesCbcCs2 => "DES-CBC-CS2",
+            Self::DesCbcCs3 => "DES-CBC-CS3",
             Self::Aes => "AES",
             Self::AesCbc => "AES-CBC",
+            Self::AesCbcCs1 => "AES-CBC-CS1",
+            Self::AesCbcCs2 => "AES-CBC-CS2",
+            Self::AesCbcCs3 => "AES-CBC-CS3",
+            Self::DesCfb1 => "DES-CFB1",
+            Self::DesCfb8 => "DES-CFB8",
+            Self::DesCfb64 => "DES-CFB64",
+            Self::DesOfb => "DES-OFB",
+            Self::AesCtr => "AES-CTR",
+            Self::AesCtr128 => "AES-CTR128",
+            Self::AesCfb1 => "AES-CFB1",
+            Self::AesCfb8 => "AES-CFB8",
+            Self::AesCfb128 => "AES-CFB128",
+            Self::AesOfb => "AES-OFB",
+            Self::AesXts => "AES-XTS",
+            Self::AesKw => "AES-KW",
+            Self::AesKwp => "AES-KWP",
+            Self::DesCmac => "DES-CMAC",
+            Self::TripleDes => "3DES",
+            Self::AesCmac => "AES-CMAC",
         };
         f.write_str(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_ok};
+    use rstest::rstest;
+
+    const KEY: &str = "0x2B7E151628AED2A6ABF7158809CF4F3C";
+    const PLAINTEXT: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    #[rstest]
+    #[case(Algorithm::AesCtr)]
+    #[case(Algorithm::AesCtr128)]
+    fn ctr_roundtrip(#[case] algorithm: Algorithm) {
+        let iv = "0xF0F1F2F3F4F5F6F7F8F9FAFBFCFDFEFF";
+        let ciphertext = assert_ok!(algorithm.encrypt_mode(KEY, iv, PLAINTEXT));
+        assert_eq!(ciphertext.len(), 16 + PLAINTEXT.len());
+        assert_eq!(
+            assert_ok!(algorithm.decrypt_mode(KEY, &ciphertext)),
+            PLAINTEXT
+        );
+    }
+
+    #[test]
+    fn ctr_layouts_differ_at_32_bit_counter_wrap() {
+        let iv = "0x000102030405060708090A0BFFFFFFFF";
+        let err = assert_err!(Algorithm::AesCtr.encrypt_mode(KEY, iv, PLAINTEXT));
+        assert_eq!(err, CipherError::counter_overflow(32));
+
+        let ciphertext = assert_ok!(Algorithm::AesCtr128.encrypt_mode(KEY, iv, PLAINTEXT));
+        assert_eq!(
+            assert_ok!(Algorithm::AesCtr128.decrypt_mode(KEY, &ciphertext)),
+            PLAINTEXT
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{Algorithm, OperationMode, OutputFormat};
-use cipher_core::{BlockCipher, CipherError, CipherResult, Output};
+use cipher_core::{BlockCipher, CipherError, CipherMode, CipherResult, Output, parse_block_bytes};
 
 #[derive(Clone)]
 pub struct CipherContext {
@@ -39,10 +39,16 @@ impl CipherContext {
     /// Returns `Err` if:
     /// - Parsing the input text or creating the cipher fails
     /// - The encryption/decryption process encounters an error
-    /// - CBC mode is used without providing an IV
+    /// - A mode of operation is used without providing an IV
+    /// - An unwrapped key fails its integrity check
+    /// - A MAC algorithm is asked to decrypt
     pub fn process(&self) -> CipherResult<String> {
-        if self.algorithm.requires_iv() {
-            self.process_cbc()
+        if self.algorithm.is_key_wrap() {
+            self.process_key_wrap()
+        } else if self.algorithm.is_mac() {
+            self.process_mac()
+        } else if self.algorithm.requires_iv() {
+            self.process_mode()
         } else {
             self.process_ecb()
         }
@@ -54,13 +60,12 @@ impl CipherContext {
         self.execute_ecb(cipher.as_ref(), &text_bytes)
     }
 
-    fn process_cbc(&self) -> CipherResult<String> {
-        let iv = self
-            .iv
-            .as_ref()
-            .ok_or_else(|| CipherError::InvalidPadding("CBC mode requires an IV".into()))?;
+    fn process_mode(&self) -> CipherResult<String> {
+        let iv = self.iv.as_ref().ok_or_else(|| {
+            CipherError::InvalidPadding(format!("{} requires an IV", self.algorithm))
+        })?;
 
-        let cipher = self.algorithm.new_cbc_cipher(&self.key, iv)?;
+        let cipher = self.algorithm.new_mode_cipher(&self.key, iv)?;
 
         match self.operation {
             OperationMode::Encrypt => {
@@ -77,6 +82,44 @@ impl CipherContext {
         }
     }
 
+    /// Wraps the input key (encrypt) or unwraps a hex-encoded wrapped key (decrypt).
+    ///
+    /// The key to wrap is parsed like a block (hex, binary or ASCII) but keeps
+    /// its own length instead of being padded to a block.
+    fn process_key_wrap(&self) -> CipherResult<String> {
+        match self.operation {
+            OperationMode::Encrypt => {
+                let key_data = parse_block_bytes(&self.input_text)?;
+                let wrapped = self.algorithm.wrap_key(&self.key, &key_data)?;
+                Ok(format!("{:X}", Output::from(wrapped)))
+            }
+            OperationMode::Decrypt => {
+                let wrapped = parse_hex(&self.input_text)?;
+                let key_data = self.algorithm.unwrap_key(&self.key, &wrapped)?;
+                Ok(self.output_format.format(&Output::from(key_data)))
+            }
+        }
+    }
+
+    /// Computes the hex-encoded tag over the input text.
+    ///
+    /// A MAC cannot be reversed, so decrypting is an error; tags are checked
+    /// with [`Algorithm::verify_mac`].
+    fn process_mac(&self) -> CipherResult<String> {
+        match self.operation {
+            OperationMode::Encrypt => {
+                let tag = self
+                    .algorithm
+                    .compute_mac(&self.key, self.input_text.as_bytes())?;
+                Ok(format!("{:X}", Output::from(tag)))
+            }
+            OperationMode::Decrypt => Err(CipherError::UnsupportedOperation(format!(
+                "{} cannot decrypt; verify the tag instead",
+                self.algorithm
+            ))),
+        }
+    }
+
     fn execute_ecb(&self, cipher: &dyn BlockCipher, text_bytes: &[u8]) -> CipherResult<String> {
         match self.operation {
             OperationMode::Encrypt => {
@@ -1,5 +1,5 @@
 use cipher_factory::{Algorithm, CipherContext, OperationMode, OutputFormat};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Parser)]
@@ -7,21 +7,38 @@ use std::path::PathBuf;
 pub struct Args {
     /// Operation to perform
     #[arg(value_name = "OPERATION")]
-    pub operation: OperationMode,
+    pub operation: Operation,
 
     /// Encryption algorithm
     #[arg(short, long)]
     pub algorithm: Algorithm,
 
-    /// Key used for encryption/decryption (hex string, e.g., 0x2b7e...)
+    /// Key used for encryption/decryption (hex string, e.g., 0x2b7e...).
+    /// AES accepts 128, 192 or 256-bit keys; AES-XTS takes a 256 or 512-bit
+    /// data key followed by tweak key. For wrap/unwrap this is the
+    /// key-encryption key.
     #[arg(short, long, required = true)]
     pub key: String,
 
-    /// Initialization vector for CBC mode (hex string, e.g., 0x0001...)
+    /// Initialization vector for CBC, CTR, CFB and OFB modes (hex string, e.g., 0x0001...).
+    /// Must be one block long: 8 bytes for DES, 16 bytes for AES.
     #[arg(long)]
     pub iv: Option<String>,
 
-    /// The text to encrypt/decrypt (use --input-file for file input)
+    /// Sector (data unit) size in bytes for AES-XTS
+    #[arg(long, default_value_t = 512)]
+    pub sector_size: usize,
+
+    /// Tag to check with verify (hex string, e.g., 0x070a...)
+    #[arg(long, required_if_eq("operation", "verify"))]
+    pub tag: Option<String>,
+
+    /// Data-unit number of the first sector for AES-XTS
+    #[arg(long, default_value_t = 0)]
+    pub first_sector: u64,
+
+    /// The text to encrypt/decrypt, the key to wrap, or the message to
+    /// authenticate (use --input-file for file input)
     #[arg(value_name = "TEXT", required_unless_present = "input_file")]
     pub text: Option<String>,
 
@@ -36,6 +53,67 @@ pub struct Args {
     /// Output format for decrypted data
     #[arg(short = 'f', long)]
     pub output_format: Option<OutputFormat>,
+
+    /// Print every round step of a single-block AES encryption, as a
+    /// FIPS-197 Appendix B table or, with --trace=json, as JSON
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "text"
+    )]
+    pub trace: Option<TraceFormat>,
+}
+
+/// How `--trace` prints the round steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TraceFormat {
+    /// 4x4 state matrices laid out like FIPS-197 Appendix B
+    Text,
+    /// The trace as a JSON document
+    Json,
+}
+
+/// Operation to perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Operation {
+    /// Transform plaintext to ciphertext
+    Encrypt,
+    /// Transform ciphertext to plaintext
+    Decrypt,
+    /// Wrap a key with aes-kw or aes-kwp
+    Wrap,
+    /// Unwrap a key and verify its integrity with aes-kw or aes-kwp
+    Unwrap,
+    /// Compute a message tag with aes-cmac or des-cmac
+    Mac,
+    /// Check a message tag given by --tag with aes-cmac or des-cmac
+    Verify,
+}
+
+impl Operation {
+    /// Returns the cipher direction: wrapping and computing a tag encrypt,
+    /// unwrapping and verifying a tag decrypt.
+    #[must_use]
+    pub const fn mode(self) -> OperationMode {
+        match self {
+            Self::Encrypt | Self::Wrap | Self::Mac => OperationMode::Encrypt,
+            Self::Decrypt | Self::Unwrap | Self::Verify => OperationMode::Decrypt,
+        }
+    }
+
+    /// Returns whether this is a key wrap operation.
+    #[must_use]
+    pub const fn is_key_wrap(self) -> bool {
+        matches!(self, Self::Wrap | Self::Unwrap)
+    }
+
+    /// Returns whether this is a MAC operation.
+    #[must_use]
+    pub const fn is_mac(self) -> bool {
+        matches!(self, Self::Mac | Self::Verify)
+    }
 }
 
 impl Args {
@@ -44,7 +122,7 @@ impl Args {
     pub fn into_context(self, input_text: String) -> CipherContext {
         CipherContext {
             algorithm: self.algorithm,
-            operation: self.operation,
+            operation: self.operation.mode(),
             key: self.key,
             iv: self.iv,
             input_text,
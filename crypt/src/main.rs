@@ -1,21 +1,53 @@
 mod args;
 
-use crate::args::Args;
-use aes::{AesCbc, Block128, Iv};
-use cipher_factory::{Algorithm, OperationMode};
+use crate::args::{Args, Operation, TraceFormat};
+use cipher_core::{CipherMode, parse_block_bytes};
+use cipher_factory::OperationMode;
 use clap::Parser;
 use color_eyre::eyre::{Result, eyre};
 use std::fs::{self, File};
-use std::io::{Write, stdout};
-use std::str::FromStr;
+use std::io::{BufReader, BufWriter, Read, Write, stdout};
 
 fn main() -> Result<()> {
     color_eyre::install()?;
     let args = Args::parse();
 
-    // Check if we're doing file-based CBC operation
-    if args.input_file.is_some() && args.algorithm == Algorithm::AesCbc {
-        process_cbc_file(&args)?;
+    if args.operation.is_key_wrap() && !args.algorithm.is_key_wrap() {
+        return Err(eyre!("wrap and unwrap need --algorithm aes-kw or aes-kwp"));
+    }
+    if args.algorithm.is_key_wrap() && !args.operation.is_key_wrap() {
+        return Err(eyre!(
+            "{} only wraps keys; use wrap or unwrap",
+            args.algorithm
+        ));
+    }
+    if args.operation.is_mac() && !args.algorithm.is_mac() {
+        return Err(eyre!(
+            "mac and verify need --algorithm aes-cmac or des-cmac"
+        ));
+    }
+    if args.algorithm.is_mac() && !args.operation.is_mac() {
+        return Err(eyre!(
+            "{} only authenticates messages; use mac or verify",
+            args.algorithm
+        ));
+    }
+
+    if args.trace.is_some() && args.operation != Operation::Encrypt {
+        return Err(eyre!("--trace only works with encrypt"));
+    }
+
+    // Check if we're doing a file-based mode-of-operation
+    if let Some(format) = args.trace {
+        process_trace(&args, format)?;
+    } else if args.algorithm.is_key_wrap() && args.input_file.is_some() {
+        process_key_wrap_file(&args)?;
+    } else if args.algorithm.is_mac() {
+        process_mac(&args)?;
+    } else if args.algorithm.is_sector_mode() {
+        process_sector_file(&args)?;
+    } else if args.input_file.is_some() && args.algorithm.requires_iv() {
+        process_mode_file(&args)?;
     } else {
         process_text(&args)?;
     }
@@ -38,7 +70,7 @@ fn process_text(args: &Args) -> Result<()> {
     Ok(())
 }
 
-fn process_cbc_file(args: &Args) -> Result<()> {
+fn process_mode_file(args: &Args) -> Result<()> {
     let input_path = args
         .input_file
         .as_ref()
@@ -46,14 +78,14 @@ fn process_cbc_file(args: &Args) -> Result<()> {
     let iv_str = args
         .iv
         .as_ref()
-        .ok_or_else(|| eyre!("CBC mode requires --iv"))?;
+        .ok_or_else(|| eyre!("{} requires --iv", args.algorithm))?;
 
-    let key = Block128::from_str(&args.key).map_err(|e| eyre!("Invalid key: {e}"))?;
-    let iv = Iv::from_str(iv_str).map_err(|e| eyre!("Invalid IV: {e}"))?;
+    let cipher = args
+        .algorithm
+        .new_mode_cipher(&args.key, iv_str)
+        .map_err(|e| eyre!("Invalid key or IV: {e}"))?;
 
-    let cipher = AesCbc::new(key, iv);
-
-    match args.operation {
+    match args.operation.mode() {
         OperationMode::Encrypt => {
             let plaintext = fs::read(input_path)?;
             let ciphertext = cipher
@@ -65,12 +97,7 @@ fn process_cbc_file(args: &Args) -> Result<()> {
                 write_output(args, &ciphertext)?;
             } else {
                 // Write hex to stdout
-                let hex = ciphertext.iter().fold(String::new(), |mut acc, b| {
-                    use std::fmt::Write;
-                    let _ = write!(acc, "{b:02X}");
-                    acc
-                });
-                println!("{hex}");
+                println!("{}", to_hex(&ciphertext));
             }
         }
         OperationMode::Decrypt => {
@@ -86,6 +113,153 @@ fn process_cbc_file(args: &Args) -> Result<()> {
     Ok(())
 }
 
+/// Wraps or unwraps a key stored as raw bytes, such as an HSM export.
+///
+/// Output goes to `--output-file` as raw bytes, or to stdout as hex.
+fn process_key_wrap_file(args: &Args) -> Result<()> {
+    let input_path = args
+        .input_file
+        .as_ref()
+        .ok_or_else(|| eyre!("No input file"))?;
+    let input = fs::read(input_path)?;
+
+    let output = match args.operation.mode() {
+        OperationMode::Encrypt => args.algorithm.wrap_key(&args.key, &input),
+        OperationMode::Decrypt => args.algorithm.unwrap_key(&args.key, &input),
+    }
+    .map_err(|e| eyre!("{} failed: {e}", args.algorithm))?;
+
+    if args.output_file.is_some() {
+        write_output(args, &output)?;
+    } else {
+        println!("{}", to_hex(&output));
+    }
+    Ok(())
+}
+
+/// Computes or verifies the tag over TEXT or the raw bytes of `--input-file`.
+///
+/// A computed tag goes to `--output-file` as raw bytes, or to stdout as hex.
+fn process_mac(args: &Args) -> Result<()> {
+    let message = match (&args.text, &args.input_file) {
+        (Some(text), None) => text.clone().into_bytes(),
+        (None, Some(path)) => fs::read(path)?,
+        (Some(_), Some(_)) => return Err(eyre!("Cannot specify both TEXT and --input-file")),
+        (None, None) => return Err(eyre!("Must specify TEXT or --input-file")),
+    };
+
+    match args.operation.mode() {
+        OperationMode::Encrypt => {
+            let tag = args
+                .algorithm
+                .compute_mac(&args.key, &message)
+                .map_err(|e| eyre!("{} failed: {e}", args.algorithm))?;
+
+            if args.output_file.is_some() {
+                write_output(args, &tag)?;
+            } else {
+                println!("{}", to_hex(&tag));
+            }
+        }
+        OperationMode::Decrypt => {
+            let tag = args
+                .tag
+                .as_deref()
+                .ok_or_else(|| eyre!("verify requires --tag"))?;
+            let tag = parse_block_bytes(tag).map_err(|e| eyre!("Invalid tag: {e}"))?;
+            args.algorithm
+                .verify_mac(&args.key, &message, &tag)
+                .map_err(|e| eyre!("{} failed: {e}", args.algorithm))?;
+            println!("OK");
+        }
+    }
+    Ok(())
+}
+
+/// Encrypts a single AES block and prints every round step instead of just
+/// the ciphertext.
+fn process_trace(args: &Args, format: TraceFormat) -> Result<()> {
+    let text = match (&args.text, &args.input_file) {
+        (Some(text), None) => text.clone(),
+        (None, Some(path)) => fs::read_to_string(path)?.trim().to_string(),
+        (Some(_), Some(_)) => return Err(eyre!("Cannot specify both TEXT and --input-file")),
+        (None, None) => return Err(eyre!("Must specify TEXT or --input-file")),
+    };
+
+    let trace = args
+        .algorithm
+        .trace_encrypt(&args.key, &text)
+        .map_err(|e| eyre!("Trace failed: {e}"))?;
+
+    let output = match format {
+        TraceFormat::Text => trace.to_string(),
+        TraceFormat::Json => serde_json::to_string_pretty(&trace)?,
+    };
+    write_output(args, output.as_bytes())
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().fold(String::new(), |mut acc, b| {
+        use std::fmt::Write;
+        let _ = write!(acc, "{b:02X}");
+        acc
+    })
+}
+
+/// Encrypts or decrypts a raw image one sector at a time, so the whole
+/// image never has to fit in memory.
+fn process_sector_file(args: &Args) -> Result<()> {
+    let input_path = args
+        .input_file
+        .as_ref()
+        .ok_or_else(|| eyre!("{} works on images; specify --input-file", args.algorithm))?;
+
+    let cipher = args
+        .algorithm
+        .new_xts_cipher(&args.key)
+        .map_err(|e| eyre!("Invalid key: {e}"))?;
+
+    let mut reader = BufReader::new(File::open(input_path)?);
+    let mut writer: Box<dyn Write> = match &args.output_file {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(stdout())),
+    };
+
+    let mut sector = vec![0; args.sector_size];
+    let mut sector_number = u128::from(args.first_sector);
+    loop {
+        let len = read_sector(&mut reader, &mut sector)?;
+        if len == 0 {
+            break;
+        }
+
+        let data = &mut sector[..len];
+        match args.operation.mode() {
+            OperationMode::Encrypt => cipher.encrypt_sector(sector_number, data),
+            OperationMode::Decrypt => cipher.decrypt_sector(sector_number, data),
+        }
+        .map_err(|e| eyre!("Sector {sector_number} failed: {e}"))?;
+
+        writer.write_all(data)?;
+        sector_number += 1;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Fills `buf` from `reader`, returning fewer bytes only at end of input.
+fn read_sector(reader: &mut impl Read, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
 fn write_output(args: &Args, data: &[u8]) -> Result<()> {
     if let Some(path) = &args.output_file {
         let mut file = File::create(path)?;
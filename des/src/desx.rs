@@ -0,0 +1,152 @@
+//! DESX: DES with key whitening.
+
+use crate::{
+    Des,
+    key::{DesxKey, Key},
+};
+use cipher_core::{BlockCipher, CipherAction, CipherError, CipherResult, Output};
+use std::fmt::Debug;
+
+/// DESX, which XORs a whitening key into the block before and after DES.
+///
+/// Encryption is `K2 ^ E_K(P ^ K1)`, where K is the DES key and K1 and K2 are
+/// the pre- and post-whitening keys of a [`DesxKey`]. The core cipher is
+/// unchanged, but an exhaustive key search now has to cover the whitening
+/// keys as well, so brute force costs far more than the 56-bit DES key alone.
+///
+/// Wrap it in a mode of operation such as [`Cbc`](cipher_core::Cbc) to
+/// encrypt whole messages.
+///
+/// # Example
+///
+/// ```
+/// use cipher_core::BlockCipher;
+/// use des::{Desx, DesxKey};
+///
+/// let key = [0x1334_5779_9BBC_DFF1_u64, 0xA1B2_C3D4_E5F6_0718, 0xF0E1_D2C3_B4A5_9687]
+///     .map(u64::to_be_bytes)
+///     .concat();
+/// let cipher = Desx::new(DesxKey::from_slice(&key).unwrap());
+/// let ciphertext = cipher.encrypt(&0x0123_4567_89AB_CDEF_u64.to_be_bytes()).unwrap();
+/// assert_eq!(ciphertext.as_slice(), 0x7150_2555_6016_62F5_u64.to_be_bytes());
+/// ```
+pub struct Desx {
+    des: Des,
+    pre_whitening: Key,
+    post_whitening: Key,
+}
+
+impl Desx {
+    /// Creates a new DESX cipher with the given 24-byte key.
+    #[must_use]
+    pub fn new(key: impl Into<DesxKey>) -> Self {
+        let key = key.into();
+        Self {
+            des: Des::new(key.des_key()),
+            pre_whitening: key.pre_whitening(),
+            post_whitening: key.post_whitening(),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn from_key(key: impl Into<DesxKey>) -> Self {
+        Self::new(key)
+    }
+}
+
+impl BlockCipher for Desx {
+    fn block_size(&self) -> usize {
+        8
+    }
+
+    fn transform_impl(&self, block: &[u8], action: CipherAction) -> CipherResult<Output> {
+        let block_size = self.block_size();
+        let block: [u8; 8] = block
+            .try_into()
+            .map_err(|_| CipherError::invalid_block_size(block_size, block.len()))?;
+
+        let (input_key, output_key) = match action {
+            CipherAction::Encrypt => (&self.pre_whitening, &self.post_whitening),
+            CipherAction::Decrypt => (&self.post_whitening, &self.pre_whitening),
+        };
+        let whitened = xor(block, input_key);
+        let output = self.des.transform(&whitened, action)?;
+        let output: [u8; 8] = output.as_slice().try_into().expect("8-byte DES block");
+        Ok(Output::new(&xor(output, output_key)))
+    }
+}
+
+/// Omits the keys.
+impl Debug for Desx {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Desx[REDACTED]")
+    }
+}
+
+const fn xor(block: [u8; 8], key: &Key) -> [u8; 8] {
+    (u64::from_be_bytes(block) ^ key.as_u64()).to_be_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_ok};
+
+    const DES_KEY: u64 = 0x1334_5779_9BBC_DFF1;
+    const PRE_WHITENING: u64 = 0xA1B2_C3D4_E5F6_0718;
+    const POST_WHITENING: u64 = 0xF0E1_D2C3_B4A5_9687;
+
+    fn key(des: u64, pre: u64, post: u64) -> DesxKey {
+        let bytes = [des, pre, post].map(u64::to_be_bytes).concat();
+        assert_ok!(DesxKey::from_slice(&bytes))
+    }
+
+    #[test]
+    fn zero_whitening_is_des() {
+        let desx = Desx::new(key(DES_KEY, 0, 0));
+        let block = 0x0123_4567_89AB_CDEF_u64.to_be_bytes();
+        let expected = assert_ok!(Des::new(DES_KEY).encrypt(&block));
+        assert_eq!(assert_ok!(desx.encrypt(&block)), expected);
+    }
+
+    #[test]
+    fn whitening_wraps_des() {
+        let desx = Desx::new(key(DES_KEY, PRE_WHITENING, POST_WHITENING));
+        let plaintext = 0x0123_4567_89AB_CDEF_u64;
+
+        let inner =
+            assert_ok!(Des::new(DES_KEY).encrypt(&(plaintext ^ PRE_WHITENING).to_be_bytes()));
+        let inner = u64::from_be_bytes(inner.as_slice().try_into().expect("8 bytes"));
+        let ciphertext = assert_ok!(desx.encrypt(&plaintext.to_be_bytes()));
+        assert_eq!(
+            ciphertext.as_slice(),
+            (inner ^ POST_WHITENING).to_be_bytes()
+        );
+        assert_eq!(
+            assert_ok!(desx.decrypt(&ciphertext)).as_slice(),
+            plaintext.to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn key_parts() {
+        let key = key(DES_KEY, PRE_WHITENING, POST_WHITENING);
+        assert_eq!(key.des_key().as_u64(), DES_KEY);
+        assert_eq!(key.pre_whitening().as_u64(), PRE_WHITENING);
+        assert_eq!(key.post_whitening().as_u64(), POST_WHITENING);
+    }
+
+    #[test]
+    fn invalid_key_size() {
+        let err = assert_err!(DesxKey::from_slice(&[0; 16]));
+        assert_eq!(err, CipherError::invalid_key_size(24, 16));
+    }
+
+    #[test]
+    fn debug_omits_keys() {
+        let key = key(DES_KEY, PRE_WHITENING, POST_WHITENING);
+        assert_eq!(format!("{key:?}"), "DesxKey([REDACTED])");
+        assert_eq!(format!("{:?}", Desx::new(key)), "Desx[REDACTED]");
+    }
+}
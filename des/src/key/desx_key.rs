@@ -0,0 +1,79 @@
+use cipher_core::{CipherError, CipherResult};
+use std::fmt::Debug;
+use zeroize::ZeroizeOnDrop;
+
+use crate::key::Key;
+
+/// 184-bit Key for DESX
+///
+/// Stored as 24 bytes: the DES key (56 of its 64 bits are used), then the
+/// 64-bit pre-whitening key and the 64-bit post-whitening key. This is the
+/// layout of RSA BSAFE and OpenSSL's `desx`.
+#[derive(ZeroizeOnDrop)]
+pub struct DesxKey([u8; 24]);
+
+impl DesxKey {
+    #[inline]
+    #[must_use]
+    pub const fn from_array(bytes: [u8; 24]) -> Self {
+        Self(bytes)
+    }
+
+    /// Creates a key from exactly 24 bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` if `bytes` is not 24 bytes long.
+    pub fn from_slice(bytes: &[u8]) -> CipherResult<Self> {
+        let bytes = bytes
+            .try_into()
+            .map_err(|_| CipherError::invalid_key_size(24, bytes.len()))?;
+        Ok(Self(bytes))
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn as_array(&self) -> &[u8; 24] {
+        &self.0
+    }
+
+    /// Returns the DES key.
+    #[must_use]
+    pub fn des_key(&self) -> Key {
+        self.part(0)
+    }
+
+    /// Returns the key combined with the plaintext by XOR before DES.
+    #[must_use]
+    pub fn pre_whitening(&self) -> Key {
+        self.part(1)
+    }
+
+    /// Returns the key combined with the DES output by XOR.
+    #[must_use]
+    pub fn post_whitening(&self) -> Key {
+        self.part(2)
+    }
+
+    fn part(&self, idx: usize) -> Key {
+        Key::from(&self.0[8 * idx..8 * (idx + 1)])
+    }
+}
+
+impl From<[u8; 24]> for DesxKey {
+    fn from(bytes: [u8; 24]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for DesxKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Debug for DesxKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DesxKey([REDACTED])")
+    }
+}
@@ -1,9 +1,10 @@
 mod cd56;
 mod des_key;
+mod desx_key;
 mod half28;
 mod key56;
 mod subkey;
 mod subkeys;
 
 use cipher_core::secret_key;
-pub use {des_key::Key, subkey::Subkey, subkeys::Subkeys};
+pub use {des_key::Key, desx_key::DesxKey, subkey::Subkey, subkeys::Subkeys};
@@ -3,6 +3,10 @@
 //! Provides the classic DES block cipher with 64-bit keys and blocks.
 //! Uses 16 Feistel rounds with 48-bit subkeys.
 //!
+//! [`TripleDes`] chains three DES operations (encrypt–decrypt–encrypt) with
+//! two or three independent keys, and [`Desx`] XORs whitening keys around
+//! a single DES operation.
+//!
 //! # Example
 //! ```
 //! use des::Des;
@@ -15,7 +19,16 @@
 mod block;
 pub mod constants;
 mod des;
+mod desx;
 mod key;
+mod triple_des;
 pub mod utils;
 
-pub use {block::Block64, block::LR, des::Des};
+pub use {
+    block::Block64,
+    block::LR,
+    des::Des,
+    desx::Desx,
+    key::{DesxKey, Key},
+    triple_des::TripleDes,
+};
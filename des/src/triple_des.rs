@@ -0,0 +1,169 @@
+//! Triple DES (TDEA, NIST SP 800-67) in encrypt–decrypt–encrypt form.
+
+use crate::{Des, key::Key};
+use cipher_core::{BlockCipher, CipherAction, CipherError, CipherResult, Output};
+use std::fmt::Debug;
+
+/// Triple DES with the keying options of NIST SP 800-67.
+///
+/// Encryption is `E_K3(D_K2(E_K1(P)))` and decryption is
+/// `D_K1(E_K2(D_K3(C)))`. A 24-byte key supplies three independent keys
+/// (three-key 3DES, `des-ede3`); a 16-byte key supplies K1 and K2 and reuses
+/// K1 as K3 (two-key 3DES). With K1 = K2 = K3 the cipher is single DES.
+///
+/// # Example
+///
+/// ```
+/// use cipher_core::BlockCipher;
+/// use des::TripleDes;
+///
+/// let key = [0x0123_4567_89AB_CDEF_u64, 0x2345_6789_ABCD_EF01, 0x4567_89AB_CDEF_0123]
+///     .map(u64::to_be_bytes)
+///     .concat();
+/// let cipher = TripleDes::from_slice(&key).unwrap();
+/// let ciphertext = cipher.encrypt(b"The quic").unwrap();
+/// assert_eq!(cipher.decrypt(&ciphertext).unwrap().as_slice(), b"The quic");
+/// ```
+pub struct TripleDes {
+    k1: Des,
+    k2: Des,
+    k3: Des,
+}
+
+impl TripleDes {
+    /// Two-key 3DES key length in bytes.
+    pub const TWO_KEY_SIZE: usize = 16;
+    /// Three-key 3DES key length in bytes.
+    pub const THREE_KEY_SIZE: usize = 24;
+
+    /// Creates three-key 3DES from K1, K2 and K3.
+    #[must_use]
+    pub fn new(k1: impl Into<Key>, k2: impl Into<Key>, k3: impl Into<Key>) -> Self {
+        Self {
+            k1: Des::new(k1),
+            k2: Des::new(k2),
+            k3: Des::new(k3),
+        }
+    }
+
+    /// Creates two-key 3DES from K1 and K2, with K3 = K1.
+    #[must_use]
+    pub fn with_two_keys(k1: impl Into<Key>, k2: impl Into<Key>) -> Self {
+        let k1 = k1.into();
+        let k3 = Key::from_array(*k1.as_array());
+        Self::new(k1, k2, k3)
+    }
+
+    /// Creates 3DES from a 16-byte (two-key) or 24-byte (three-key) key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CipherError::InvalidKeySize` if `key` is not 16 or 24 bytes.
+    pub fn from_slice(key: &[u8]) -> CipherResult<Self> {
+        match key.len() {
+            Self::TWO_KEY_SIZE => Ok(Self::with_two_keys(&key[..8], &key[8..])),
+            Self::THREE_KEY_SIZE => Ok(Self::new(&key[..8], &key[8..16], &key[16..])),
+            len => {
+                let expected = if len < Self::TWO_KEY_SIZE {
+                    Self::TWO_KEY_SIZE
+                } else {
+                    Self::THREE_KEY_SIZE
+                };
+                Err(CipherError::invalid_key_size(expected, len))
+            }
+        }
+    }
+}
+
+impl BlockCipher for TripleDes {
+    fn block_size(&self) -> usize {
+        8
+    }
+
+    fn transform_impl(&self, block: &[u8], action: CipherAction) -> CipherResult<Output> {
+        match action {
+            CipherAction::Encrypt => {
+                let block = self.k1.encrypt(block)?;
+                let block = self.k2.decrypt(&block)?;
+                self.k3.encrypt(&block)
+            }
+            CipherAction::Decrypt => {
+                let block = self.k3.decrypt(block)?;
+                let block = self.k2.encrypt(&block)?;
+                self.k1.decrypt(&block)
+            }
+        }
+    }
+}
+
+/// Omits the keys.
+impl Debug for TripleDes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TripleDes[REDACTED]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_ok};
+    use rstest::rstest;
+
+    const K1: u64 = 0x0123_4567_89AB_CDEF;
+    const K2: u64 = 0x2345_6789_ABCD_EF01;
+    const K3: u64 = 0x4567_89AB_CDEF_0123;
+
+    #[test]
+    fn equal_keys_are_single_des() {
+        let key = 0x1334_5779_9BBC_DFF1_u64;
+        let cipher = TripleDes::new(key, key, key);
+        let ciphertext = assert_ok!(cipher.encrypt(&0x0123_4567_89AB_CDEF_u64.to_be_bytes()));
+        assert_eq!(
+            ciphertext.as_slice(),
+            0x85E8_1354_0F0A_B405_u64.to_be_bytes()
+        );
+    }
+
+    #[test]
+    fn two_key_reuses_k1() {
+        let key = [K1.to_be_bytes(), K2.to_be_bytes()].concat();
+        let two_key = assert_ok!(TripleDes::from_slice(&key));
+        let three_key = TripleDes::new(K1, K2, K1);
+
+        let block = 0x5468_6520_7175_6663_u64.to_be_bytes();
+        assert_eq!(
+            assert_ok!(two_key.encrypt(&block)),
+            assert_ok!(three_key.encrypt(&block))
+        );
+    }
+
+    #[test]
+    fn three_key_slice_matches_keys() {
+        let key = [K1.to_be_bytes(), K2.to_be_bytes(), K3.to_be_bytes()].concat();
+        let from_slice = assert_ok!(TripleDes::from_slice(&key));
+        let from_keys = TripleDes::new(K1, K2, K3);
+
+        let block = 0x5468_6520_7175_6663_u64.to_be_bytes();
+        let ciphertext = assert_ok!(from_slice.encrypt(&block));
+        assert_eq!(ciphertext, assert_ok!(from_keys.encrypt(&block)));
+        assert_eq!(assert_ok!(from_keys.decrypt(&ciphertext)).as_slice(), block);
+    }
+
+    #[rstest]
+    #[case(0, 16)]
+    #[case(8, 16)]
+    #[case(17, 24)]
+    #[case(32, 24)]
+    fn invalid_key_sizes(#[case] len: usize, #[case] expected: usize) {
+        let err = assert_err!(TripleDes::from_slice(&vec![0; len]));
+        assert_eq!(err, CipherError::invalid_key_size(expected, len));
+    }
+
+    #[test]
+    fn debug_omits_keys() {
+        assert_eq!(
+            format!("{:?}", TripleDes::new(K1, K2, K3)),
+            "TripleDes[REDACTED]"
+        );
+    }
+}
@@ -1,6 +1,11 @@
-use cipher_core::{BlockCipher, Output};
+mod common;
+
+use cipher_core::{
+    BlockCipher, Cbc, Cfb, CfbSegment, CipherMode, Cmac, Ecb, Mac, NoPadding, Ofb, Output,
+};
 use claims::assert_ok;
-use des::Des;
+use common::hex;
+use des::{Des, Desx, DesxKey, TripleDes};
 use rstest::rstest;
 
 const TEST_KEY: u64 = 0x1334_5779_9BBC_DFF1;
@@ -192,6 +197,172 @@ fn different_inputs() {
     );
 }
 
+// FIPS 81 Appendix B/C examples
+const FIPS81_KEY: u64 = 0x0123_4567_89AB_CDEF;
+const FIPS81_IV: u64 = 0x1234_5678_90AB_CDEF;
+const FIPS81_PLAINTEXT: &[u8] = b"Now is the time for all ";
+
+#[test]
+fn fips81_ecb() {
+    let ecb = Ecb::<_, NoPadding>::new(Des::new(FIPS81_KEY));
+    let expected = [
+        0x3FA4_0E8A_984D_4815_u64,
+        0x6A27_1787_AB88_83F9,
+        0x893D_51EC_4B56_3B53,
+    ];
+
+    let ciphertext = assert_ok!(ecb.encrypt(FIPS81_PLAINTEXT));
+    assert_eq!(ciphertext, expected.map(u64::to_be_bytes).concat());
+    assert_eq!(assert_ok!(ecb.decrypt(&ciphertext)), FIPS81_PLAINTEXT);
+}
+
+#[test]
+fn fips81_cbc() {
+    let cbc = assert_ok!(Cbc::<_, NoPadding>::new(
+        Des::new(FIPS81_KEY),
+        &FIPS81_IV.to_be_bytes()
+    ));
+    let expected = [
+        FIPS81_IV,
+        0xE5C7_CDDE_872B_F27C,
+        0x43E9_3400_8C38_9C0F,
+        0x6837_8849_9A7C_05F6,
+    ];
+
+    let ciphertext = assert_ok!(cbc.encrypt(FIPS81_PLAINTEXT));
+    assert_eq!(ciphertext, expected.map(u64::to_be_bytes).concat());
+    assert_eq!(assert_ok!(cbc.decrypt(&ciphertext)), FIPS81_PLAINTEXT);
+}
+
+// FIPS 81 Appendix D: 64-bit and 8-bit CFB; the 1-bit case covers the first 3 bytes
+#[rstest]
+#[case(CfbSegment::Block, "f3096249c7f46e51a69e839b1a92f78403467133898ea622")]
+#[case(CfbSegment::Byte, "f31fda07011462ee187f43d80a7cd9b5b0d290da6e5b9a87")]
+#[case(CfbSegment::Bit, "cd1ec9")]
+fn fips81_cfb(#[case] segment: CfbSegment, #[case] expected: &str) {
+    let cfb = assert_ok!(Cfb::new(
+        Des::new(FIPS81_KEY),
+        &FIPS81_IV.to_be_bytes(),
+        segment
+    ));
+    let expected = hex(expected);
+    let plaintext = &FIPS81_PLAINTEXT[..expected.len()];
+
+    let ciphertext = assert_ok!(cfb.encrypt(plaintext));
+    assert_eq!(ciphertext[..8], FIPS81_IV.to_be_bytes());
+    assert_eq!(ciphertext[8..], expected);
+    assert_eq!(assert_ok!(cfb.decrypt(&ciphertext)), plaintext);
+}
+
+#[test]
+fn fips81_ofb() {
+    let ofb = assert_ok!(Ofb::new(Des::new(FIPS81_KEY), &FIPS81_IV.to_be_bytes()));
+    let expected = [
+        FIPS81_IV,
+        0xF309_6249_C7F4_6E51,
+        0x35F2_4A24_2EEB_3D3F,
+        0x3D6D_5BE3_255A_F8C3,
+    ];
+
+    let ciphertext = assert_ok!(ofb.encrypt(FIPS81_PLAINTEXT));
+    assert_eq!(ciphertext, expected.map(u64::to_be_bytes).concat());
+    assert_eq!(assert_ok!(ofb.decrypt(&ciphertext)), FIPS81_PLAINTEXT);
+}
+
+#[test]
+fn cbc_padded_roundtrip() {
+    let cbc = assert_ok!(Cbc::<_>::new(Des::new(TEST_KEY), &FIPS81_IV.to_be_bytes()));
+    let plaintext = b"The quick brown fox jumps over the lazy dog";
+
+    let ciphertext = assert_ok!(cbc.encrypt(plaintext));
+    assert_eq!(ciphertext.len(), 8 + 48);
+    assert_eq!(assert_ok!(cbc.decrypt(&ciphertext)), plaintext);
+}
+
+// CMAC with a 64-bit block derives its subkeys in GF(2^64). Expected tags
+// were cross-checked against OpenSSL's CMAC with the key repeated as 3DES.
+#[rstest]
+#[case("", "5467455b9d7c5221")]
+#[case("0123456789abcdef", "f51a5facc53221e0")]
+#[case("4e6f77206973207468652074696d6520666f7220616c6c20", "181ae8b92d957225")]
+fn cmac(#[case] message: &str, #[case] expected: &str) {
+    let mut cmac = assert_ok!(Cmac::new(Des::new(TEST_KEY)));
+    assert_eq!(cmac.output_size(), 8);
+    assert_ok!(cmac.update(&hex(message)));
+    assert_eq!(assert_ok!(cmac.finalize()), hex(expected));
+}
+
+// NIST SP 800-67 Rev. 1 Appendix B: three-key 3DES in ECB mode
+#[test]
+fn triple_des_sp800_67_ecb() {
+    let key = [
+        0x0123_4567_89AB_CDEF_u64,
+        0x2345_6789_ABCD_EF01,
+        0x4567_89AB_CDEF_0123,
+    ]
+    .map(u64::to_be_bytes)
+    .concat();
+    let ecb = Ecb::<_, NoPadding>::new(assert_ok!(TripleDes::from_slice(&key)));
+    let plaintext = b"The qufck brown fox jump";
+    let expected = [
+        0xA826_FD8C_E53B_855F_u64,
+        0xCCE2_1C81_1225_6FE6,
+        0x68D5_C05D_D9B6_B900,
+    ];
+
+    let ciphertext = assert_ok!(ecb.encrypt(plaintext));
+    assert_eq!(ciphertext, expected.map(u64::to_be_bytes).concat());
+    assert_eq!(assert_ok!(ecb.decrypt(&ciphertext)), plaintext);
+}
+
+// With the key repeated, 3DES is single DES, so it reproduces the DES-CMAC tags
+#[rstest]
+#[case("", "5467455b9d7c5221")]
+#[case("4e6f77206973207468652074696d6520666f7220616c6c20", "181ae8b92d957225")]
+fn triple_des_repeated_key_cmac(#[case] message: &str, #[case] expected: &str) {
+    let key = TEST_KEY.to_be_bytes().repeat(3);
+    let mut cmac = assert_ok!(Cmac::new(assert_ok!(TripleDes::from_slice(&key))));
+    assert_ok!(cmac.update(&hex(message)));
+    assert_eq!(assert_ok!(cmac.finalize()), hex(expected));
+}
+
+// DESX expected values were cross-checked against OpenSSL's desx-cbc (zero IV
+// for the single block), which uses the same DES key, pre- and post-whitening
+// key layout as RSA BSAFE
+#[test]
+fn desx_ecb() {
+    let key = [TEST_KEY, 0xA1B2_C3D4_E5F6_0718, 0xF0E1_D2C3_B4A5_9687]
+        .map(u64::to_be_bytes)
+        .concat();
+    let desx = Desx::new(assert_ok!(DesxKey::from_slice(&key)));
+
+    let ciphertext = assert_ok!(desx.encrypt(&TEST_PLAINTEXT.to_be_bytes()));
+    assert_eq!(cipher_block_to_u64(&ciphertext), 0x7150_2555_6016_62F5);
+    let plaintext = assert_ok!(desx.decrypt(&ciphertext));
+    assert_eq!(cipher_block_to_u64(&plaintext), TEST_PLAINTEXT);
+}
+
+#[test]
+fn desx_cbc() {
+    let key = [FIPS81_KEY, 0x1011_1213_1415_1617, 0xF0E1_D2C3_B4A5_9687]
+        .map(u64::to_be_bytes)
+        .concat();
+    let cbc = assert_ok!(Cbc::<_, NoPadding>::new(
+        Desx::new(assert_ok!(DesxKey::from_slice(&key))),
+        &FIPS81_IV.to_be_bytes()
+    ));
+    let expected = [
+        FIPS81_IV,
+        0x224E_BE24_94DB_CB34,
+        0xC808_6692_7D42_164F,
+        0xD6E3_FA84_C8A3_B6E6,
+    ];
+
+    let ciphertext = assert_ok!(cbc.encrypt(FIPS81_PLAINTEXT));
+    assert_eq!(ciphertext, expected.map(u64::to_be_bytes).concat());
+    assert_eq!(assert_ok!(cbc.decrypt(&ciphertext)), FIPS81_PLAINTEXT);
+}
+
 fn cipher_block_to_u64(block: &Output) -> u64 {
     let bytes = block.as_slice().try_into().expect("8 bytes");
     u64::from_be_bytes(bytes)
@@ -1,6 +1,8 @@
 use crate::pages::{
-    aes::AesPage, aes_cbc::AesCbcPage, des::DesPage, footer::Footer, header::Header, home::Home,
-    not_found::NotFound,
+    aes::AesPage, aes_cbc::AesCbcPage, aes_cmac::AesCmacPage, aes_ctr::AesCtrPage,
+    aes_ctr128::AesCtr128Page, aes_ofb::AesOfbPage, des::DesPage, des_cbc::DesCbcPage,
+    des_cmac::DesCmacPage, des_ofb::DesOfbPage, footer::Footer, header::Header, home::Home,
+    not_found::NotFound, triple_des::TripleDesPage,
 };
 use leptos::prelude::*;
 use leptos_meta::{MetaTags, Stylesheet, Title, provide_meta_context};
@@ -49,8 +51,16 @@ pub fn App() -> impl IntoView {
                     <Routes fallback=|| view! { <NotFound /> }>
                         <Route path=StaticSegment("/") view=Home />
                         <Route path=StaticSegment("/des") view=DesPage />
+                        <Route path=StaticSegment("/des-cbc") view=DesCbcPage />
+                        <Route path=StaticSegment("/des-ofb") view=DesOfbPage />
+                        <Route path=StaticSegment("/des-cmac") view=DesCmacPage />
+                        <Route path=StaticSegment("/3des") view=TripleDesPage />
                         <Route path=StaticSegment("/aes") view=AesPage />
                         <Route path=StaticSegment("/aes-cbc") view=AesCbcPage />
+                        <Route path=StaticSegment("/aes-ctr") view=AesCtrPage />
+                        <Route path=StaticSegment("/aes-ctr128") view=AesCtr128Page />
+                        <Route path=StaticSegment("/aes-ofb") view=AesOfbPage />
+                        <Route path=StaticSegment("/aes-cmac") view=AesCmacPage />
                     </Routes>
                 </main>
                 <Footer />
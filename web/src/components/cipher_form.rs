@@ -22,10 +22,7 @@ pub fn CipherForm(algorithm: Algorithm) -> AnyView {
 
     let (copy_feedback, set_copy_feedback) = signal(false);
 
-    let key_size = match algorithm {
-        Algorithm::Des => KeySize::Des,
-        Algorithm::Aes | Algorithm::AesCbc => KeySize::Aes128,
-    };
+    let key_size = KeySize::for_algorithm(algorithm);
 
     let handle_submit = move || {
         set_error_msg(String::new());
@@ -13,7 +13,7 @@ use wasm_bindgen::JsCast;
 use web_sys::{Blob, Url};
 
 #[component]
-pub fn CipherFormCbc() -> AnyView {
+pub fn CipherFormMode(algorithm: Algorithm) -> AnyView {
     let (mode, set_mode) = signal(OperationMode::Encrypt);
     let (output_fmt, set_output_fmt) = signal(OutputFormat::Hex);
 
@@ -32,6 +32,9 @@ pub fn CipherFormCbc() -> AnyView {
     let (error_msg, set_error_msg) = signal(String::new());
     let (copy_feedback, set_copy_feedback) = signal(false);
 
+    let key_size = KeySize::for_algorithm(algorithm);
+    let iv_size = algorithm.block_size();
+
     let is_decrypt_mode = Memo::new(move |_| mode.get() == OperationMode::Decrypt);
 
     let handle_submit = move || {
@@ -91,7 +94,7 @@ pub fn CipherFormCbc() -> AnyView {
         // Process encryption/decryption
         match mode.get() {
             OperationMode::Encrypt => {
-                match Algorithm::AesCbc.encrypt_cbc(&key, &formatted_iv, &input_data) {
+                match algorithm.encrypt_mode(&key, &formatted_iv, &input_data) {
                     Ok(ciphertext) => {
                         let hex_output = bytes_to_hex(&ciphertext);
                         set_output(hex_output);
@@ -100,7 +103,7 @@ pub fn CipherFormCbc() -> AnyView {
                     Err(e) => set_error_msg(e.to_string()),
                 }
             }
-            OperationMode::Decrypt => match Algorithm::AesCbc.decrypt_cbc(&key, &input_data) {
+            OperationMode::Decrypt => match algorithm.decrypt_mode(&key, &input_data) {
                 Ok(plaintext) => {
                     set_output_bytes(Some(plaintext.clone()));
                     let formatted = match output_fmt.get() {
@@ -153,7 +156,7 @@ pub fn CipherFormCbc() -> AnyView {
     view! {
         <div class="cipher-card">
             <div class="card-header">
-                <h2>"AES-CBC"</h2>
+                <h2>{algorithm.to_string()}</h2>
             </div>
             <ConfigurationSection
                 mode=mode
@@ -161,10 +164,13 @@ pub fn CipherFormCbc() -> AnyView {
                 output_fmt=output_fmt
                 update_output=update_output
             />
-            <KeyInput key_input=key_input set_key_input=set_key_input key_size=KeySize::Aes128 />
+            <KeyInput key_input=key_input set_key_input=set_key_input key_size=key_size />
             {move || {
                 if mode.get() == OperationMode::Encrypt {
-                    view! { <IvInput iv_input=iv_input set_iv_input=set_iv_input /> }.into_any()
+                    view! {
+                        <IvInput iv_input=iv_input set_iv_input=set_iv_input iv_size=iv_size />
+                    }
+                        .into_any()
                 } else {
                     view! { <span></span> }.into_any()
                 }
@@ -183,7 +189,7 @@ pub fn CipherFormCbc() -> AnyView {
             />
 
             <button class="btn-primary" on:click=move |_| handle_submit()>
-                {move || format!("{} using AES-CBC", mode.get())}
+                {move || format!("{} using {algorithm}", mode.get())}
             </button>
 
             // Output section
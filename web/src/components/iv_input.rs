@@ -23,7 +23,13 @@ fn bytes_to_hex(bytes: &[u8]) -> String {
 }
 
 #[component]
-pub fn IvInput(iv_input: ReadSignal<String>, set_iv_input: WriteSignal<String>) -> AnyView {
+pub fn IvInput(
+    iv_input: ReadSignal<String>,
+    set_iv_input: WriteSignal<String>,
+    /// IV size in bytes, one block of the underlying cipher.
+    #[prop(default = 16)]
+    iv_size: usize,
+) -> AnyView {
     let handle_hex_input = move |ev| {
         let val = event_target_value(&ev);
         let cleaned = clean_hex_input(val);
@@ -31,18 +37,24 @@ pub fn IvInput(iv_input: ReadSignal<String>, set_iv_input: WriteSignal<String>)
     };
 
     let generate_random_iv = move |_| {
-        if let Some(bytes) = generate_random_bytes(16) {
+        if let Some(bytes) = generate_random_bytes(iv_size) {
             let hex = bytes_to_hex(&bytes);
             set_iv_input(hex);
         }
     };
 
+    let hint = format!("{iv_size} bytes ({} hex chars)", iv_size * 2);
+    let placeholder = "000102030405060708090A0B0C0D0E0F"
+        .chars()
+        .take(iv_size * 2)
+        .collect::<String>();
+
     view! {
         <div class="form-group">
             <div class="label-header">
                 <label>"Initialization Vector (IV)"</label>
                 <div class="header-actions">
-                    <span class="input-hint">"16 bytes (32 hex chars)"</span>
+                    <span class="input-hint">{hint}</span>
                     <button
                         type="button"
                         class="btn-generate"
@@ -57,11 +69,11 @@ pub fn IvInput(iv_input: ReadSignal<String>, set_iv_input: WriteSignal<String>)
                 <span class="prefix">"0x"</span>
                 <input
                     type="text"
-                    placeholder="000102030405060708090A0B0C0D0E0F"
+                    placeholder=placeholder
                     prop:value=move || iv_input.get()
                     on:input=handle_hex_input
                     spellcheck="false"
-                    maxlength="32"
+                    maxlength=iv_size * 2
                 />
             </div>
         </div>
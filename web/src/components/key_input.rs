@@ -1,5 +1,7 @@
+use cipher_factory::Algorithm;
 use js_sys::Uint8Array;
 use leptos::{prelude::*, tachys::dom::event_target_value};
+use std::{fmt::Display, str::FromStr};
 
 fn generate_random_bytes(len: usize) -> Option<Vec<u8>> {
     let window = web_sys::window()?;
@@ -20,15 +22,81 @@ fn bytes_to_hex(bytes: &[u8]) -> String {
 pub enum KeySize {
     /// DES: 8 bytes (64 bits, though only 56 are used)
     Des,
+    /// Two-key 3DES: 16 bytes (K1 and K2, with K3 = K1)
+    TripleDes2,
+    /// Three-key 3DES: 24 bytes (K1, K2 and K3)
+    TripleDes3,
     /// AES-128: 16 bytes (128 bits)
     Aes128,
+    /// AES-192: 24 bytes (192 bits)
+    Aes192,
+    /// AES-256: 32 bytes (256 bits)
+    Aes256,
 }
 
 impl KeySize {
+    /// Key sizes selectable for AES-based algorithms.
+    const AES: [Self; 3] = [Self::Aes128, Self::Aes192, Self::Aes256];
+
+    /// Key sizes selectable for 3DES.
+    const TRIPLE_DES: [Self; 2] = [Self::TripleDes3, Self::TripleDes2];
+
+    /// Default key size for the given algorithm.
+    pub const fn for_algorithm(algorithm: Algorithm) -> Self {
+        if matches!(algorithm, Algorithm::TripleDes) {
+            Self::TripleDes3
+        } else if algorithm.block_size() == 8 {
+            // DES-based algorithms are the ones with 64-bit blocks
+            Self::Des
+        } else {
+            Self::Aes128
+        }
+    }
+
     const fn bytes(self) -> usize {
         match self {
             Self::Des => 8,
-            Self::Aes128 => 16,
+            Self::TripleDes2 | Self::Aes128 => 16,
+            Self::TripleDes3 | Self::Aes192 => 24,
+            Self::Aes256 => 32,
+        }
+    }
+
+    /// Key sizes offered alongside this one, if there is a choice.
+    const fn choices(self) -> &'static [Self] {
+        match self {
+            Self::Des => &[],
+            Self::TripleDes2 | Self::TripleDes3 => &Self::TRIPLE_DES,
+            Self::Aes128 | Self::Aes192 | Self::Aes256 => &Self::AES,
+        }
+    }
+}
+
+impl Display for KeySize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Des => "DES",
+            Self::TripleDes2 => "3DES-2KEY",
+            Self::TripleDes3 => "3DES-3KEY",
+            Self::Aes128 => "AES-128",
+            Self::Aes192 => "AES-192",
+            Self::Aes256 => "AES-256",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for KeySize {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_ref() {
+            "DES" => Ok(Self::Des),
+            "3DES-2KEY" => Ok(Self::TripleDes2),
+            "3DES-3KEY" => Ok(Self::TripleDes3),
+            "AES-128" => Ok(Self::Aes128),
+            "AES-192" => Ok(Self::Aes192),
+            "AES-256" => Ok(Self::Aes256),
+            _ => Err(()),
         }
     }
 }
@@ -39,19 +107,49 @@ pub fn KeyInput(
     set_key_input: WriteSignal<String>,
     #[prop(default = KeySize::Aes128)] key_size: KeySize,
 ) -> AnyView {
+    let (selected_size, set_selected_size) = signal(key_size);
+
     let generate_random_key = move |_| {
-        if let Some(bytes) = generate_random_bytes(key_size.bytes()) {
+        if let Some(bytes) = generate_random_bytes(selected_size.get().bytes()) {
             let hex = format!("0x{}", bytes_to_hex(&bytes));
             set_key_input(hex);
         }
     };
 
+    let handle_size_change = move |ev| {
+        let size = KeySize::from_str(&event_target_value(&ev)).unwrap_or(key_size);
+        set_selected_size(size);
+    };
+
+    let size_select = if key_size.choices().is_empty() {
+        view! { <span></span> }.into_any()
+    } else {
+        view! {
+            <select
+                class="key-size-select"
+                title="Key size for random keys"
+                on:change=handle_size_change
+                prop:value=move || selected_size.get().to_string()
+            >
+                {key_size
+                    .choices()
+                    .iter()
+                    .map(|size| {
+                        view! { <option value=size.to_string()>{size.to_string()}</option> }
+                    })
+                    .collect_view()}
+            </select>
+        }
+            .into_any()
+    };
+
     view! {
         <div class="form-group">
             <div class="label-header">
                 <label>"Secret Key"</label>
                 <div class="header-actions">
                     <span class="input-hint">"Prefix: 0x (Hex), 0b (Bin), or nothing (Text)"</span>
+                    {size_select}
                     <button
                         type="button"
                         class="btn-generate"
@@ -0,0 +1,149 @@
+use crate::components::{
+    error_box::ErrorBox,
+    key_input::{KeyInput, KeySize},
+    output_box::OutputBox,
+    text_input::clean_hex_input,
+};
+use cipher_core::parse_block_bytes;
+use cipher_factory::prelude::*;
+use leptos::{prelude::*, tachys::dom::event_target_value};
+use std::time::Duration;
+
+/// Form for computing and verifying message tags with a MAC algorithm.
+#[component]
+pub fn MacForm(algorithm: Algorithm) -> AnyView {
+    let (output_fmt, _) = signal(OutputFormat::Hex);
+
+    let (key_input, set_key_input) = signal(String::new());
+    let (text_input, set_text_input) = signal(String::new());
+    let (tag_input, set_tag_input) = signal(String::new());
+
+    let (output, set_output) = signal(String::new());
+    let (verified, set_verified) = signal(false);
+    let (error_msg, set_error_msg) = signal(String::new());
+
+    let (copy_feedback, set_copy_feedback) = signal(false);
+
+    let key_size = KeySize::for_algorithm(algorithm);
+
+    let reset = move || {
+        set_error_msg(String::new());
+        set_output(String::new());
+        set_verified(false);
+    };
+
+    let handle_compute = move || {
+        reset();
+
+        let key = key_input.get();
+        if key.is_empty() {
+            set_error_msg("Please enter a key.".to_string());
+            return;
+        }
+
+        match algorithm.compute_mac(&key, text_input.get().as_bytes()) {
+            Ok(tag) => set_output(tag.iter().map(|b| format!("{b:02X}")).collect()),
+            Err(e) => set_error_msg(e.to_string()),
+        }
+    };
+
+    let handle_verify = move || {
+        reset();
+
+        let key = key_input.get();
+        let tag = tag_input.get();
+        if key.is_empty() || tag.is_empty() {
+            set_error_msg("Please enter both key and tag.".to_string());
+            return;
+        }
+
+        let tag = match parse_block_bytes(&format!("0x{tag}")) {
+            Ok(tag) => tag,
+            Err(e) => {
+                set_error_msg(format!("Invalid tag: {e}"));
+                return;
+            }
+        };
+
+        match algorithm.verify_mac(&key, text_input.get().as_bytes(), &tag) {
+            Ok(()) => set_verified(true),
+            Err(e) => set_error_msg(e.to_string()),
+        }
+    };
+
+    let copy_to_clipboard = move |content: String| {
+        let clipboard = window().navigator().clipboard();
+        let _ = clipboard.write_text(&content);
+        set_copy_feedback(true);
+        set_timeout(move || set_copy_feedback(false), Duration::from_secs(2));
+    };
+
+    view! {
+        <div class="cipher-card">
+            <div class="card-header">
+                <h2>{algorithm.to_string()}</h2>
+            </div>
+            <KeyInput key_input=key_input set_key_input=set_key_input key_size=key_size />
+
+            <div class="form-group">
+                <div class="label-header">
+                    <label>"Message"</label>
+                </div>
+                <div class="input-wrapper standard-input">
+                    <input
+                        type="text"
+                        placeholder="Enter text..."
+                        on:input=move |ev| set_text_input(event_target_value(&ev))
+                        spellcheck="false"
+                    />
+                </div>
+            </div>
+
+            <div class="form-group">
+                <div class="label-header">
+                    <label>"Tag"</label>
+                    <span class="input-hint">"Only needed to verify"</span>
+                </div>
+                <div class="input-wrapper hex-input">
+                    <span class="prefix">"0x"</span>
+                    <input
+                        type="text"
+                        prop:value=move || tag_input.get()
+                        placeholder="001122"
+                        on:input=move |ev| set_tag_input(clean_hex_input(event_target_value(&ev)))
+                        spellcheck="false"
+                    />
+                </div>
+            </div>
+
+            <button class="btn-primary" on:click=move |_| handle_compute()>
+                {format!("Compute tag using {algorithm}")}
+            </button>
+            <button class="btn-primary" on:click=move |_| handle_verify()>
+                {format!("Verify tag using {algorithm}")}
+            </button>
+
+            <OutputBox
+                output=output
+                output_fmt=output_fmt
+                copy_to_clipboard=copy_to_clipboard
+                copy_feedback=copy_feedback
+            />
+            {move || {
+                if verified.get() {
+                    view! {
+                        <div class="result-box">
+                            <code>"Tag verified"</code>
+                        </div>
+                    }
+                        .into_any()
+                } else {
+                    view! { <span></span> }.into_any()
+                }
+            }}
+            <ErrorBox error_msg=error_msg />
+
+        </div>
+    }
+    .into_any()
+}
@@ -1,9 +1,10 @@
 pub mod cipher_form;
-pub mod cipher_form_cbc;
+pub mod cipher_form_mode;
 pub mod config_section;
 pub mod error_box;
 pub mod file_input;
 pub mod iv_input;
 pub mod key_input;
+pub mod mac_form;
 pub mod output_box;
 pub mod text_input;
@@ -1,9 +1,8 @@
-use crate::components::cipher_form_cbc::CipherFormCbc;
+use crate::components::cipher_form_mode::CipherFormMode;
+use cipher_factory::Algorithm;
 use leptos::prelude::*;
 
 #[component]
 pub fn AesCbcPage() -> impl IntoView {
-    view! {
-        <CipherFormCbc />
-    }
+    view! { <CipherFormMode algorithm=Algorithm::AesCbc /> }
 }
@@ -0,0 +1,8 @@
+use crate::components::mac_form::MacForm;
+use cipher_factory::Algorithm;
+use leptos::prelude::*;
+
+#[component]
+pub fn AesCmacPage() -> impl IntoView {
+    view! { <MacForm algorithm=Algorithm::AesCmac /> }
+}
@@ -0,0 +1,8 @@
+use crate::components::cipher_form_mode::CipherFormMode;
+use cipher_factory::Algorithm;
+use leptos::prelude::*;
+
+#[component]
+pub fn AesCtrPage() -> impl IntoView {
+    view! { <CipherFormMode algorithm=Algorithm::AesCtr /> }
+}
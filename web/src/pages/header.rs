@@ -34,12 +34,36 @@ pub fn Header() -> impl IntoView {
                 <li>
                     <A href="/des">"DES"</A>
                 </li>
+                <li>
+                    <A href="/des-cbc">"DES-CBC"</A>
+                </li>
+                <li>
+                    <A href="/des-ofb">"DES-OFB"</A>
+                </li>
+                <li>
+                    <A href="/des-cmac">"DES-CMAC"</A>
+                </li>
+                <li>
+                    <A href="/3des">"3DES"</A>
+                </li>
                 <li>
                     <A href="/aes">"AES"</A>
                 </li>
                 <li>
                     <A href="/aes-cbc">"AES-CBC"</A>
                 </li>
+                <li>
+                    <A href="/aes-ctr">"AES-CTR"</A>
+                </li>
+                <li>
+                    <A href="/aes-ctr128">"AES-CTR128"</A>
+                </li>
+                <li>
+                    <A href="/aes-ofb">"AES-OFB"</A>
+                </li>
+                <li>
+                    <A href="/aes-cmac">"AES-CMAC"</A>
+                </li>
             </ul>
             <button class="theme-toggle" on:click=toggle_theme>
                 {move || theme.get().to_string()}
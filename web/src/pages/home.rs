@@ -17,7 +17,10 @@ pub fn Home() -> impl IntoView {
                     <p>
                         "A legacy algorithm from the 1970s. While historically significant, "
                         "it is now considered insecure due to its short 56-bit key length. "
-                        "This tool provides DES block encryption for educational purposes."
+                        "This tool provides DES block encryption, " <strong>"DES-CBC"</strong>
+                        " and " <strong>"DES-OFB"</strong> " for educational purposes. "
+                        <strong>"3DES"</strong> " runs DES three times (encrypt, decrypt, encrypt) "
+                        "with two or three keys, as legacy payment systems still do."
                     </p>
                 </div>
 
@@ -25,9 +28,12 @@ pub fn Home() -> impl IntoView {
                     <h3>"AES (Advanced Encryption Standard)"</h3>
                     <p>
                         "The modern standard for symmetric encryption. This tool offers "
-                        <strong>"AES-128"</strong> " for single-block operations and "
-                        <strong>"AES-128-CBC"</strong> " for encrypting arbitrary data with "
-                        "PKCS#7 padding."
+                        <strong>"AES-128/192/256"</strong> " for single-block operations and "
+                        <strong>"AES-CBC"</strong> " for encrypting arbitrary data with "
+                        "PKCS#7 padding, and " <strong>"AES-CTR"</strong> " and "
+                        <strong>"AES-OFB"</strong> " as stream ciphers without padding. "
+                        "Unlike CBC, OFB never feeds the data back into the cipher: its keystream "
+                        "depends only on the key and IV. The key length selects the variant."
                     </p>
                 </div>
             </div>
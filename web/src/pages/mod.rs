@@ -1,7 +1,15 @@
 pub mod aes;
 pub mod aes_cbc;
+pub mod aes_cmac;
+pub mod aes_ctr;
+pub mod aes_ctr128;
+pub mod aes_ofb;
 pub mod des;
+pub mod des_cbc;
+pub mod des_cmac;
+pub mod des_ofb;
 pub mod footer;
 pub mod header;
 pub mod home;
 pub mod not_found;
+pub mod triple_des;
@@ -0,0 +1,8 @@
+use crate::components::cipher_form::CipherForm;
+use cipher_factory::Algorithm;
+use leptos::prelude::*;
+
+#[component]
+pub fn TripleDesPage() -> impl IntoView {
+    view! { <CipherForm algorithm=Algorithm::TripleDes /> }
+}